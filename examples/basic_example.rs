@@ -2,7 +2,9 @@ use rlgym_sim_rs::action_parsers::test_parser::TestAction;
 use rlgym_sim_rs::conditionals::common_conditions::{TimeoutCondition, GoalScoredCondition};
 use rlgym_sim_rs::conditionals::extra_conditions::CombinedTerminalConditions;
 use rlgym_sim_rs::TerminalCondition;
-use rlgym_sim_rs::envs::game_match::GameConfig;
+use rlgym_sim_rs::envs::car_bodies::CarBodies;
+use rlgym_sim_rs::envs::game_match::{GameConfig, GameMode, StepMode};
+use rlgym_sim_rs::envs::mutators::Mutators;
 use rlgym_sim_rs::make;
 use rlgym_sim_rs::obs_builders::advanced_obs::AdvancedObs;
 use rlgym_sim_rs::ObsBuilder;
@@ -33,6 +35,11 @@ fn main() {
         gravity: 1.,
         boost_consumption: 1.,
         car_config: CarConfig::octane(),
+        car_bodies: CarBodies::Same,
+        step_mode: StepMode::Final,
+        game_mode: GameMode::Soccar,
+        mutators: Mutators::default(),
+        kickoff_phase: None,
     };
 
     let game_config = make::MakeConfig {
@@ -43,6 +50,12 @@ fn main() {
         use_single_obs: true,
         action_parser,
         state_setter, 
+        auto_reset: false,
+        team_sizes: None,
+        bots: Vec::new(),
+        opponent_pool: None,
+        seed: None,
+        match_config: None,
     };
 
     // If you want to render, use this as a second argument:
@@ -51,14 +64,14 @@ fn main() {
     //     update_rate: 120.,
     // };
     
-    let mut gym = make::make(game_config, None);
+    let mut gym = make::make(game_config, None).unwrap();
 
-    gym.reset(None, None);
+    gym.reset(None, None).unwrap();
 
     for _i in 0..(120 * 50) {
-        let (obs, reward, done, info) = gym.step(actions.clone());
-        if done {
-            gym.reset(None, None);
+        let step_result = gym.step(actions.clone()).unwrap();
+        if step_result.done() {
+            gym.reset(None, None).unwrap();
         }
     }
 }
\ No newline at end of file
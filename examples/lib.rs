@@ -37,6 +37,7 @@ use std::path::Path;
 use crate::gym::Gym;
 use pyo3::{
     prelude::*, 
+    exceptions::PyRuntimeError,
     // exceptions::PyTypeError
 };
 // use rayon::prelude::*;
@@ -84,7 +85,7 @@ pub struct GymWrapper {
 impl GymWrapper {
     #[new]
     /// create the gym wrapper to be used (team_size: i32, tick_skip: usize)
-    pub fn new(team_size: usize, gravity: f32, boost: f32, tick_skip: usize, seed: Option<u64>, self_play: Option<bool>) -> Self {
+    pub fn new(team_size: usize, gravity: f32, boost: f32, tick_skip: usize, seed: Option<u64>, self_play: Option<bool>) -> PyResult<Self> {
         let term_cond = Box::new(CombinedTerminalConditions::new(tick_skip));
         let reward_fn = get_custom_reward_func();
         let mut obs_build_vec = Vec::<Box<dyn ObsBuilder>>::new();
@@ -107,6 +108,11 @@ impl GymWrapper {
             gravity,
             boost_consumption,
             car_config: CarConfig::octane(),
+            car_bodies: envs::car_bodies::CarBodies::Same,
+            step_mode: envs::game_match::StepMode::Final,
+            game_mode: envs::game_match::GameMode::Soccar,
+            mutators: envs::mutators::Mutators::default(),
+            kickoff_phase: None,
         };
         let game_config = make::MakeConfig {
             game_config: config,
@@ -115,17 +121,31 @@ impl GymWrapper {
             obs_builder: obs_build_vec,
             action_parser: act_parse,
             state_setter: state_set, 
+            auto_reset: false,
+            team_sizes: None,
+            bots: Vec::new(),
+            opponent_pool: None,
+            seed: None,
+            match_config: None,
         };
-        let gym = make::make(game_config, None);
-        GymWrapper { gym }
+        match make::make(game_config, None) {
+            Ok(gym) => Ok(GymWrapper { gym }),
+            Err(e) => Err(PyRuntimeError::new_err(e.to_string())),
+        }
     }
 
     pub fn reset(&mut self, seed: Option<u64>) -> PyResult<Vec<Vec<f32>>> {
-        Ok(self.gym.reset(Some(false), seed))
+        match self.gym.reset(Some(false), seed) {
+            Ok(val) => Ok(val.into_legacy()),
+            Err(e) => Err(PyRuntimeError::new_err(e.to_string())),
+        }
     }
 
     pub fn step(&mut self, actions: Vec<Vec<f32>>) -> PyResult<(Vec<Vec<f32>>, Vec<f32>, bool, HashMap<String, f32>)> {
-        Ok(self.gym.step(actions))
+        match self.gym.step(actions) {
+            Ok(val) => Ok(val.into_legacy()),
+            Err(e) => Err(PyRuntimeError::new_err(e.to_string())),
+        }
     }
 
     // pub fn close(&mut self) -> PyResult<()> {
@@ -165,7 +185,7 @@ pub struct GymWrapperRust {
 
 impl GymWrapperRust {
     /// create the gym wrapper to be used (team_size: i32, tick_skip: usize)
-    pub fn new(team_size: usize, gravity: f32, boost: f32, self_play: bool, tick_skip: usize, sender: Sender<Vec<f32>>) -> Self {
+    pub fn new(team_size: usize, gravity: f32, boost: f32, self_play: bool, tick_skip: usize, sender: Sender<Vec<f32>>) -> PyResult<Self> {
         let term_cond = Box::new(CombinedTerminalConditions::new(tick_skip));
         let reward_fn = get_custom_reward_func_mult_inst(sender);
         let mut obs_build_vec = Vec::<Box<dyn ObsBuilder>>::new();
@@ -188,6 +208,11 @@ impl GymWrapperRust {
             gravity,
             boost_consumption,
             car_config: CarConfig::octane(),
+            car_bodies: envs::car_bodies::CarBodies::Same,
+            step_mode: envs::game_match::StepMode::Final,
+            game_mode: envs::game_match::GameMode::Soccar,
+            mutators: envs::mutators::Mutators::default(),
+            kickoff_phase: None,
         };
         let game_config = make::MakeConfig {
             game_config: config,
@@ -196,17 +221,25 @@ impl GymWrapperRust {
             obs_builder: obs_build_vec,
             action_parser: act_parse,
             state_setter: state_set, 
+            auto_reset: false,
+            team_sizes: None,
+            bots: Vec::new(),
+            opponent_pool: None,
+            seed: None,
+            match_config: None,
         };
-        let gym = make::make(game_config, None);
-        GymWrapperRust { gym }
+        match make::make(game_config, None) {
+            Ok(gym) => Ok(GymWrapperRust { gym }),
+            Err(e) => Err(PyRuntimeError::new_err(e.to_string())),
+        }
     }
 
     pub fn reset(&mut self) -> Vec<Vec<f32>> {
-        self.gym.reset(Some(false), None)
+        self.gym.reset(Some(false), None).expect("unable to reset gym").into_legacy()
     }
 
     pub fn step(&mut self, actions: Vec<Vec<f32>>) -> (Vec<Vec<f32>>, Vec<f32>, bool, HashMap<String, f32>) {
-        self.gym.step(actions)
+        self.gym.step(actions).expect("unable to step gym").into_legacy()
     }
 
     // pub fn close(&mut self) {
@@ -446,7 +479,14 @@ pub fn worker(
     // launches env and then sends the reset action to a new thread since receiving a message from the plugin will be blocking,
     // waits for x seconds for thread to return the env if it is a success else tries to force close the pipe and
     // make the gym crash (which should terminate the game)
-    let mut env = GymWrapperRust::new(team_num, gravity, boost, self_play, tick_skip, reward_sender);
+    let mut env = match GymWrapperRust::new(team_num, gravity, boost, self_play, tick_skip, reward_sender) {
+        Ok(env) => env,
+        Err(e) => {
+            // the manager sees the hung up channel and retries creating the worker
+            println!("unable to make gym: {e}");
+            return;
+        }
+    };
     send_chan.send(WorkerPacket::InitReturn).unwrap();
 
     loop {
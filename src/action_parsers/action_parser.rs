@@ -1,6 +1,13 @@
-use crate::gamestates::game_state::GameState;
+use crate::{gamestates::game_state::GameState, spaces::Space};
 
-pub trait ActionParser {
-    fn get_action_space(&mut self) -> Vec<usize>;
+pub trait ActionParser: Send {
+    /// Space of the action of one agent before it is parsed
+    fn get_action_space(&mut self) -> Space;
     fn parse_actions(&mut self, actions: Vec<Vec<f32>>, state: &GameState) -> Vec<Vec<f32>>;
+    /// Seeds parsers that sample actions, does nothing by default
+    fn set_seed(&mut self, _seed: u64) {}
+    /// Copy of this action parser (including its rng) for [Gym::snapshot](crate::Gym::snapshot). `None` if unsupported.
+    fn snapshot(&self) -> Option<Box<dyn ActionParser>> {
+        None
+    }
 }
@@ -1,6 +1,8 @@
 use super::action_parser::ActionParser;
 use crate::gamestates::game_state::GameState;
+use crate::common_values::NUM_ACTIONS;
 use crate::math::clip;
+use crate::spaces::Space;
 
 #[derive(Clone, Copy, Default)]
 pub struct ContinuousAction;
@@ -21,7 +23,11 @@ impl ActionParser for ContinuousAction {
         parsed_actions
     }
 
-    fn get_action_space(&mut self) -> Vec<usize> {
-        vec![]
+    fn get_action_space(&mut self) -> Space {
+        Space::box_f32(-1., 1., NUM_ACTIONS)
+    }
+
+    fn snapshot(&self) -> Option<Box<dyn ActionParser>> {
+        Some(Box::new(*self))
     }
 }
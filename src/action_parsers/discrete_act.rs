@@ -1,4 +1,5 @@
 use super::action_parser::ActionParser;
+use crate::spaces::Space;
 
 pub struct DiscreteAction {
     n_bins: usize,
@@ -17,10 +18,10 @@ impl Default for DiscreteAction {
 }
 
 impl ActionParser for DiscreteAction {
-    fn get_action_space(&mut self) -> Vec<usize> {
+    fn get_action_space(&mut self) -> Space {
         let mut act_space = vec![self.n_bins; 5];
         act_space.extend([2; 3]);
-        act_space
+        Space::MultiDiscrete(act_space)
     }
 
     fn parse_actions(&mut self, actions: Vec<Vec<f32>>, _state: &crate::gamestates::game_state::GameState) -> Vec<Vec<f32>> {
@@ -36,4 +37,8 @@ impl ActionParser for DiscreteAction {
 
         parsed_actions
     }
+
+    fn snapshot(&self) -> Option<Box<dyn ActionParser>> {
+        Some(Box::new(DiscreteAction { n_bins: self.n_bins }))
+    }
 }
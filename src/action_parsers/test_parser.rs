@@ -1,4 +1,4 @@
-use crate::gamestates::game_state::GameState;
+use crate::{common_values::NUM_ACTIONS, gamestates::game_state::GameState, spaces::Space};
 
 use super::action_parser::ActionParser;
 
@@ -18,11 +18,15 @@ impl TestAction {
 }
 
 impl ActionParser for TestAction {
-    fn get_action_space(&mut self) -> Vec<usize> {
-        vec![8]
+    fn get_action_space(&mut self) -> Space {
+        Space::box_f32(-1., 1., NUM_ACTIONS)
     }
 
     fn parse_actions(&mut self, actions: Vec<Vec<f32>>, _state: &GameState) -> Vec<Vec<f32>> {
         actions
     }
+
+    fn snapshot(&self) -> Option<Box<dyn ActionParser>> {
+        Some(Box::new(TestAction::new()))
+    }
 }
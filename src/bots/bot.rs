@@ -0,0 +1,19 @@
+use rocketsim_rs::sim::CarControls;
+
+use crate::gamestates::{game_state::GameState, player_data::PlayerData};
+
+/// Scripted policy for a car that is not controlled by the trainer, see [MakeConfig::bots](crate::make::MakeConfig::bots).
+///
+/// Bots get the state after the previous step and their controls are merged with the actions of the learning agents
+/// before the sim is stepped, so they act on the same tick skip as the agents.
+pub trait Bot: Send {
+    /// Called with the initial state of every episode
+    fn reset(&mut self, _initial_state: &GameState) {}
+    fn get_controls(&mut self, player: &PlayerData, state: &GameState) -> CarControls;
+    /// Seeds bots that act randomly, each bot gets its own seed from the seed of the gym
+    fn set_seed(&mut self, _seed: u64) {}
+    /// Copy of this bot for [Gym::snapshot](crate::Gym::snapshot), `None` (the default) if it can not be copied
+    fn snapshot(&self) -> Option<Box<dyn Bot>> {
+        None
+    }
+}
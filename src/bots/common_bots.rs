@@ -0,0 +1,144 @@
+use rand::{rng, rngs::SmallRng, Rng, SeedableRng};
+use rocketsim_rs::sim::CarControls;
+
+use crate::gamestates::{game_state::GameState, physics_object::Position, player_data::PlayerData};
+
+use super::bot::Bot;
+
+/// Controls that drive `player` on the ground towards `target`, boosting when facing it if `use_boost` is true
+fn drive_to(player: &PlayerData, target: &Position, throttle: f32, use_boost: bool) -> CarControls {
+    let car = &player.car_data;
+    let forward = car.forward();
+    let dx = target.x - car.position.x;
+    let dy = target.y - car.position.y;
+
+    // signed angle between the car's forward direction and the target on the ground plane
+    let angle = (forward[0] * dy - forward[1] * dx).atan2(forward[0] * dx + forward[1] * dy);
+
+    CarControls {
+        throttle,
+        steer: (angle * 3.).clamp(-1., 1.),
+        boost: use_boost && throttle > 0. && angle.abs() < 0.3 && player.boost_amount > 0.,
+        handbrake: angle.abs() > 2.,
+        ..Default::default()
+    }
+}
+
+/// Sits still without any inputs
+#[derive(Clone, Default)]
+pub struct IdleBot {}
+
+impl IdleBot {
+    pub fn new() -> Self {
+        IdleBot {}
+    }
+}
+
+impl Bot for IdleBot {
+    fn get_controls(&mut self, _player: &PlayerData, _state: &GameState) -> CarControls {
+        CarControls::default()
+    }
+
+    fn snapshot(&self) -> Option<Box<dyn Bot>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// Uniformly random inputs every step
+#[derive(Clone)]
+pub struct RandomBot {
+    rng: SmallRng,
+}
+
+impl RandomBot {
+    pub fn new(seed: Option<u64>) -> Self {
+        let seed = match seed {
+            Some(seed) => seed,
+            None => rng().random_range(0..10000),
+        };
+        RandomBot { rng: SmallRng::seed_from_u64(seed) }
+    }
+}
+
+impl Bot for RandomBot {
+    fn get_controls(&mut self, _player: &PlayerData, _state: &GameState) -> CarControls {
+        CarControls {
+            throttle: self.rng.random_range(-1.0..=1.),
+            steer: self.rng.random_range(-1.0..=1.),
+            pitch: self.rng.random_range(-1.0..=1.),
+            yaw: self.rng.random_range(-1.0..=1.),
+            roll: self.rng.random_range(-1.0..=1.),
+            jump: self.rng.random(),
+            boost: self.rng.random(),
+            handbrake: self.rng.random(),
+        }
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
+    }
+
+    fn snapshot(&self) -> Option<Box<dyn Bot>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// Drives straight at the ball at full throttle, boosting when it is lined up
+#[derive(Clone)]
+pub struct BallChaser {
+    use_boost: bool,
+}
+
+impl BallChaser {
+    pub fn new(use_boost: Option<bool>) -> Self {
+        BallChaser { use_boost: use_boost.unwrap_or(true) }
+    }
+}
+
+impl Bot for BallChaser {
+    fn get_controls(&mut self, player: &PlayerData, state: &GameState) -> CarControls {
+        drive_to(player, &state.ball.position, 1., self.use_boost)
+    }
+
+    fn snapshot(&self) -> Option<Box<dyn Bot>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// Shadows the ball along the front of its own net and slows down once it is in position
+#[derive(Clone)]
+pub struct Goalie {
+    /// distance from its goal line (or rim in hoops) that the goalie stays at
+    depth: f32,
+    /// how far the goalie follows the ball sideways from the center of the net
+    max_x: f32,
+}
+
+impl Goalie {
+    pub fn new(depth: Option<f32>, max_x: Option<f32>) -> Self {
+        Goalie { depth: depth.unwrap_or(500.), max_x: max_x.unwrap_or(800.) }
+    }
+}
+
+impl Bot for Goalie {
+    fn get_controls(&mut self, player: &PlayerData, state: &GameState) -> CarControls {
+        let goal_y = state.game_mode().goal_center(player.team_num).y;
+        let target = Position {
+            x: state.ball.position.x.clamp(-self.max_x, self.max_x),
+            y: goal_y - goal_y.signum() * self.depth,
+            z: 0.,
+        };
+
+        let dist = ((target.x - player.car_data.position.x).powi(2) + (target.y - player.car_data.position.y).powi(2)).sqrt();
+        let throttle = (dist / 500.).min(1.);
+        if dist < 100. {
+            // in position, stay put
+            return CarControls::default();
+        }
+        drive_to(player, &target, throttle, false)
+    }
+
+    fn snapshot(&self) -> Option<Box<dyn Bot>> {
+        Some(Box::new(self.clone()))
+    }
+}
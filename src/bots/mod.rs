@@ -0,0 +1,3 @@
+pub mod bot;
+pub mod common_bots;
+pub mod opponent_pool;
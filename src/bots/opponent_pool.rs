@@ -0,0 +1,229 @@
+use rand::{distr::{weighted::WeightedIndex, Distribution}, rng, Rng, rngs::SmallRng, SeedableRng};
+
+use crate::{
+    common_values::{BLUE_TEAM, ORANGE_TEAM},
+    error::{Error, Result},
+    gamestates::game_state::GameState,
+};
+
+/// Frozen policy (eg. an older snapshot of the policy that is being trained) that drives every car of one team.
+///
+/// The observations are built by the first observation builder of the gym in the same pass as the ones of the
+/// learning agents and the returned actions go through the gym's action parser, the same as the learners' actions.
+pub trait Opponent: Send {
+    /// Called with the initial state of every episode that this opponent is picked for
+    fn reset(&mut self, _initial_state: &GameState) {}
+    /// One action per observation, the observations are in the order of `state.players`
+    fn get_actions(&mut self, obs: &[Vec<f32>], state: &GameState) -> Vec<Vec<f32>>;
+    /// Copy of this opponent for [Gym::snapshot](crate::Gym::snapshot), `None` (the default) if it can not be copied
+    fn snapshot(&self) -> Option<Box<dyn Opponent>> {
+        None
+    }
+}
+
+/// Outcome of an episode for the learning agents
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatchOutcome {
+    Win,
+    Loss,
+    Draw,
+}
+
+/// Which opponent drove the opponent team, the outcome is only set on the step that ends the episode
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OpponentReport {
+    pub id: String,
+    pub outcome: Option<MatchOutcome>,
+}
+
+/// An opponent in the [OpponentPool] with its sampling weight and results against the learning agents
+pub struct PoolEntry {
+    pub id: String,
+    pub weight: f64,
+    pub wins: u64,
+    pub losses: u64,
+    pub draws: u64,
+    opponent: Box<dyn Opponent>,
+}
+
+impl PoolEntry {
+    pub fn games(&self) -> u64 {
+        self.wins + self.losses + self.draws
+    }
+
+    fn snapshot(&self) -> Option<PoolEntry> {
+        Some(PoolEntry {
+            id: self.id.clone(),
+            weight: self.weight,
+            wins: self.wins,
+            losses: self.losses,
+            draws: self.draws,
+            opponent: self.opponent.snapshot()?,
+        })
+    }
+}
+
+/// Pool of opponents for self-play, one is picked on every reset of the gym (weighted by `weight`)
+/// to drive the cars of `team`.
+///
+/// The wins, losses and draws of the learning agents against each opponent are counted in its [PoolEntry]
+/// so that ratings or weights can be updated from [entries](OpponentPool::entries).
+pub struct OpponentPool {
+    team: i32,
+    entries: Vec<PoolEntry>,
+    current: Option<usize>,
+    // the current opponent after it was removed from the pool, it plays until the next sample
+    removed_current: Option<PoolEntry>,
+    rng: SmallRng,
+}
+
+impl OpponentPool {
+    /// `team` is the team that the opponents drive, this is [ORANGE_TEAM] by default
+    pub fn new(team: Option<i32>, seed: Option<u64>) -> Self {
+        let seed = match seed {
+            Some(seed) => seed,
+            None => rng().random_range(0..10000),
+        };
+        OpponentPool {
+            team: team.unwrap_or(ORANGE_TEAM),
+            entries: Vec::new(),
+            current: None,
+            removed_current: None,
+            rng: SmallRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Adds an opponent, an opponent with the same id is replaced (keeping its results)
+    pub fn add(&mut self, id: impl Into<String>, opponent: Box<dyn Opponent>, weight: f64) {
+        let id = id.into();
+        match self.entries.iter_mut().find(|entry| entry.id == id) {
+            Some(entry) => {
+                entry.opponent = opponent;
+                entry.weight = weight;
+            }
+            None => self.entries.push(PoolEntry { id, weight, wins: 0, losses: 0, draws: 0, opponent }),
+        }
+    }
+
+    /// Removes the opponent with this id, the current opponent keeps playing until the next reset
+    pub fn remove(&mut self, id: &str) -> bool {
+        let index = match self.entries.iter().position(|entry| entry.id == id) {
+            Some(val) => val,
+            None => return false,
+        };
+        let entry = self.entries.remove(index);
+        self.current = match self.current {
+            Some(current) if current == index => {
+                self.removed_current = Some(entry);
+                None
+            }
+            Some(current) if current > index => Some(current - 1),
+            current => current,
+        };
+        true
+    }
+
+    pub fn set_weight(&mut self, id: &str, weight: f64) -> bool {
+        match self.entries.iter_mut().find(|entry| entry.id == id) {
+            Some(entry) => {
+                entry.weight = weight;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn entries(&self) -> &[PoolEntry] {
+        &self.entries
+    }
+
+    pub fn team(&self) -> i32 {
+        self.team
+    }
+
+    /// id of the opponent of the current episode
+    pub fn current_id(&self) -> Option<&str> {
+        match &self.removed_current {
+            Some(entry) => Some(entry.id.as_str()),
+            None => self.current.map(|index| self.entries[index].id.as_str()),
+        }
+    }
+
+    fn current_entry(&mut self) -> Option<&mut PoolEntry> {
+        match self.removed_current.as_mut() {
+            Some(entry) => Some(entry),
+            None => self.current.map(|index| &mut self.entries[index]),
+        }
+    }
+
+    /// Picks the opponent of the next episode
+    pub fn sample(&mut self) -> Result<()> {
+        if self.entries.is_empty() {
+            return Err(Error::OpponentPool("the pool has no opponents"));
+        }
+        let distribution = match WeightedIndex::new(self.entries.iter().map(|entry| entry.weight)) {
+            Ok(val) => val,
+            Err(_) => return Err(Error::OpponentPool("the weights of the pool must be non-negative and not all zero")),
+        };
+        self.current = Some(distribution.sample(&mut self.rng));
+        self.removed_current = None;
+        Ok(())
+    }
+
+    /// Copy of the pool with its opponents, results, current pick and rng for [Gym::snapshot](crate::Gym::snapshot),
+    /// `None` if one of the opponents can not be copied
+    pub fn snapshot(&self) -> Option<OpponentPool> {
+        let removed_current = match &self.removed_current {
+            Some(entry) => Some(entry.snapshot()?),
+            None => None,
+        };
+        Some(OpponentPool {
+            team: self.team,
+            entries: self.entries.iter().map(|entry| entry.snapshot()).collect::<Option<Vec<_>>>()?,
+            current: self.current,
+            removed_current,
+            rng: self.rng.clone(),
+        })
+    }
+
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
+    }
+
+    pub(crate) fn reset_current(&mut self, initial_state: &GameState) {
+        if let Some(entry) = self.current_entry() {
+            entry.opponent.reset(initial_state);
+        }
+    }
+
+    pub(crate) fn get_actions(&mut self, obs: &[Vec<f32>], state: &GameState) -> Result<Vec<Vec<f32>>> {
+        match self.current_entry() {
+            Some(entry) => Ok(entry.opponent.get_actions(obs, state)),
+            None => Err(Error::OpponentPool("no opponent was picked, the gym must be reset first")),
+        }
+    }
+
+    /// Report of the current opponent, `result` (blue - orange goals) is given when the episode has ended
+    /// and is counted in the results of the opponent
+    pub(crate) fn report(&mut self, result: Option<i32>) -> Option<OpponentReport> {
+        let team = self.team;
+        let entry = self.current_entry()?;
+        let outcome = result.map(|result| {
+            // the learning agents are on the other team
+            let learner_diff = if team == BLUE_TEAM { -result } else { result };
+            match learner_diff {
+                diff if diff > 0 => MatchOutcome::Win,
+                diff if diff < 0 => MatchOutcome::Loss,
+                _ => MatchOutcome::Draw,
+            }
+        });
+
+        match outcome {
+            Some(MatchOutcome::Win) => entry.wins += 1,
+            Some(MatchOutcome::Loss) => entry.losses += 1,
+            Some(MatchOutcome::Draw) => entry.draws += 1,
+            None => (),
+        }
+        Some(OpponentReport { id: entry.id.clone(), outcome })
+    }
+}
@@ -1,4 +1,6 @@
-use crate::gamestates::physics_object::Position;
+use std::f32::consts::FRAC_PI_2;
+
+use crate::gamestates::physics_object::{Position, Velocity};
 
 pub const SIDE_WALL_X: f32 = 4096.;
 pub const BACK_WALL_Y: f32 = 5120.;
@@ -29,6 +31,9 @@ pub const BLUE_GOAL_BACK: Position = Position {
 };
 
 pub const BALL_RADIUS: f32 = 92.75;
+pub const HOOPS_BALL_RADIUS: f32 = 96.3831;
+pub const PUCK_RADIUS: f32 = 114.25;
+pub const PUCK_HEIGHT: f32 = 62.5;
 pub const BALL_MAX_SPEED: f32 = 6000.0;
 
 pub const CAR_MAX_SPEED: f32 = 2300.0;
@@ -40,6 +45,8 @@ pub const ORANGE_TEAM: i32 = 1;
 
 pub const NUM_ACTIONS: usize = 8;
 
+pub const TICKS_PER_SECOND: u64 = 120;
+
 pub const GRAVITY_Z: f32 = -650.;
 
 pub const ROCKETSIM_BOOST_MAX: f32 = 100.;
@@ -83,3 +90,70 @@ pub const BOOST_LOCATIONS: [[f32; 3]; 34] = [
     [1792.0, 4184.0, 70.0],
     [0.0, 4240.0, 70.0],
 ];
+
+pub const HOOPS_SIDE_WALL_X: f32 = 8900. / 3.;
+pub const HOOPS_BACK_WALL_Y: f32 = 3581.;
+pub const HOOPS_CEILING_Z: f32 = 1820.;
+/// the ball is scored when it falls below this height inside of a rim
+pub const HOOPS_RIM_HEIGHT: f32 = 270.;
+/// distance of the center of each rim from the middle of the field
+pub const HOOPS_RIM_Y: f32 = 2770. / 0.9;
+
+pub const ORANGE_HOOP_CENTER: Position = Position {
+    x: 0.,
+    y: HOOPS_RIM_Y,
+    z: HOOPS_RIM_HEIGHT,
+};
+pub const BLUE_HOOP_CENTER: Position = Position {
+    x: 0.,
+    y: -HOOPS_RIM_Y,
+    z: HOOPS_RIM_HEIGHT,
+};
+
+pub const HOOPS_BOOST_LOCATIONS: [[f32; 3]; 20] = [
+    [-2176.0, -2944.0, 72.0],
+    [2176.0, -2944.0, 72.0],
+    [0.0, -2816.0, 64.0],
+    [-1280.0, -2304.0, 64.0],
+    [1280.0, -2304.0, 64.0],
+    [-1536.0, -1024.0, 64.0],
+    [1536.0, -1024.0, 64.0],
+    [-512.0, -512.0, 64.0],
+    [512.0, -512.0, 64.0],
+    [-2432.0, 0.0, 72.0],
+    [2432.0, 0.0, 72.0],
+    [-512.0, 512.0, 64.0],
+    [512.0, 512.0, 64.0],
+    [-1536.0, 1024.0, 64.0],
+    [1536.0, 1024.0, 64.0],
+    [-1280.0, 2304.0, 64.0],
+    [1280.0, 2304.0, 64.0],
+    [0.0, 2816.0, 64.0],
+    [-2176.0, 2944.0, 72.0],
+    [2176.0, 2944.0, 72.0],
+];
+
+/// kickoff spots of the blue team in hoops as x, y and yaw, the orange spots are mirrored
+pub const HOOPS_KICKOFF_SPAWNS: [[f32; 3]; 5] = [
+    [-1536., -3072., FRAC_PI_2],
+    [1536., -3072., FRAC_PI_2],
+    [-256., -2816., FRAC_PI_2],
+    [256., -2816., FRAC_PI_2],
+    [0., -3200., FRAC_PI_2],
+];
+/// upwards velocity of the ball on a hoops kickoff
+pub const HOOPS_KICKOFF_BALL_VEL_Z: f32 = 1000.;
+
+/// kickoff spots of the blue team in heatseeker as x, y and yaw, the orange spots are mirrored
+pub const HEATSEEKER_KICKOFF_SPAWNS: [[f32; 3]; 4] = [
+    [-1000., -4620., FRAC_PI_2],
+    [1000., -4620., FRAC_PI_2],
+    [-2000., -4620., FRAC_PI_2],
+    [2000., -4620., FRAC_PI_2],
+];
+/// the ball starts here on a heatseeker kickoff, with y flipped for a kickoff towards the blue side
+pub const HEATSEEKER_BALL_START: Position = Position { x: -1000., y: -2220., z: 92.75 };
+pub const HEATSEEKER_BALL_START_VEL: Velocity = Velocity { x: 0., y: -65., z: 650. };
+
+/// where the ball is held in the void, far away from the cars
+pub const VOID_BALL_POSITION: Position = Position { x: 0., y: 0., z: -100_000. };
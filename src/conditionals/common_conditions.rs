@@ -1,8 +1,9 @@
 use crate::gamestates::game_state::GameState;
 
-use super::terminal_condition::TerminalCondition;
+use super::terminal_condition::{TerminalCondition, TerminalSignal};
 
-/// Returns a terminal signal when max_steps has been hit
+/// Returns a truncated signal when max_steps has been hit
+#[derive(Clone)]
 pub struct TimeoutCondition {
     steps: i64,
     max_steps: i64,
@@ -22,9 +23,18 @@ impl TerminalCondition for TimeoutCondition {
         self.steps += 1;
         self.steps >= self.max_steps
     }
+
+    fn terminal_signal(&mut self, current_state: &GameState) -> TerminalSignal {
+        TerminalSignal::truncated_if(self.is_terminal(current_state), self.name())
+    }
+
+    fn snapshot(&self) -> Option<Box<dyn TerminalCondition>> {
+        Some(Box::new(self.clone()))
+    }
 }
 
-/// Returns a terminal signal when there have been no ball touches in max_steps
+/// Returns a truncated signal when there have been no ball touches in max_steps
+#[derive(Clone)]
 pub struct NoTouchTimeoutCondition {
     steps: i64,
     max_steps: i64,
@@ -50,9 +60,18 @@ impl TerminalCondition for NoTouchTimeoutCondition {
             self.steps >= self.max_steps
         }
     }
+
+    fn terminal_signal(&mut self, current_state: &GameState) -> TerminalSignal {
+        TerminalSignal::truncated_if(self.is_terminal(current_state), self.name())
+    }
+
+    fn snapshot(&self) -> Option<Box<dyn TerminalCondition>> {
+        Some(Box::new(self.clone()))
+    }
 }
 
-/// Returns a terminal signal when the ball has been scored
+/// Returns a terminated signal when the ball has been scored
+#[derive(Clone)]
 pub struct GoalScoredCondition {
     blue_score: i32,
     orange_score: i32,
@@ -82,4 +101,30 @@ impl TerminalCondition for GoalScoredCondition {
             false
         }
     }
+
+    fn snapshot(&self) -> Option<Box<dyn TerminalCondition>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// Never ends the episode, for gyms that are only used for inference
+#[derive(Clone, Copy, Default)]
+pub struct NeverTerminalCondition;
+
+impl NeverTerminalCondition {
+    pub fn new() -> Self {
+        NeverTerminalCondition
+    }
+}
+
+impl TerminalCondition for NeverTerminalCondition {
+    fn reset(&mut self, _initial_state: &GameState) {}
+
+    fn is_terminal(&mut self, _current_state: &GameState) -> bool {
+        false
+    }
+
+    fn snapshot(&self) -> Option<Box<dyn TerminalCondition>> {
+        Some(Box::new(*self))
+    }
 }
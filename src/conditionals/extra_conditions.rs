@@ -1,7 +1,9 @@
-use crate::gamestates::game_state::GameState;
+use crate::{gamestates::game_state::GameState, seeding::derive_seed};
 
-use super::terminal_condition::TerminalCondition;
+use super::terminal_condition::{TerminalCondition, TerminalSignal};
 
+/// Combines multiple terminal conditions, every condition is evaluated each step and their signals are merged
+/// so that the episode is terminated if any condition terminated it and truncated if any condition truncated it
 pub struct CombinedTerminalConditions {
     conditionals: Vec<Box<dyn TerminalCondition>>,
 }
@@ -21,12 +23,40 @@ impl TerminalCondition for CombinedTerminalConditions {
         }
     }
 
+    fn sub_steps(&mut self, states: &[GameState]) {
+        for conditional in self.conditionals.iter_mut() {
+            conditional.sub_steps(states);
+        }
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        for (i, conditional) in self.conditionals.iter_mut().enumerate() {
+            conditional.set_seed(derive_seed(seed, i as u64));
+        }
+    }
+
     fn is_terminal(&mut self, current_state: &GameState) -> bool {
-        self.conditionals.iter_mut().any(|f| f.is_terminal(current_state))
+        self.terminal_signal(current_state).is_done()
+    }
+
+    fn terminal_signal(&mut self, current_state: &GameState) -> TerminalSignal {
+        // evaluate every condition (no short-circuiting) so that step counters stay in sync
+        self.conditionals
+            .iter_mut()
+            .fold(TerminalSignal::default(), |signal, f| signal.merge(f.terminal_signal(current_state)))
+    }
+
+    fn snapshot(&self) -> Option<Box<dyn TerminalCondition>> {
+        let conditionals = self.conditionals.iter().map(|conditional| conditional.snapshot()).collect::<Option<Vec<_>>>()?;
+        Some(Box::new(CombinedTerminalConditions { conditionals }))
     }
 }
 
-/// Returns a terminal signal when the ball is at x: 0, y: 0 after the specified steps
+/// Returns a truncated signal when the ball has not been touched after the specified steps of a kickoff, see [GameState::is_kickoff]
+///
+/// Kickoffs of state setters that do not set [StateWrapper::kickoff](crate::state_setters::wrappers::state_wrapper::StateWrapper::kickoff)
+/// are not tracked by the gym, for those the ball resting at the center of the field without having been touched counts as a kickoff.
+#[derive(Clone)]
 pub struct NoTouchKickoffTimeoutCondition {
     steps: i64,
     max_steps: i64,
@@ -44,16 +74,24 @@ impl TerminalCondition for NoTouchKickoffTimeoutCondition {
     }
 
     fn is_terminal(&mut self, current_state: &GameState) -> bool {
-        if current_state.ball.position.x == 0. && current_state.ball.position.y == 0. {
-            if current_state.players.iter().any(|x| x.ball_touched) {
-                self.steps = 0;
-                false
-            } else {
-                self.steps += 1;
-                self.steps >= self.max_steps
-            }
+        let untracked_kickoff = current_state.ball.position.x == 0.
+            && current_state.ball.position.y == 0.
+            && !current_state.players.iter().any(|player| player.ball_touched);
+        // the steps of the countdown are not counted since the cars can not move
+        if (current_state.is_kickoff || untracked_kickoff) && !current_state.is_kickoff_pause {
+            self.steps += 1;
+            self.steps >= self.max_steps
         } else {
+            self.steps = 0;
             false
         }
     }
+
+    fn terminal_signal(&mut self, current_state: &GameState) -> TerminalSignal {
+        TerminalSignal::truncated_if(self.is_terminal(current_state), self.name())
+    }
+
+    fn snapshot(&self) -> Option<Box<dyn TerminalCondition>> {
+        Some(Box::new(self.clone()))
+    }
 }
@@ -1,6 +1,73 @@
 use crate::gamestates::game_state::GameState;
 
-pub trait TerminalCondition {
+/// Gymnasium-style terminal signal.
+///
+/// `terminated` is for an episode that reached a true end state (eg. a goal was scored) while `truncated`
+/// is for an episode that was cut short (eg. a timeout) and should still be bootstrapped from.
+/// `reasons` holds the names of the conditions that ended the episode.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TerminalSignal {
+    pub terminated: bool,
+    pub truncated: bool,
+    pub reasons: Vec<&'static str>,
+}
+
+impl TerminalSignal {
+    pub fn terminated(reason: &'static str) -> Self {
+        Self { terminated: true, truncated: false, reasons: vec![reason] }
+    }
+
+    pub fn truncated(reason: &'static str) -> Self {
+        Self { terminated: false, truncated: true, reasons: vec![reason] }
+    }
+
+    /// [terminated](TerminalSignal::terminated) if `terminated` is true, an empty signal otherwise
+    pub fn terminated_if(terminated: bool, reason: &'static str) -> Self {
+        if terminated { Self::terminated(reason) } else { Self::default() }
+    }
+
+    /// [truncated](TerminalSignal::truncated) if `truncated` is true, an empty signal otherwise
+    pub fn truncated_if(truncated: bool, reason: &'static str) -> Self {
+        if truncated { Self::truncated(reason) } else { Self::default() }
+    }
+
+    /// true if the episode has ended for either reason
+    pub fn is_done(&self) -> bool {
+        self.terminated || self.truncated
+    }
+
+    /// merges two signals, either flag being set in one of the signals sets it in the merged signal
+    pub fn merge(mut self, other: TerminalSignal) -> Self {
+        self.reasons.extend(other.reasons);
+        Self {
+            terminated: self.terminated || other.terminated,
+            truncated: self.truncated || other.truncated,
+            reasons: self.reasons,
+        }
+    }
+}
+
+pub trait TerminalCondition: Send {
     fn reset(&mut self, initial_state: &GameState);
     fn is_terminal(&mut self, current_state: &GameState) -> bool;
+    /// Sees the intermediate tick states of a step before the terminal check (with
+    /// [StepMode::History](crate::envs::game_match::StepMode::History) only), eg. to catch an event that happened mid-step
+    fn sub_steps(&mut self, _states: &[GameState]) {}
+    /// Seeds conditions with randomness (eg. a random timeout), does nothing by default
+    fn set_seed(&mut self, _seed: u64) {}
+    /// Copy of this condition (with its step counters etc.) for [Gym::snapshot](crate::Gym::snapshot), `None` if unsupported
+    fn snapshot(&self) -> Option<Box<dyn TerminalCondition>> {
+        None
+    }
+    /// Name that is reported in the terminal reasons, by default this is the name of the type
+    fn name(&self) -> &'static str {
+        let type_name = std::any::type_name::<Self>();
+        type_name.rsplit("::").next().unwrap_or(type_name)
+    }
+    /// Reports whether the episode was terminated or truncated, by default `is_terminal` counts as terminated.
+    ///
+    /// This is what the gym calls every step so only one of `is_terminal` and `terminal_signal` should be called per step.
+    fn terminal_signal(&mut self, current_state: &GameState) -> TerminalSignal {
+        TerminalSignal::terminated_if(self.is_terminal(current_state), self.name())
+    }
 }
@@ -0,0 +1,123 @@
+use rand::Rng;
+use rocketsim_rs::sim::{CarConfig, WheelPairConfig};
+use serde::{Deserialize, Serialize};
+
+/// The car bodies (hitboxes) of Rocket League, reported for each car in [PlayerData::hitbox_type](crate::gamestates::player_data::PlayerData::hitbox_type)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum HitboxType {
+    #[default]
+    Octane,
+    Dominus,
+    Plank,
+    Breakout,
+    Hybrid,
+    Merc,
+}
+
+impl HitboxType {
+    pub const ALL: [HitboxType; 6] = [
+        HitboxType::Octane,
+        HitboxType::Dominus,
+        HitboxType::Plank,
+        HitboxType::Breakout,
+        HitboxType::Hybrid,
+        HitboxType::Merc,
+    ];
+
+    /// RocketSim car config of this body
+    pub fn config(self) -> &'static CarConfig {
+        match self {
+            HitboxType::Octane => CarConfig::octane(),
+            HitboxType::Dominus => CarConfig::dominus(),
+            HitboxType::Plank => CarConfig::plank(),
+            HitboxType::Breakout => CarConfig::breakout(),
+            HitboxType::Hybrid => CarConfig::hybrid(),
+            HitboxType::Merc => CarConfig::merc(),
+        }
+    }
+
+    /// Body with the hitbox of `config`, a custom config gets the body with the closest hitbox size
+    pub fn from_config(config: &CarConfig) -> Self {
+        let size = config.hitbox_size;
+        let distance = |hitbox_type: &HitboxType| {
+            let other = hitbox_type.config().hitbox_size;
+            (size.x - other.x).powi(2) + (size.y - other.y).powi(2) + (size.z - other.z).powi(2)
+        };
+        Self::ALL.into_iter().min_by(|a, b| distance(a).total_cmp(&distance(b))).unwrap_or_default()
+    }
+
+    /// Index of this body in [HitboxType::ALL], the same numbering as RLGym
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// One-hot encoding of the body for observations
+    pub fn one_hot(self) -> [f32; 6] {
+        let mut encoding = [0.; 6];
+        encoding[self.index()] = 1.;
+        encoding
+    }
+}
+
+/// Which car body each car gets, set in [GameConfig::car_bodies](crate::envs::game_match::GameConfig::car_bodies).
+///
+/// Slots are the indices of the cars in `state.players`, the blue cars come first. Slots that are not given a body
+/// use [GameConfig::car_config](crate::envs::game_match::GameConfig::car_config).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CarBodies {
+    /// every car uses `car_config`
+    #[default]
+    Same,
+    /// one body for each team
+    PerTeam { blue: HitboxType, orange: HitboxType },
+    /// body of each slot
+    PerSlot(&'static [HitboxType]),
+    /// every car gets a random body out of these on every reset of the gym
+    Random(&'static [HitboxType]),
+}
+
+impl CarBodies {
+    /// Car config of every slot of a match with `team_size` cars per team, `rng` is only used by [CarBodies::Random]
+    pub fn resolve(
+        &self,
+        car_config: &'static CarConfig,
+        team_size: usize,
+        spawn_opponents: bool,
+        rng: &mut impl Rng,
+    ) -> Vec<&'static CarConfig> {
+        let num_cars = if spawn_opponents { team_size * 2 } else { team_size };
+        (0..num_cars)
+            .map(|slot| match self {
+                CarBodies::Same => car_config,
+                CarBodies::PerTeam { blue, orange } => if slot < team_size { blue.config() } else { orange.config() },
+                CarBodies::PerSlot(bodies) => bodies.get(slot).map_or(car_config, |body| body.config()),
+                CarBodies::Random(bodies) => {
+                    if bodies.is_empty() {
+                        car_config
+                    } else {
+                        bodies[rng.random_range(0..bodies.len())].config()
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// true if the bodies are sampled again on every reset
+    pub fn is_random(&self) -> bool {
+        matches!(self, CarBodies::Random(_))
+    }
+}
+
+/// true if the two configs give the same car, CarConfig does not implement PartialEq
+pub(crate) fn same_car_config(a: &CarConfig, b: &CarConfig) -> bool {
+    let same_wheels = |a: &WheelPairConfig, b: &WheelPairConfig| {
+        a.wheel_radius == b.wheel_radius
+            && a.suspension_rest_length == b.suspension_rest_length
+            && a.connection_point_offset == b.connection_point_offset
+    };
+    a.hitbox_size == b.hitbox_size
+        && a.hitbox_pos_offset == b.hitbox_pos_offset
+        && same_wheels(&a.front_wheels, &b.front_wheels)
+        && same_wheels(&a.back_wheels, &b.back_wheels)
+        && a.dodge_deadzone == b.dodge_deadzone
+}
@@ -1,14 +1,30 @@
 use rocketsim_rs::sim::CarConfig;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     action_parsers::action_parser::ActionParser,
-    conditionals::terminal_condition::TerminalCondition,
+    conditionals::terminal_condition::{TerminalCondition, TerminalSignal},
+    error::{Error, Result},
     obs_builders::obs_builder::ObsBuilder,
     reward_functions::reward_fn::RewardFn,
-    sim_wrapper::wrapper::RocketsimWrapper,
+    sim_wrapper::wrapper::{self, RocketsimWrapper},
     state_setters::{state_setter::StateSetter, wrappers::state_wrapper::StateWrapper}, make::MakeConfig,
 };
 
+use crate::{
+    bots::{bot::Bot, opponent_pool::{OpponentPool, OpponentReport}},
+    common_values::{
+        BALL_RADIUS, BLUE_GOAL_BACK, BLUE_GOAL_CENTER, BLUE_HOOP_CENTER, BLUE_TEAM, BOOST_LOCATIONS, HOOPS_BALL_RADIUS,
+        HOOPS_BOOST_LOCATIONS, ORANGE_GOAL_BACK, ORANGE_GOAL_CENTER, ORANGE_HOOP_CENTER, ORANGE_TEAM, PUCK_RADIUS,
+    },
+    gamestates::physics_object::Position,
+    seeding::{derive_seed, SeedStream},
+    spaces::Space,
+    state_setters::wrappers::car_wrapper::IntoArray,
+};
+
+use super::{car_bodies::CarBodies, kickoff::KickoffPhase, match_mode::MatchClock, mutators::Mutators, team_sizes::TeamSizes};
+
 use crate::gamestates::game_state::GameState;
 
 /// Struct that wraps the game structs (basically) and provides an interface to the observation builders, state setters, etc.
@@ -20,17 +36,28 @@ pub struct GameMatch {
     pub _action_parser: Box<dyn ActionParser>,
     pub _state_setter: Box<dyn StateSetter>,
     pub agents: usize,
-    pub observation_space: Vec<usize>,
+    pub observation_space: Space,
     pub use_single_obs: bool,
-    pub action_space: Vec<usize>,
+    pub action_space: Space,
     pub _prev_actions: Vec<Vec<f32>>,
     pub _spectator_ids: Vec<i32>,
     // pub last_touch: i32,
     pub _initial_score: i32,
     pub sim_wrapper: RocketsimWrapper,
+    /// team sizes that are sampled on every reset, the gym pads to the largest one if this is set
+    pub team_sizes: Option<TeamSizes>,
+    /// scripted bots and the index in `state.players` of the car that each one drives
+    pub bots: Vec<(usize, Box<dyn Bot>)>,
+    /// self-play opponents that drive the cars of one team, see [OpponentPool]
+    pub opponent_pool: Option<OpponentPool>,
+    /// observations of the cars driven by the opponent pool from the last time the observations were built
+    pub opponent_obs: Vec<Vec<f32>>,
+    /// clock of a full match, goals start a new round from a kickoff instead of ending the episode
+    pub match_clock: Option<MatchClock>,
 }
 
 /// Config struct that takes mutators, team size, tick skip, and spawn opponents.
+/// `gravity` and `boost_consumption` are multipliers of the defaults, the other mutators are set in `mutators`.
 /// Should be used in the `make` function.
 /// 
 /// # Default
@@ -43,6 +70,11 @@ pub struct GameMatch {
 ///         tick_skip: 8, 
 ///         spawn_opponents: true, 
 ///         car_config: CarConfig::octane(),
+///         car_bodies: CarBodies::Same,
+///         step_mode: StepMode::Final,
+///         game_mode: GameMode::Soccar,
+///         mutators: Mutators::default(),
+///         kickoff_phase: None,
 ///     }
 /// }
 /// ```
@@ -54,7 +86,130 @@ pub struct GameConfig {
     pub team_size: usize,
     pub tick_skip: usize,
     pub spawn_opponents: bool,
+    /// Car config of the cars that are not given a body by `car_bodies`
     pub car_config: &'static CarConfig,
+    /// Body of each car, see [CarBodies]
+    pub car_bodies: CarBodies,
+    /// Which states are decoded while stepping through the tick skip
+    pub step_mode: StepMode,
+    /// Arena and rules of the match
+    pub game_mode: GameMode,
+    /// RocketSim mutators that are changed from the defaults of the game mode
+    pub mutators: Mutators,
+    /// Countdown of the kickoffs that are placed by the state setter, the cars can drive right away without it
+    pub kickoff_phase: Option<KickoffPhase>,
+}
+
+/// Decides which state(s) the sim returns from a step of `tick_skip` ticks.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StepMode {
+    /// The state after all of the ticks
+    #[default]
+    Final,
+    /// The state after the first tick, the remaining ticks are stepped after decoding (the behavior of older versions)
+    FirstTick,
+    /// The state after all of the ticks as well as the states after each of the ticks before it,
+    /// which are given to the components through their `sub_steps` methods and returned in `StepResult::sub_states`
+    History,
+}
+
+/// The RocketSim game modes that the gym can be played in.
+///
+/// The mode picks the arena that is built and is given to the components through [GameState::game_mode],
+/// see its methods for the goals, ball radius and boost pads of each mode.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameMode {
+    #[default]
+    Soccar,
+    Hoops,
+    /// Soccar field where the ball seeks the goal of the team that did not touch it last
+    Heatseeker,
+    /// Soccar field with the hockey puck
+    Snowday,
+    /// No arena at all (no floor, walls, goals or boost pads) and the ball is not in play, eg. for aerial control training
+    TheVoid,
+}
+
+impl GameMode {
+    /// Value of [GameState::game_type] for this mode
+    pub fn game_type(self) -> i32 {
+        match self {
+            GameMode::Soccar => 0,
+            GameMode::Hoops => 1,
+            GameMode::Heatseeker => 2,
+            GameMode::Snowday => 3,
+            GameMode::TheVoid => 4,
+        }
+    }
+
+    /// Mode of a [GameState::game_type], unknown values are treated as soccar
+    pub fn from_game_type(game_type: i32) -> Self {
+        match game_type {
+            1 => GameMode::Hoops,
+            2 => GameMode::Heatseeker,
+            3 => GameMode::Snowday,
+            4 => GameMode::TheVoid,
+            _ => GameMode::Soccar,
+        }
+    }
+
+    pub fn has_goals(self) -> bool {
+        self != GameMode::TheVoid
+    }
+
+    /// Radius of the ball, or of the puck in snowday
+    pub fn ball_radius(self) -> f32 {
+        match self {
+            GameMode::Hoops => HOOPS_BALL_RADIUS,
+            GameMode::Snowday => PUCK_RADIUS,
+            GameMode::Soccar | GameMode::Heatseeker | GameMode::TheVoid => BALL_RADIUS,
+        }
+    }
+
+    /// Center of the goal that `team` defends, in hoops this is the center of the rim
+    pub fn goal_center(self, team: i32) -> Position {
+        match (self, team) {
+            (GameMode::Hoops, BLUE_TEAM) => BLUE_HOOP_CENTER,
+            (GameMode::Hoops, _) => ORANGE_HOOP_CENTER,
+            (_, BLUE_TEAM) => BLUE_GOAL_CENTER,
+            (_, _) => ORANGE_GOAL_CENTER,
+        }
+    }
+
+    /// Back of the net of the goal that `team` defends, hoops has no net so this is the same as [goal_center](GameMode::goal_center)
+    pub fn goal_back(self, team: i32) -> Position {
+        match (self, team) {
+            (GameMode::Hoops, _) => self.goal_center(team),
+            (_, BLUE_TEAM) => BLUE_GOAL_BACK,
+            (_, _) => ORANGE_GOAL_BACK,
+        }
+    }
+
+    /// Locations of the boost pads of the arena, the void has none
+    pub fn boost_locations(self) -> &'static [[f32; 3]] {
+        match self {
+            GameMode::Hoops => &HOOPS_BOOST_LOCATIONS,
+            GameMode::TheVoid => &[],
+            GameMode::Soccar | GameMode::Heatseeker | GameMode::Snowday => &BOOST_LOCATIONS,
+        }
+    }
+
+    /// Amount of boost pads in the arena, the pads of a [GameState] after this amount are unused
+    pub fn num_pads(self) -> usize {
+        self.boost_locations().len()
+    }
+}
+
+impl From<GameMode> for rocketsim_rs::sim::GameMode {
+    fn from(game_mode: GameMode) -> Self {
+        match game_mode {
+            GameMode::Soccar => rocketsim_rs::sim::GameMode::Soccar,
+            GameMode::Hoops => rocketsim_rs::sim::GameMode::Hoops,
+            GameMode::Heatseeker => rocketsim_rs::sim::GameMode::Heatseeker,
+            GameMode::Snowday => rocketsim_rs::sim::GameMode::Snowday,
+            GameMode::TheVoid => rocketsim_rs::sim::GameMode::TheVoid,
+        }
+    }
 }
 
 impl Default for GameConfig {
@@ -66,6 +221,11 @@ impl Default for GameConfig {
             tick_skip: 8, 
             spawn_opponents: true, 
             car_config: CarConfig::octane(),
+            car_bodies: CarBodies::Same,
+            step_mode: StepMode::Final,
+            game_mode: GameMode::Soccar,
+            mutators: Mutators::default(),
+            kickoff_phase: None,
         }
     }
 }
@@ -88,8 +248,9 @@ impl GameMatch {
 
         // rocketsim start
         let sim_wrapper = RocketsimWrapper::new(config.game_config);
+        let seed = config.seed;
 
-        GameMatch {
+        let mut game_match = GameMatch {
             game_config: config.game_config,
             _reward_fn: config.reward_fn,
             _terminal_condition: config.terminal_condition,
@@ -97,14 +258,24 @@ impl GameMatch {
             _action_parser: config.action_parser,
             _state_setter: config.state_setter,
             agents: num_agents,
-            observation_space: Vec::<usize>::new(),
+            observation_space: Space::default(),
             use_single_obs: config.use_single_obs,
-            action_space: Vec::<usize>::new(),
+            action_space: Space::default(),
             _prev_actions: vec![vec![0.; 8]; num_agents],
             _spectator_ids: vec![0; 6],
             _initial_score: 0,
             sim_wrapper,
+            team_sizes: config.team_sizes,
+            bots: config.bots,
+            opponent_pool: config.opponent_pool,
+            opponent_obs: Vec::new(),
+            match_clock: config.match_config.map(MatchClock::new),
+        };
+        if let Some(seed) = seed {
+            game_match.set_seeds(seed);
         }
+        game_match.detect_spaces();
+        game_match
     }
 
     pub fn episode_reset(&mut self, initial_state: &GameState) {
@@ -118,56 +289,241 @@ impl GameMatch {
             self._obs_builder.iter_mut().map(|func| func.reset(initial_state)).for_each(drop);
         }
         self._initial_score = initial_state.blue_score - initial_state.orange_score;
+        for (_, bot) in self.bots.iter_mut() {
+            bot.reset(initial_state);
+        }
+        if let Some(pool) = self.opponent_pool.as_mut() {
+            pool.reset_current(initial_state);
+        }
     }
 
-    pub fn build_observations(&mut self, state: &GameState) -> Vec<Vec<f32>> {
-        if !self.use_single_obs {
-            let obs_build_len = self._obs_builder.len();
-            let player_len = state.players.len();
-            assert!(obs_build_len >= player_len, "not enough observation builders (len: {obs_build_len}) were provided for the amount of players (len: {player_len})");
+    /// true if the car at `slot` (index in `state.players`) is driven by a bot
+    pub fn is_bot(&self, slot: usize) -> bool {
+        self.bots.iter().any(|(bot_slot, _)| *bot_slot == slot)
+    }
+
+    /// true if the car at `slot` out of `players` cars is driven by the opponent pool (and not by a bot)
+    pub fn is_opponent(&self, slot: usize, players: usize) -> bool {
+        let pool = match &self.opponent_pool {
+            Some(val) => val,
+            None => return false,
+        };
+        // blue cars are spawned first so they come first in the players, which are sorted by car id
+        let team = if self.game_config.spawn_opponents && slot >= players / 2 { ORANGE_TEAM } else { BLUE_TEAM };
+        team == pool.team() && !self.is_bot(slot)
+    }
+
+    /// true for the cars out of `players` that are controlled by the trainer
+    pub(crate) fn learner_mask(&self, players: usize) -> Vec<bool> {
+        (0..players).map(|slot| !self.is_bot(slot) && !self.is_opponent(slot, players)).collect()
+    }
+
+    /// amount of agents out of `players` cars that are controlled by the trainer
+    fn learner_count(&self, players: usize) -> usize {
+        self.learner_mask(players).into_iter().filter(|is_learner| *is_learner).count()
+    }
+
+    /// Picks the opponent of the next episode if there is an opponent pool
+    pub fn sample_opponent(&mut self) -> Result<()> {
+        match self.opponent_pool.as_mut() {
+            Some(pool) => pool.sample(),
+            None => Ok(()),
+        }
+    }
+
+    /// Which opponent is being played, `result` should be given on the step that ends the episode to record the outcome
+    pub fn opponent_report(&mut self, result: Option<i32>) -> Option<OpponentReport> {
+        self.opponent_pool.as_mut()?.report(result)
+    }
+
+    /// amount of agents in the match that the trainer controls, this is what obs, rewards and actions are given for
+    pub fn learners(&self) -> usize {
+        self.learner_count(self.agents)
+    }
+
+    fn check_obs_builders(&self, state: &GameState) -> Result<()> {
+        let obs_build_len = self._obs_builder.len();
+        let player_len = self.learner_count(state.players.len());
+        if obs_build_len == 0 || (!self.use_single_obs && obs_build_len < player_len) {
+            return Err(Error::ObsBuilderCount { builders: obs_build_len, players: player_len });
         }
+        Ok(())
+    }
+
+    pub fn build_observations(&mut self, state: &GameState) -> Result<Vec<Vec<f32>>> {
+        self.check_obs_builders(state)?;
 
         if self.use_single_obs {
             self._obs_builder[0].pre_step(state, &self.game_config);
+        } else {
+            self._obs_builder.iter_mut().map(|func| func.pre_step(state, &self.game_config)).for_each(drop);
+        }
+
+        let players_len = state.players.len();
+        let learner_mask = self.learner_mask(players_len);
+        let mut obs = Vec::with_capacity(players_len);
+        for (player, _) in state.players.iter().zip(&learner_mask).filter(|(_, is_learner)| **is_learner) {
+            let builder = if self.use_single_obs { 0 } else { obs.len() };
+            obs.push(self._obs_builder[builder].build_obs(player, state, &self.game_config));
+        }
+
+        self.build_opponent_obs(state);
+        Ok(obs)
+    }
+
+    /// Builds the obs of the opponent for [parse_actions](GameMatch::parse_actions), the first builder must have seen `state` in `pre_step`
+    fn build_opponent_obs(&mut self, state: &GameState) {
+        // the opponents use the first builder, the same as the learners with a single builder
+        self.opponent_obs.clear();
+        if self.opponent_pool.is_some() {
+            let players_len = state.players.len();
+            for (slot, player) in state.players.iter().enumerate() {
+                if self.is_opponent(slot, players_len) {
+                    let opponent_obs = self._obs_builder[0].build_obs(player, state, &self.game_config);
+                    self.opponent_obs.push(opponent_obs);
+                }
+            }
+        }
+    }
 
-            state.players
-            .iter()
-            .map(|player| self._obs_builder[0].build_obs(player, state, &self.game_config))
-            .collect()
+    /// Same as [build_observations](GameMatch::build_observations) but writes into `obs` (row major, one row per player)
+    ///
+    /// This does not allocate unless there are [GameMatch::team_sizes], [GameMatch::bots] or a [GameMatch::opponent_pool]:
+    /// the obs are then built with [build_observations](GameMatch::build_observations) (the obs of the bots and
+    /// opponents are built or skipped there), padded like [pad_observations](GameMatch::pad_observations) and copied into `obs`.
+    pub fn build_observations_into(&mut self, state: &GameState, obs: &mut [f32]) -> Result<()> {
+        self.check_obs_builders(state)?;
+
+        if self.team_sizes.is_some() || !self.bots.is_empty() || self.opponent_pool.is_some() {
+            let mut padded_obs = self.build_observations(state)?;
+            self.pad_observations(&mut padded_obs)?;
+            let padded_obs = padded_obs.concat();
+            if padded_obs.len() != obs.len() {
+                return Err(Error::BufferLength { buffer: "obs", len: obs.len(), expected: padded_obs.len() });
+            }
+            obs.copy_from_slice(&padded_obs);
+            return Ok(());
+        }
+
+        if self.use_single_obs {
+            self._obs_builder[0].pre_step(state, &self.game_config);
+            self._obs_builder[0].build_all_obs_into(state, &self.game_config, obs)
         } else {
             self._obs_builder.iter_mut().map(|func| func.pre_step(state, &self.game_config)).for_each(drop);
 
-            state.players
-            .iter()
-            .zip(&mut self._obs_builder)
-            .map(|(player, func)| func.build_obs(player, state, &self.game_config))
-            .collect()
+            let player_len = state.players.len();
+            if player_len == 0 {
+                return Ok(());
+            }
+            if !obs.len().is_multiple_of(player_len) {
+                return Err(Error::BufferLength { buffer: "obs", len: obs.len(), expected: obs.len() / player_len * player_len });
+            }
+            let obs_len = obs.len() / player_len;
+            for (i, (player, func)) in state.players.iter().zip(&mut self._obs_builder).enumerate() {
+                func.build_obs_into(player, state, &self.game_config, &mut obs[i * obs_len..(i + 1) * obs_len])?;
+            }
+            Ok(())
         }
     }
 
     pub fn get_rewards(&mut self, state: &GameState, done: bool) -> Vec<f32> {
-        let mut rewards = Vec::<f32>::with_capacity(self.agents);
+        let mut rewards = vec![0.; self.learner_count(state.players.len())];
+        self.fill_rewards(state, done, &mut rewards);
+        rewards
+    }
+
+    /// Same as [get_rewards](GameMatch::get_rewards) but writes into `rewards`, which must have one value per player
+    /// (or per agent of [max_agents](GameMatch::max_agents) with [GameMatch::team_sizes], the padding is zeroed)
+    pub fn get_rewards_into(&mut self, state: &GameState, done: bool, rewards: &mut [f32]) -> Result<()> {
+        let players_len = self.learner_count(state.players.len());
+        let expected = if self.team_sizes.is_some() { self.max_agents().max(players_len) } else { players_len };
+        if rewards.len() != expected {
+            return Err(Error::BufferLength { buffer: "rewards", len: rewards.len(), expected });
+        }
+        let (player_rewards, padding) = rewards.split_at_mut(players_len);
+        self.fill_rewards(state, done, player_rewards);
+        padding.fill(0.);
+        Ok(())
+    }
 
+    fn fill_rewards(&mut self, state: &GameState, done: bool, rewards: &mut [f32]) {
         self._reward_fn.pre_step(state);
 
-        for player in state.players.iter() {
+        let learner_mask = self.learner_mask(state.players.len());
+        let learners = state.players.iter().zip(learner_mask).filter(|(_, is_learner)| *is_learner);
+        for ((player, _), reward) in learners.zip(rewards.iter_mut()) {
             if done {
-                rewards.push(self._reward_fn.get_final_reward(player, state));
+                *reward = self._reward_fn.get_final_reward(player, state);
             } else {
-                rewards.push(self._reward_fn.get_reward(player, state));
+                *reward = self._reward_fn.get_reward(player, state);
             }
         }
+    }
 
-        // if rewards.len() == 1 {
-        //     return vec![rewards[0]]
-        // } else {
-        //     return rewards
-        // }
-        rewards
+    /// Gives the intermediate states of a step to the obs builders, reward function and terminal condition
+    pub fn sub_steps(&mut self, states: &[GameState]) {
+        if states.is_empty() {
+            return;
+        }
+        for obs_builder in self._obs_builder.iter_mut() {
+            obs_builder.sub_steps(states, &self.game_config);
+        }
+        self._reward_fn.sub_steps(states);
+        self._terminal_condition.sub_steps(states);
     }
 
-    pub fn is_done(&mut self, state: &GameState) -> bool {
-        self._terminal_condition.is_terminal(state)
+    /// Terminal signal of the terminal condition, which is also terminated with `"match_ended"` when a full match is over
+    pub fn get_terminal_signal(&mut self, state: &GameState) -> TerminalSignal {
+        let signal = self._terminal_condition.terminal_signal(state);
+        match &self.match_clock {
+            Some(clock) if clock.is_finished() => signal.merge(TerminalSignal::terminated("match_ended")),
+            _ => signal,
+        }
+    }
+
+    /// Starts a full match from the initial state of the episode and writes the clock to it,
+    /// a kickoff that was placed by the state setter gets the countdown of the match
+    pub fn reset_match_clock(&mut self, initial_state: &mut GameState) {
+        let clock = match self.match_clock.as_mut() {
+            Some(val) => val,
+            None => return,
+        };
+        if initial_state.is_kickoff {
+            self.sim_wrapper.start_kickoff(Some(clock.kickoff_phase(self.game_config.kickoff_phase)));
+            self.sim_wrapper.write_kickoff(initial_state);
+        }
+        clock.reset(initial_state);
+    }
+
+    /// Resets the sim to a kickoff if a goal was scored in the last step of a full match, returns the kickoff state in that case.
+    /// The obs of the opponent of the [GameMatch::opponent_pool] are rebuilt for the kickoff state.
+    pub fn match_kickoff(&mut self) -> Result<Option<GameState>> {
+        let clock = match self.match_clock.as_mut() {
+            Some(val) if val.kickoff_pending() => val,
+            _ => return Ok(None),
+        };
+        let mut state = self.sim_wrapper.reset_to_kickoff(Some(clock.kickoff_phase(self.game_config.kickoff_phase)))?;
+        clock.start_round();
+        clock.write(&mut state);
+
+        // the opponent obs were built for the goal, the opponent has to act on the kickoff
+        if self.opponent_pool.is_some() && !self._obs_builder.is_empty() {
+            self._obs_builder[0].pre_step(&state, &self.game_config);
+            self.build_opponent_obs(&state);
+        }
+        Ok(Some(state))
+    }
+
+    /// Advances the clock of a full match by a step and writes it to the state, the sub-step states get the same clock
+    pub fn update_match_clock(&mut self, state: &mut GameState, sub_states: &mut [GameState]) {
+        let clock = match self.match_clock.as_mut() {
+            Some(val) => val,
+            None => return,
+        };
+        clock.update(state, self.game_config.tick_skip as u64);
+        for sub_state in sub_states.iter_mut() {
+            clock.write(sub_state);
+        }
     }
 
     pub fn get_result(&self, state: &GameState) -> i32 {
@@ -175,35 +531,172 @@ impl GameMatch {
         current_score - self._initial_score
     }
 
-    pub fn get_state(&mut self) -> GameState {
-        self.sim_wrapper.get_rlgym_gamestate(false).0
+    pub fn get_state(&mut self) -> Result<GameState> {
+        Ok(self.sim_wrapper.get_rlgym_gamestate(false)?.0)
     }
 
-    pub fn parse_actions(&mut self, actions: Vec<Vec<f32>>, state: &GameState) -> Vec<Vec<f32>> {
+    pub fn get_stats(&self) -> Vec<wrapper::Stats> {
+        self.sim_wrapper.get_stats()
+    }
+
+    /// Parses the actions of the learning agents and merges in the controls of the bots and the actions of the opponent,
+    /// the returned actions have one entry per player in the order of `state.players`.
+    pub fn parse_actions(&mut self, actions: Vec<Vec<f32>>, state: &GameState) -> Result<Vec<Vec<f32>>> {
         let parsed_actions = self._action_parser.parse_actions(actions, state);
         let acts_len = parsed_actions.len();
-        let players_len = state.players.len();
-        assert!(acts_len == players_len, "parsed actions was not the same length (len: {acts_len}) as player count (len: {players_len})");
+        let players_len = self.learner_count(state.players.len());
+        if acts_len != players_len {
+            return Err(Error::ActionCount { actions: acts_len, players: players_len });
+        }
+        if let Some(action) = parsed_actions.iter().find(|action| action.len() != 8) {
+            return Err(Error::ActionLength { len: action.len() });
+        }
+
+        let opponent_actions = match self.opponent_pool.as_mut() {
+            Some(pool) => {
+                let opponent_actions = pool.get_actions(&self.opponent_obs, state)?;
+                let opponent_actions = self._action_parser.parse_actions(opponent_actions, state);
+                if opponent_actions.len() != self.opponent_obs.len() {
+                    return Err(Error::ActionCount { actions: opponent_actions.len(), players: self.opponent_obs.len() });
+                }
+                if let Some(action) = opponent_actions.iter().find(|action| action.len() != 8) {
+                    return Err(Error::ActionLength { len: action.len() });
+                }
+                opponent_actions
+            }
+            None => Vec::new(),
+        };
+
+        let parsed_actions = if self.bots.is_empty() && self.opponent_pool.is_none() {
+            parsed_actions
+        } else {
+            let learner_mask = self.learner_mask(state.players.len());
+            let mut learner_actions = parsed_actions.into_iter();
+            let mut opponent_actions = opponent_actions.into_iter();
+            let mut merged_actions = Vec::with_capacity(state.players.len());
+            for (slot, player) in state.players.iter().enumerate() {
+                let action = match self.bots.iter_mut().find(|(bot_slot, _)| *bot_slot == slot) {
+                    Some((_, bot)) => bot.get_controls(player, state).into_array().to_vec(),
+                    None if learner_mask[slot] => learner_actions.next().unwrap_or_else(|| vec![0.; 8]),
+                    None => opponent_actions.next().unwrap_or_else(|| vec![0.; 8]),
+                };
+                merged_actions.push(action);
+            }
+            merged_actions
+        };
         self._prev_actions = parsed_actions.to_vec();
-        parsed_actions
+        Ok(parsed_actions)
     }
 
-    pub fn get_reset_state(&mut self, state: &GameState) -> StateWrapper {
+    pub fn get_reset_state(&mut self, state: &GameState) -> Result<StateWrapper> {
         let mut new_state = self._state_setter.build_wrapper(self.game_config.team_size, self.game_config.spawn_opponents, Some(state));
-        self._state_setter.reset(&mut new_state);
-        new_state
+        new_state.game_mode = self.game_config.game_mode;
+        self._state_setter.reset(&mut new_state)?;
+        Ok(new_state)
     }
 
+    /// Seeds every random part of the match from `seed`, each one with its own [SeedStream].
+    ///
+    /// Two matches with the same config and seed give the same states for the same actions
+    /// (which can be checked with [GameState::state_hash]). Builders that are created later by the
+    /// [TeamSizes] factory are not seeded.
     pub fn set_seeds(&mut self, seed: u64) {
-        self._state_setter.set_seed(seed);
+        self.sim_wrapper.set_seed(SeedStream::Arena.derive(seed));
+        self._state_setter.set_seed(SeedStream::StateSetter.derive(seed));
+        self._reward_fn.set_seed(SeedStream::RewardFn.derive(seed));
+        self._terminal_condition.set_seed(SeedStream::TerminalCondition.derive(seed));
+        self._action_parser.set_seed(SeedStream::ActionParser.derive(seed));
+        let obs_seed = SeedStream::ObsBuilder.derive(seed);
+        for (i, builder) in self._obs_builder.iter_mut().enumerate() {
+            builder.set_seed(derive_seed(obs_seed, i as u64));
+        }
+        if let Some(team_sizes) = self.team_sizes.as_mut() {
+            team_sizes.set_seed(SeedStream::TeamSizes.derive(seed));
+        }
+        if let Some(pool) = self.opponent_pool.as_mut() {
+            pool.set_seed(SeedStream::OpponentPool.derive(seed));
+        }
+        let bots_seed = SeedStream::Bots.derive(seed);
+        for (slot, bot) in self.bots.iter_mut() {
+            bot.set_seed(derive_seed(bots_seed, *slot as u64));
+        }
+    }
+
+    /// Samples the team size of the next episode from [GameMatch::team_sizes] and rebuilds the cars if it changed,
+    /// returns the new state of the sim in that case.
+    pub fn sample_team_size(&mut self) -> Result<Option<GameState>> {
+        let team_sizes = match self.team_sizes.as_mut() {
+            Some(val) => val,
+            None => return Ok(None),
+        };
+        let option = team_sizes.sample();
+        if option.team_size == self.game_config.team_size && option.spawn_opponents == self.game_config.spawn_opponents {
+            return Ok(None);
+        }
+
+        let new_obs_builder = match &team_sizes.obs_builder_factory {
+            Some(factory) if !self.use_single_obs => Some((0..option.agents()).map(|_| factory()).collect()),
+            _ => None,
+        };
+        let new_config = GameConfig { team_size: option.team_size, spawn_opponents: option.spawn_opponents, ..self.game_config };
+        Ok(Some(self.update_settings(new_config, new_obs_builder)?))
+    }
+
+    /// Samples new car bodies for the next episode with [CarBodies::Random] and rebuilds the cars if they changed,
+    /// returns the new state of the sim in that case.
+    pub fn sample_car_bodies(&mut self) -> Result<Option<GameState>> {
+        if !self.game_config.car_bodies.is_random() {
+            return Ok(None);
+        }
+        Ok(Some(self.update_settings(self.game_config, None)?))
+    }
+
+    /// Amount of agents that the obs, rewards and mask are padded to, this is just the agent count without [GameMatch::team_sizes]
+    ///
+    /// Cars that are driven by bots are not counted.
+    pub fn max_agents(&self) -> usize {
+        match &self.team_sizes {
+            Some(team_sizes) => self.learner_count(team_sizes.max_agents().max(self.agents)),
+            None => self.learners(),
+        }
+    }
+
+    /// true for the agents that are in the match, false for padding
+    pub fn agent_mask(&self) -> Vec<bool> {
+        let mut mask = vec![false; self.max_agents()];
+        mask[..self.learners()].fill(true);
+        mask
+    }
+
+    /// Pads the observations to [max_agents](GameMatch::max_agents) rows (and each row to the `pad_obs_len` of the team sizes)
+    pub fn pad_observations(&self, obs: &mut Vec<Vec<f32>>) -> Result<()> {
+        let team_sizes = match &self.team_sizes {
+            Some(val) => val,
+            None => return Ok(()),
+        };
+        if let Some(pad_obs_len) = team_sizes.pad_obs_len {
+            for player_obs in obs.iter_mut() {
+                if player_obs.len() > pad_obs_len {
+                    return Err(Error::BufferLength { buffer: "padded obs", len: player_obs.len(), expected: pad_obs_len });
+                }
+                player_obs.resize(pad_obs_len, 0.);
+            }
+        }
+        let obs_len = team_sizes.pad_obs_len.or(obs.first().map(|player_obs| player_obs.len())).unwrap_or(0);
+        obs.resize(self.max_agents(), vec![0.; obs_len]);
+        Ok(())
+    }
+
+    /// Pads the rewards with zeros to [max_agents](GameMatch::max_agents)
+    pub fn pad_rewards(&self, rewards: &mut Vec<f32>) {
+        rewards.resize(self.max_agents().max(rewards.len()), 0.);
     }
 
     pub fn get_config(&self) -> GameConfig {
         self.game_config
     }
 
-    pub fn update_settings(&mut self, new_config: GameConfig, new_obs_builder: Option<Vec<Box<dyn ObsBuilder>>>) -> GameState {
-        // TODO: do extra modes and more mutators
+    pub fn update_settings(&mut self, new_config: GameConfig, new_obs_builder: Option<Vec<Box<dyn ObsBuilder>>>) -> Result<GameState> {
         self.game_config = new_config;
         let car_count = if new_config.spawn_opponents {
             new_config.team_size * 2
@@ -212,11 +705,22 @@ impl GameMatch {
         };
         self.agents = car_count;
         if let Some(val) = new_obs_builder { self._obs_builder = val }
-        self.sim_wrapper.set_game_config(new_config, false).0
+        Ok(self.sim_wrapper.set_game_config(new_config, false)?.0)
     }
 
-    fn _auto_detech_obs_space(&mut self) {
-        self.observation_space = self._obs_builder[0].get_obs_space();
+    /// Sets the observation and action space from the first observation builder and the action parser,
+    /// the observation space is the padded length with the `pad_obs_len` of the [TeamSizes]
+    pub fn detect_spaces(&mut self) {
+        let obs_space = self._obs_builder.first_mut().map(|builder| builder.get_obs_space()).unwrap_or_default();
+        self.observation_space = match self.team_sizes.as_ref().and_then(|team_sizes| team_sizes.pad_obs_len) {
+            // the padding is zeros so the bounds of the builder still hold if they include 0
+            Some(pad_obs_len) => match obs_space {
+                Space::Box { low, high, dtype, .. } => Space::Box { low: low.min(0.), high: high.max(0.), shape: vec![pad_obs_len], dtype },
+                _ => Space::box_f32(f32::NEG_INFINITY, f32::INFINITY, pad_obs_len),
+            },
+            None => obs_space,
+        };
+        self.action_space = self._action_parser.get_action_space();
     }
 }
 
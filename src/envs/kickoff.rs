@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+/// Kickoff countdown of Rocket League, which runs whenever the state setter places a kickoff
+/// (see [StateWrapper::kickoff](crate::state_setters::wrappers::state_wrapper::StateWrapper::kickoff)).
+///
+/// The cars are frozen until the countdown is over, the actions of those ticks are ignored. With `hold_ball` the ball is
+/// held at its kickoff position during the countdown and after it until the first touch. In hoops and heatseeker the ball
+/// is launched at the kickoff so it is only held for the countdown and then released with its kickoff velocity.
+///
+/// The kickoff shows up in the state as [GameState::is_kickoff](crate::gamestates::game_state::GameState::is_kickoff)
+/// and [GameState::kickoff_time_remaining](crate::gamestates::game_state::GameState::kickoff_time_remaining).
+///
+/// # Default
+/// ```rust,ignore
+/// fn default() -> Self {
+///     Self {
+///         countdown: 3.,
+///         hold_ball: true,
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KickoffPhase {
+    /// Seconds that the cars are frozen for
+    pub countdown: f32,
+    pub hold_ball: bool,
+}
+
+impl Default for KickoffPhase {
+    fn default() -> Self {
+        Self {
+            countdown: 3.,
+            hold_ball: true,
+        }
+    }
+}
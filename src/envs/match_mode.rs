@@ -0,0 +1,155 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{common_values::TICKS_PER_SECOND, gamestates::game_state::GameState};
+
+use super::kickoff::KickoffPhase;
+
+/// Rules of a full match, see [MatchClock].
+///
+/// # Default
+/// ```rust,ignore
+/// fn default() -> Self {
+///     Self {
+///         game_length: 300.,
+///         kickoff_countdown: 3.,
+///         overtime: true,
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MatchConfig {
+    /// Length of regulation time in seconds
+    pub game_length: f32,
+    /// Seconds that the cars are frozen for at each kickoff, this replaces the countdown of the
+    /// [GameConfig::kickoff_phase](crate::envs::game_match::GameConfig::kickoff_phase)
+    pub kickoff_countdown: f32,
+    /// Plays overtime (next goal wins) when regulation ends in a tie, the match ends in a draw otherwise
+    pub overtime: bool,
+}
+
+impl Default for MatchConfig {
+    fn default() -> Self {
+        Self {
+            game_length: 300.,
+            kickoff_countdown: 3.,
+            overtime: true,
+        }
+    }
+}
+
+/// Game clock and kickoffs of a full match, this is what [GameMatch](crate::envs::game_match::GameMatch) runs when
+/// [MakeConfig::match_config](crate::make::MakeConfig::match_config) is set.
+///
+/// A goal does not end the episode, the step after it starts a new round from a kickoff instead (the actions of
+/// that step are ignored) and the cars are frozen for the countdown. Like in Rocket League the clock only runs
+/// once the ball has been touched after the kickoff ([GameState::is_kickoff] is false). The match ends when regulation
+/// time runs out with one team ahead or with the first goal of overtime, which terminates the episode with the reason `"match_ended"`.
+///
+/// The clock is written to [GameState::game_seconds_remaining], [GameState::is_overtime] and [GameState::round_num].
+#[derive(Clone, Debug)]
+pub struct MatchClock {
+    config: MatchConfig,
+    ticks_remaining: u64,
+    is_overtime: bool,
+    round_num: u32,
+    kickoff_pending: bool,
+    finished: bool,
+    blue_score: i32,
+    orange_score: i32,
+}
+
+impl MatchClock {
+    pub fn new(config: MatchConfig) -> Self {
+        MatchClock {
+            config,
+            ticks_remaining: seconds_to_ticks(config.game_length),
+            is_overtime: false,
+            round_num: 0,
+            kickoff_pending: false,
+            finished: false,
+            blue_score: 0,
+            orange_score: 0,
+        }
+    }
+
+    pub fn config(&self) -> MatchConfig {
+        self.config
+    }
+
+    /// Kickoff phase of the kickoffs of the match, `phase` with the countdown of the match
+    pub fn kickoff_phase(&self, phase: Option<KickoffPhase>) -> KickoffPhase {
+        KickoffPhase { countdown: self.config.kickoff_countdown, ..phase.unwrap_or_default() }
+    }
+
+    /// Starts a new match from `initial_state`, which is the first kickoff
+    pub fn reset(&mut self, initial_state: &mut GameState) {
+        *self = MatchClock::new(self.config);
+        self.blue_score = initial_state.blue_score;
+        self.orange_score = initial_state.orange_score;
+        self.start_round();
+        self.write(initial_state);
+    }
+
+    /// true if a goal was scored on the last step, the next step has to start from a kickoff
+    pub fn kickoff_pending(&self) -> bool {
+        self.kickoff_pending
+    }
+
+    /// Starts the next round, called after the sim was reset to a kickoff
+    pub fn start_round(&mut self) {
+        self.kickoff_pending = false;
+        self.round_num += 1;
+    }
+
+    /// true once regulation time ran out with one team ahead or a goal was scored in overtime
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Advances the clock by the `ticks` that were stepped to reach `state` and writes it to the state
+    pub fn update(&mut self, state: &mut GameState, ticks: u64) {
+        let round_active = !state.is_kickoff && !self.kickoff_pending;
+        if round_active && !self.is_overtime {
+            self.ticks_remaining = self.ticks_remaining.saturating_sub(ticks);
+        }
+
+        let goal = state.blue_score != self.blue_score || state.orange_score != self.orange_score;
+        self.blue_score = state.blue_score;
+        self.orange_score = state.orange_score;
+        if goal {
+            if self.is_overtime {
+                self.finished = true;
+            } else if self.ticks_remaining > 0 {
+                self.kickoff_pending = true;
+            } else {
+                // a goal at the buzzer still counts
+                self.end_regulation();
+            }
+        } else if round_active && !self.is_overtime && self.ticks_remaining == 0 {
+            self.end_regulation();
+        }
+        self.write(state);
+    }
+
+    /// Writes the clock to the clock fields of `state`
+    pub fn write(&self, state: &mut GameState) {
+        state.game_seconds_remaining = self.ticks_remaining as f32 / TICKS_PER_SECOND as f32;
+        state.is_overtime = self.is_overtime;
+        state.round_num = self.round_num;
+    }
+
+    fn end_regulation(&mut self) {
+        if self.blue_score != self.orange_score || !self.config.overtime {
+            self.finished = true;
+        } else {
+            // overtime starts from a kickoff as well
+            self.is_overtime = true;
+            self.kickoff_pending = true;
+        }
+    }
+}
+
+fn seconds_to_ticks(seconds: f32) -> u64 {
+    (seconds.max(0.) * TICKS_PER_SECOND as f32).round() as u64
+}
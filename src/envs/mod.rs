@@ -1,2 +1,7 @@
 pub mod environment;
 pub mod game_match;
+pub mod team_sizes;
+pub mod mutators;
+pub mod car_bodies;
+pub mod kickoff;
+pub mod match_mode;
@@ -0,0 +1,159 @@
+use rocketsim_rs::sim::{DemoMode, GameMode, MutatorConfig};
+use serde::{Deserialize, Serialize};
+
+/// Changes to RocketSim's mutators for [GameConfig::mutators](crate::envs::game_match::GameConfig::mutators).
+///
+/// Every field that is `None` keeps the RocketSim default of the game mode (eg. the mass of the puck in snowday).
+/// Gravity and boost consumption are set with the multipliers of the game config.
+/// The masses are in RocketSim's physics units, everything else is in unreal units and seconds.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Mutators {
+    pub car_mass: Option<f32>,
+    pub car_world_friction: Option<f32>,
+    pub car_world_restitution: Option<f32>,
+    pub ball_mass: Option<f32>,
+    pub ball_max_speed: Option<f32>,
+    pub ball_drag: Option<f32>,
+    pub ball_world_friction: Option<f32>,
+    pub ball_world_restitution: Option<f32>,
+    pub ball_radius: Option<f32>,
+    pub ball_hit_extra_force_scale: Option<f32>,
+    pub jump_accel: Option<f32>,
+    pub jump_immediate_force: Option<f32>,
+    pub boost_accel_ground: Option<f32>,
+    pub boost_accel_air: Option<f32>,
+    /// time a demolished car waits before it respawns
+    pub respawn_delay: Option<f32>,
+    pub bump_cooldown_time: Option<f32>,
+    pub bump_force_scale: Option<f32>,
+    pub boost_pad_cooldown_big: Option<f32>,
+    pub boost_pad_cooldown_small: Option<f32>,
+    /// boost that a car has after respawning, from 0 to 100
+    pub car_spawn_boost_amount: Option<f32>,
+    pub unlimited_flips: Option<bool>,
+    pub unlimited_double_jumps: Option<bool>,
+    pub demo_mode: Option<DemoMode>,
+    pub enable_team_demos: Option<bool>,
+    /// distance of the goal lines from the middle of the field, for the modes with soccar goals
+    pub goal_base_threshold_y: Option<f32>,
+}
+
+impl Mutators {
+    /// Overwrites the values of `config` that are set in these mutators
+    pub fn apply(&self, config: &mut MutatorConfig) {
+        let overrides = [
+            (self.car_mass, &mut config.car_mass),
+            (self.car_world_friction, &mut config.car_world_friction),
+            (self.car_world_restitution, &mut config.car_world_restitution),
+            (self.ball_mass, &mut config.ball_mass),
+            (self.ball_max_speed, &mut config.ball_max_speed),
+            (self.ball_drag, &mut config.ball_drag),
+            (self.ball_world_friction, &mut config.ball_world_friction),
+            (self.ball_world_restitution, &mut config.ball_world_restitution),
+            (self.ball_radius, &mut config.ball_radius),
+            (self.ball_hit_extra_force_scale, &mut config.ball_hit_extra_force_scale),
+            (self.jump_accel, &mut config.jump_accel),
+            (self.jump_immediate_force, &mut config.jump_immediate_force),
+            (self.boost_accel_ground, &mut config.boost_accel_ground),
+            (self.boost_accel_air, &mut config.boost_accel_air),
+            (self.respawn_delay, &mut config.respawn_delay),
+            (self.bump_cooldown_time, &mut config.bump_cooldown_time),
+            (self.bump_force_scale, &mut config.bump_force_scale),
+            (self.boost_pad_cooldown_big, &mut config.boost_pad_cooldown_big),
+            (self.boost_pad_cooldown_small, &mut config.boost_pad_cooldown_small),
+            (self.car_spawn_boost_amount, &mut config.car_spawn_boost_amount),
+            (self.goal_base_threshold_y, &mut config.goal_base_threshold_y),
+        ];
+        for (value, target) in overrides {
+            if let Some(value) = value {
+                *target = value;
+            }
+        }
+
+        if let Some(unlimited_flips) = self.unlimited_flips {
+            config.unlimited_flips = unlimited_flips;
+        }
+        if let Some(unlimited_double_jumps) = self.unlimited_double_jumps {
+            config.unlimited_double_jumps = unlimited_double_jumps;
+        }
+        if let Some(demo_mode) = self.demo_mode {
+            config.demo_mode = demo_mode;
+        }
+        if let Some(enable_team_demos) = self.enable_team_demos {
+            config.enable_team_demos = enable_team_demos;
+        }
+    }
+}
+
+/// The mutators that the arena is running with, reported in [GameState::mutators](crate::gamestates::game_state::GameState::mutators).
+/// The default is the soccar defaults.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ActiveMutators {
+    pub gravity_z: f32,
+    pub boost_used_per_second: f32,
+    pub car_mass: f32,
+    pub car_world_friction: f32,
+    pub car_world_restitution: f32,
+    pub ball_mass: f32,
+    pub ball_max_speed: f32,
+    pub ball_drag: f32,
+    pub ball_world_friction: f32,
+    pub ball_world_restitution: f32,
+    pub ball_radius: f32,
+    pub ball_hit_extra_force_scale: f32,
+    pub jump_accel: f32,
+    pub jump_immediate_force: f32,
+    pub boost_accel_ground: f32,
+    pub boost_accel_air: f32,
+    pub respawn_delay: f32,
+    pub bump_cooldown_time: f32,
+    pub bump_force_scale: f32,
+    pub boost_pad_cooldown_big: f32,
+    pub boost_pad_cooldown_small: f32,
+    pub car_spawn_boost_amount: f32,
+    pub unlimited_flips: bool,
+    pub unlimited_double_jumps: bool,
+    pub demo_mode: DemoMode,
+    pub enable_team_demos: bool,
+    pub goal_base_threshold_y: f32,
+}
+
+impl From<&MutatorConfig> for ActiveMutators {
+    fn from(config: &MutatorConfig) -> Self {
+        ActiveMutators {
+            gravity_z: config.gravity.z,
+            boost_used_per_second: config.boost_used_per_second,
+            car_mass: config.car_mass,
+            car_world_friction: config.car_world_friction,
+            car_world_restitution: config.car_world_restitution,
+            ball_mass: config.ball_mass,
+            ball_max_speed: config.ball_max_speed,
+            ball_drag: config.ball_drag,
+            ball_world_friction: config.ball_world_friction,
+            ball_world_restitution: config.ball_world_restitution,
+            ball_radius: config.ball_radius,
+            ball_hit_extra_force_scale: config.ball_hit_extra_force_scale,
+            jump_accel: config.jump_accel,
+            jump_immediate_force: config.jump_immediate_force,
+            boost_accel_ground: config.boost_accel_ground,
+            boost_accel_air: config.boost_accel_air,
+            respawn_delay: config.respawn_delay,
+            bump_cooldown_time: config.bump_cooldown_time,
+            bump_force_scale: config.bump_force_scale,
+            boost_pad_cooldown_big: config.boost_pad_cooldown_big,
+            boost_pad_cooldown_small: config.boost_pad_cooldown_small,
+            car_spawn_boost_amount: config.car_spawn_boost_amount,
+            unlimited_flips: config.unlimited_flips,
+            unlimited_double_jumps: config.unlimited_double_jumps,
+            demo_mode: config.demo_mode,
+            enable_team_demos: config.enable_team_demos,
+            goal_base_threshold_y: config.goal_base_threshold_y,
+        }
+    }
+}
+
+impl Default for ActiveMutators {
+    fn default() -> Self {
+        ActiveMutators::from(&MutatorConfig::default(GameMode::Soccar))
+    }
+}
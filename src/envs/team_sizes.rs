@@ -0,0 +1,100 @@
+use rand::{distr::{weighted::WeightedIndex, Distribution}, rng, Rng, rngs::SmallRng, SeedableRng};
+
+use crate::{
+    error::{Error, Result},
+    obs_builders::obs_builder::ObsBuilder,
+};
+
+/// Creates a new observation builder for an agent, used when the team size changes between episodes
+pub type ObsBuilderFactory = Box<dyn Fn() -> Box<dyn ObsBuilder> + Send>;
+
+/// One of the match sizes that [TeamSizes] can sample
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TeamSizeOption {
+    pub team_size: usize,
+    pub spawn_opponents: bool,
+    /// relative chance of this option being picked
+    pub weight: f64,
+}
+
+impl TeamSizeOption {
+    pub fn new(team_size: usize, spawn_opponents: bool, weight: f64) -> Self {
+        TeamSizeOption { team_size, spawn_opponents, weight }
+    }
+
+    /// amount of agents in a match of this size
+    pub fn agents(&self) -> usize {
+        if self.spawn_opponents { self.team_size * 2 } else { self.team_size }
+    }
+}
+
+/// Distribution over team sizes that is sampled on every reset of the gym.
+///
+/// The obs, rewards and mask of the gym are padded to [max_agents](TeamSizes::max_agents) so that the
+/// batch size stays the same whatever size is sampled, padded agents have zeroed obs and rewards and their
+/// actions are ignored. `pad_obs_len` pads every observation with zeros for builders whose obs length
+/// depends on the player count (eg. `AdvancedObs::obs_len(max_agents)`).
+///
+/// When `use_single_obs` is false the `obs_builder_factory` is used to create one builder per agent on every
+/// change of team size, without a factory the builders that were given to `make` are kept (so there must be enough of them).
+pub struct TeamSizes {
+    options: Vec<TeamSizeOption>,
+    distribution: WeightedIndex<f64>,
+    rng: SmallRng,
+    pub obs_builder_factory: Option<ObsBuilderFactory>,
+    pub pad_obs_len: Option<usize>,
+}
+
+impl TeamSizes {
+    /// Returns [Error::Config] when there are no options or the weights are invalid (negative, not finite or all zero)
+    pub fn new(options: Vec<TeamSizeOption>, seed: Option<u64>) -> Result<Self> {
+        if options.is_empty() {
+            return Err(Error::Config("TeamSizes requires at least one option".to_string()));
+        }
+        let distribution = WeightedIndex::new(options.iter().map(|option| option.weight))
+            .map_err(|err| Error::Config(format!("invalid team size weights: {err}")))?;
+        let seed = match seed {
+            Some(seed) => seed,
+            None => rng().random_range(0..10000),
+        };
+        let rng = SmallRng::seed_from_u64(seed);
+        Ok(TeamSizes { options, distribution, rng, obs_builder_factory: None, pad_obs_len: None })
+    }
+
+    /// Sets the factory that builds the observation builders of new agents
+    pub fn with_obs_builder_factory(mut self, factory: ObsBuilderFactory) -> Self {
+        self.obs_builder_factory = Some(factory);
+        self
+    }
+
+    /// Pads every observation with zeros to `obs_len`
+    pub fn with_pad_obs_len(mut self, obs_len: usize) -> Self {
+        self.pad_obs_len = Some(obs_len);
+        self
+    }
+
+    pub fn options(&self) -> &[TeamSizeOption] {
+        &self.options
+    }
+
+    /// the largest amount of agents of all of the options, this is what the gym pads to
+    pub fn max_agents(&self) -> usize {
+        self.options.iter().map(|option| option.agents()).max().unwrap_or(0)
+    }
+
+    pub fn sample(&mut self) -> TeamSizeOption {
+        self.options[self.distribution.sample(&mut self.rng)]
+    }
+
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
+    }
+
+    pub(crate) fn rng(&self) -> &SmallRng {
+        &self.rng
+    }
+
+    pub(crate) fn set_rng(&mut self, rng: SmallRng) {
+        self.rng = rng;
+    }
+}
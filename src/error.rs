@@ -0,0 +1,99 @@
+use std::{fmt, io};
+
+use rocketsim_rs::NoCarFound;
+
+/// Result type used throughout the crate
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Crate-wide error type.
+///
+/// These are returned instead of panicking so that a misconfigured component can be handled by the caller.
+#[derive(Debug)]
+pub enum Error {
+    /// The state wrapper did not have a car with this (gamestate) car id, this is likely an error from the state setter
+    MissingCar(i32),
+    /// Fewer observation builders were provided than the amount of players
+    ObsBuilderCount { builders: usize, players: usize },
+    /// The action parser did not return one action per player
+    ActionCount { actions: usize, players: usize },
+    /// A parsed action was not of length 8
+    ActionLength { len: usize },
+    /// An observation was not of the same length as the other observations of the [VecGym](crate::VecGym), the
+    /// observations of the agents have to be padded (see [TeamSizes](crate::envs::team_sizes::TeamSizes::pad_obs_len))
+    /// when the amount of players changes the length
+    ObsLength { len: usize, expected: usize },
+    /// A caller-provided buffer (see [Gym::step_into](crate::Gym::step_into)) did not have the expected length
+    BufferLength { buffer: &'static str, len: usize, expected: usize },
+    /// A caller-provided ndarray buffer was not contiguous in standard (row major) layout
+    BufferLayout(&'static str),
+    /// A component does not support snapshots (its `snapshot` returned `None`)
+    Snapshot(&'static str),
+    /// The episode monitor could not write to its file
+    Monitor(io::Error),
+    /// The opponent pool could not pick or run an opponent
+    OpponentPool(&'static str),
+    /// The methods of the [VecGym](crate::VecGym) were called out of order (eg. stepping before the first reset),
+    /// or one of its worker threads exited (eg. after a panic in one of the gyms)
+    VecGym(&'static str),
+    /// A state setter was unable to set the state
+    StateSetter(String),
+    /// The config of the gym is invalid (see [GymBuilder](crate::GymBuilder)), or a config file could not be read or
+    /// one of its components could not be built (see [registry](crate::registry))
+    Config(String),
+    /// Error from RocketSim
+    Sim(NoCarFound),
+    /// Error from the renderer (the renderer is closed after this)
+    Render(io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MissingCar(car_id) => write!(
+                f,
+                "unable to find car with id {car_id} in the state wrapper, this is likely an error from the state setter"
+            ),
+            Error::ObsBuilderCount { builders, players } => write!(
+                f,
+                "not enough observation builders (len: {builders}) were provided for the amount of players (len: {players})"
+            ),
+            Error::ActionCount { actions, players } => {
+                write!(f, "parsed actions was not the same length (len: {actions}) as player count (len: {players})")
+            }
+            Error::ActionLength { len } => write!(f, "parsed action was not of length 8, was of length {len}"),
+            Error::ObsLength { len, expected } => write!(
+                f,
+                "observation size (len: {len}) was not the same for every agent (expected len: {expected}), set a pad_obs_len on the team sizes"
+            ),
+            Error::BufferLength { buffer, len, expected } => {
+                write!(f, "{buffer} buffer was of length {len} but length {expected} was expected")
+            }
+            Error::BufferLayout(buffer) => write!(f, "{buffer} buffer must be contiguous and in standard layout"),
+            Error::Snapshot(component) => write!(f, "the {component} does not support snapshots"),
+            Error::Monitor(err) => write!(f, "monitor error: {err}"),
+            Error::OpponentPool(msg) => write!(f, "opponent pool error: {msg}"),
+            Error::VecGym(msg) => write!(f, "vec gym error: {msg}"),
+            Error::StateSetter(msg) => write!(f, "state setter error: {msg}"),
+            Error::Config(msg) => write!(f, "config error: {msg}"),
+            Error::Sim(err) => write!(f, "sim error: {err}"),
+            Error::Render(err) => write!(f, "render error: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Sim(err) => Some(err),
+            Error::Render(err) => Some(err),
+            Error::Monitor(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<NoCarFound> for Error {
+    fn from(err: NoCarFound) -> Self {
+        Error::Sim(err)
+    }
+}
@@ -6,8 +6,9 @@ use rocketsim_rs::{sim::{BallHitInfo, CarControls}, BoostPad};
 use serde::{Serialize, Deserialize};
 
 use crate::common_values::BLUE_TEAM;
+use crate::envs::{car_bodies::HitboxType, game_match::GameMode, mutators::ActiveMutators};
 use crate::gamestates::physics_object::PhysicsObject;
-use crate::gamestates::player_data::PlayerData;
+use crate::gamestates::player_data::{PlayerData, StepEvents};
 
 use super::physics_object::{Position, Velocity};
 
@@ -26,6 +27,20 @@ pub struct GameState {
     #[serde(with = "serde_arrays")]
     pub inverted_boost_pads: [BoostPad; 34],
     pub tick_num: u64,
+    /// mutators that the arena is running with
+    pub mutators: ActiveMutators,
+    /// true from a kickoff until the ball is first touched
+    pub is_kickoff: bool,
+    /// seconds left of the countdown of the [KickoffPhase](crate::envs::kickoff::KickoffPhase)
+    pub kickoff_time_remaining: f32,
+    /// true while the cars are frozen for the countdown of a kickoff
+    pub is_kickoff_pause: bool,
+    /// seconds of regulation time that are left in a full match, the clock fields are only set with a
+    /// [MatchClock](crate::envs::match_mode::MatchClock)
+    pub game_seconds_remaining: f32,
+    pub is_overtime: bool,
+    /// kickoffs in the match so far, starting at 1 for the first one
+    pub round_num: u32,
 }
 
 // const BOOST_PAD_LENGTH: usize = 34;
@@ -47,6 +62,13 @@ impl Default for GameState {
             boost_pads: [BoostPad::default(); 34],
             inverted_boost_pads: [BoostPad::default(); 34],
             tick_num: 0,
+            mutators: ActiveMutators::default(),
+            is_kickoff: false,
+            kickoff_time_remaining: 0.,
+            is_kickoff_pause: false,
+            game_seconds_remaining: 0.,
+            is_overtime: false,
+            round_num: 0,
         }
     }
 }
@@ -56,6 +78,41 @@ impl GameState {
         GameState::default()
     }
 
+    /// Game mode of the match, from `game_type`
+    pub fn game_mode(&self) -> GameMode {
+        GameMode::from_game_type(self.game_type)
+    }
+
+    /// Hash of the tick, scores, ball, boost pads and the physics and status of every player.
+    ///
+    /// This only depends on the values of the state (not on the platform or the build) so it can be used to check
+    /// that two runs with the same seed and actions give exactly the same trajectory. Stats and the last actions are not included.
+    pub fn state_hash(&self) -> u64 {
+        let mut hasher = StateHasher::new();
+        hasher.write_u64(self.tick_num);
+        hasher.write_i32(self.blue_score);
+        hasher.write_i32(self.orange_score);
+        hasher.write_i32(self.last_touch);
+        hasher.write_physics(&self.ball);
+        for pad in self.boost_pads.iter() {
+            hasher.write_bool(pad.state.is_active);
+            hasher.write_f32(pad.state.cooldown);
+        }
+        for player in self.players.iter() {
+            hasher.write_i32(player.car_id);
+            hasher.write_i32(player.team_num);
+            hasher.write_physics(&player.car_data);
+            hasher.write_f32(player.boost_amount);
+            hasher.write_bool(player.on_ground);
+            hasher.write_bool(player.ball_touched);
+            hasher.write_bool(player.has_jump);
+            hasher.write_bool(player.has_flip);
+            hasher.write_bool(player.is_demoed);
+            hasher.write_u64(player.hitbox_type.index() as u64);
+        }
+        hasher.finish()
+    }
+
     // pub fn decode(&mut self, state_vals: Vec<f32>) {
     //     let mut start = 3;
     //     let num_ball_packets = 1;
@@ -211,6 +268,8 @@ impl GameState {
                     inverted_car_data: PhysicsObject::new(),
                     last_ball_touch_tick: 0,
                     last_actions: CarControls::default(),
+                    step_events: StepEvents::default(),
+                    hitbox_type: HitboxType::Octane,
                 },
                 PlayerData {
                     car_id: 2,
@@ -235,6 +294,8 @@ impl GameState {
                     inverted_car_data: PhysicsObject::new(),
                     last_ball_touch_tick: 0,
                     last_actions: CarControls::default(),
+                    step_events: StepEvents::default(),
+                    hitbox_type: HitboxType::Octane,
                 },
             ],
             ball,
@@ -242,6 +303,13 @@ impl GameState {
             boost_pads: [BoostPad::default(); 34],
             inverted_boost_pads: [BoostPad::default(); 34],
             tick_num: 0,
+            mutators: ActiveMutators::default(),
+            is_kickoff: false,
+            kickoff_time_remaining: 0.,
+            is_kickoff_pause: false,
+            game_seconds_remaining: 0.,
+            is_overtime: false,
+            round_num: 0,
         }
     }
 
@@ -308,3 +376,51 @@ impl GameState {
     //     return player_data
     // }
 }
+
+/// FNV-1a, written out so that the hash stays the same across Rust versions unlike [std::hash::DefaultHasher]
+struct StateHasher(u64);
+
+impl StateHasher {
+    fn new() -> Self {
+        StateHasher(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_u64(&mut self, val: u64) {
+        self.write(&val.to_le_bytes());
+    }
+
+    fn write_i32(&mut self, val: i32) {
+        self.write(&val.to_le_bytes());
+    }
+
+    fn write_f32(&mut self, val: f32) {
+        self.write(&val.to_bits().to_le_bytes());
+    }
+
+    fn write_bool(&mut self, val: bool) {
+        self.write(&[val as u8]);
+    }
+
+    fn write_physics(&mut self, object: &PhysicsObject) {
+        let quat = &object.quaternion;
+        for val in [
+            object.position.x, object.position.y, object.position.z,
+            quat.w, quat.x, quat.y, quat.z,
+            object.linear_velocity.x, object.linear_velocity.y, object.linear_velocity.z,
+            object.angular_velocity.x, object.angular_velocity.y, object.angular_velocity.z,
+        ] {
+            self.write_f32(val);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
@@ -1,7 +1,31 @@
 use rocketsim_rs::sim::{BallHitInfo, CarControls};
 use serde::{Deserialize, Serialize};
 
-use crate::gamestates::physics_object::PhysicsObject;
+use crate::{envs::car_bodies::HitboxType, gamestates::physics_object::PhysicsObject};
+
+/// Events of a car that happened over all of the ticks of a step (or up to the decoded tick with
+/// [StepMode::FirstTick](crate::envs::game_match::StepMode::FirstTick)).
+///
+/// Tick values are arena tick counts and are 0 if there was no touch in the step.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct StepEvents {
+    /// amount of ticks on which the car hit the ball
+    pub touches: u32,
+    pub first_touch_tick: u64,
+    pub last_touch_tick: u64,
+    pub demos_dealt: u32,
+    pub demos_received: u32,
+    /// bumps (including demos) by the car on another car
+    pub bumps_dealt: u32,
+    /// bumps (including demos) of another car on this car
+    pub bumps_received: u32,
+    /// boost pads that were picked up by the car
+    pub pads_collected: u32,
+    /// the car was on the ground on one tick and off it on the next
+    pub left_ground: bool,
+    /// the car was on the ground on at least one tick
+    pub touched_ground: bool,
+}
 
 /// Struct which holds extra data for agents/players aside from just the PhysicsObjects
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
@@ -30,6 +54,10 @@ pub struct PlayerData {
     pub last_ball_touch_tick: u64,
     #[serde(skip)]
     pub last_actions: CarControls,
+    /// everything that happened to this car during the last step, not only on the decoded tick
+    pub step_events: StepEvents,
+    /// body of the car, see [GameConfig::car_bodies](crate::envs::game_match::GameConfig::car_bodies)
+    pub hitbox_type: HitboxType,
 }
 
 impl PlayerData {
@@ -58,6 +86,8 @@ impl PlayerData {
             inverted_car_data: PhysicsObject::new(),
             last_ball_touch_tick: 0,
             last_actions: CarControls::default(),
+            step_events: StepEvents::default(),
+            hitbox_type: HitboxType::Octane,
         }
     }
 }
@@ -1,44 +1,140 @@
+use crate::bots::opponent_pool::OpponentReport;
 use crate::gamestates::game_state::GameState;
 use crate::envs::game_match::{GameMatch, GameConfig};
 use crate::make::RenderConfig;
 use crate::obs_builders::obs_builder::ObsBuilder;
 use crate::render::renderer::Renderer;
+use crate::sim_wrapper::wrapper::{SimStep, Stats};
+use crate::snapshot::Snapshot;
+use crate::spaces::Space;
+use crate::error::{Error, Result};
+
+use ndarray::ArrayViewMut2;
+use rocketsim_rs::GameState as GameState_sim;
 
 // use subprocess::Popen;
 
 use std::collections::HashMap;
-use std::io;
 
 // use std::thread;
 // use std::time::Duration;
 
+/// Result of [Gym::step].
+///
+/// `terminated` and `truncated` follow Gymnasium's definitions, `terminal_reasons` are the names of the terminal
+/// conditions that ended the episode. `stats` are in the same order as `state.players`, as are the obs/rewards
+/// except that cars driven by bots are skipped.
+/// `result` is the goal difference (blue - orange) since the start of the episode.
+///
+/// With [Gym::auto_reset] the gym is reset when the episode ends, in which case `obs`, `state` and `tick_count` are
+/// from the new episode while the terminal obs and state are in `terminal_obs` and `terminal_state`.
+/// The rewards, stats and result are still those of the step that ended the episode.
+#[derive(Clone, Debug)]
+pub struct StepResult {
+    pub obs: Vec<Vec<f32>>,
+    pub rewards: Vec<f32>,
+    pub terminated: bool,
+    pub truncated: bool,
+    pub state: GameState,
+    pub stats: Vec<Stats>,
+    pub result: i32,
+    pub terminal_reasons: Vec<&'static str>,
+    pub tick_count: u64,
+    pub terminal_obs: Option<Vec<Vec<f32>>>,
+    pub terminal_state: Option<GameState>,
+    /// states after each tick of the step before `state`, empty unless the step mode is [StepMode::History](crate::envs::game_match::StepMode::History)
+    pub sub_states: Vec<GameState>,
+    /// true for the agents of `obs` that are in the match, false for the padding of variable team sizes
+    pub agent_mask: Vec<bool>,
+    /// opponent of the opponent pool in this episode, the outcome is set when the episode ends
+    pub opponent: Option<OpponentReport>,
+}
+
+impl StepResult {
+    /// true if the episode has ended for either reason
+    pub fn done(&self) -> bool {
+        self.terminated || self.truncated
+    }
+
+    /// Converts to the old `(obs, rewards, done, info)` form, which is what the PyO3 bindings use.
+    ///
+    /// The info holds "result", "terminated" and "truncated" (as 0./1.).
+    pub fn into_legacy(self) -> (Vec<Vec<f32>>, Vec<f32>, bool, HashMap<String, f32>) {
+        let done = self.done();
+        let mut info = HashMap::<String, f32>::new();
+        info.insert("result".to_string(), self.result as f32);
+        info.insert("terminated".to_string(), self.terminated as u8 as f32);
+        info.insert("truncated".to_string(), self.truncated as u8 as f32);
+        (self.obs, self.rewards, done, info)
+    }
+}
+
+/// Result of [Gym::step_into], the obs and rewards are written to the buffers that were passed in.
+///
+/// With [Gym::auto_reset] the obs buffer holds the obs of the new episode and the terminal obs are in `terminal_obs`
+/// (which is only allocated when an episode ends).
+#[derive(Clone, Debug, Default)]
+pub struct StepInfo {
+    pub terminated: bool,
+    pub truncated: bool,
+    pub result: i32,
+    pub terminal_reasons: Vec<&'static str>,
+    pub tick_count: u64,
+    pub terminal_obs: Option<Vec<Vec<f32>>>,
+    pub terminal_state: Option<GameState>,
+    /// amount of rows of the obs buffer that are agents in the match, the rest is padding
+    pub active_agents: usize,
+    /// see [StepResult::opponent]
+    pub opponent: Option<OpponentReport>,
+}
+
+impl StepInfo {
+    /// true if the episode has ended for either reason
+    pub fn done(&self) -> bool {
+        self.terminated || self.truncated
+    }
+}
+
+/// Result of [Gym::reset].
+#[derive(Clone, Debug)]
+pub struct ResetResult {
+    pub obs: Vec<Vec<f32>>,
+    pub state: GameState,
+    pub stats: Vec<Stats>,
+    pub tick_count: u64,
+    /// see [StepResult::agent_mask]
+    pub agent_mask: Vec<bool>,
+}
+
+impl ResetResult {
+    /// Converts to the old form of the reset return (only the obs)
+    pub fn into_legacy(self) -> Vec<Vec<f32>> {
+        self.obs
+    }
+}
+
 /// Base Gym struct for RLGym-Rust.
 /// 
 /// See 
 pub struct Gym {
     pub _game_match: GameMatch,
-    pub observation_space: Vec<usize>,
-    pub action_space: Vec<usize>,
+    /// space of the observation of one agent, see [GameMatch::detect_spaces]
+    pub observation_space: Space,
+    /// space of the action of one agent before it is parsed
+    pub action_space: Space,
     pub _prev_state: GameState,
+    /// If true, `step` resets the gym through the state setter when the episode ends and returns the obs of the new episode
+    pub auto_reset: bool,
     renderer: Option<Renderer>,
 }
 
 impl Gym {
     /// Creates a new instance of a gym and launches + connects to Rocket League instance
-    pub fn new(game_match: GameMatch, render_config: RenderConfig) -> Self {
+    pub fn new(game_match: GameMatch, render_config: RenderConfig) -> Result<Self> {
         let observation_space = game_match.observation_space.clone();
         let action_space = game_match.action_space.clone();
         let renderer = if render_config.render {
-            let render_op = Renderer::new(render_config);
-            match render_op {
-                Ok(val) => {
-                    Some(val)
-                },
-                Err(e) => {
-                    println!("Unable to start rendering due to error: {e}");
-                    None
-                }
-            }
+            Some(Renderer::new(render_config)?)
         } else {
             None
         };
@@ -48,155 +144,252 @@ impl Gym {
             observation_space,
             action_space,
             _prev_state: GameState::new(),
+            auto_reset: false,
             renderer,
         };
 
-        gym._prev_state = gym.receive_state();
-        gym.reset(None, None);
+        gym._prev_state = gym.receive_state()?;
+        gym.reset(None, None)?;
+        // builders can only know their obs space after they have seen a state
+        gym._game_match.detect_spaces();
+        gym.observation_space = gym._game_match.observation_space.clone();
+        gym.action_space = gym._game_match.action_space.clone();
 
-        gym
+        Ok(gym)
     }
 
-    pub fn reset(&mut self, _return_info: Option<bool>, seed: Option<u64>) -> Vec<Vec<f32>> {
-        // let _return_info = match _return_info {
-        //     Some(return_info) => return_info,
-        //     None => false
-        // };
+    pub fn reset(&mut self, _return_info: Option<bool>, seed: Option<u64>) -> Result<ResetResult> {
         if let Some(seed) = seed { self._game_match.set_seeds(seed) };
+        // a new team size also samples the bodies again
+        let rebuilt_state = match self._game_match.sample_team_size()? {
+            Some(state) => Some(state),
+            None => self._game_match.sample_car_bodies()?,
+        };
+        if let Some(state) = rebuilt_state {
+            self._prev_state = state;
+        }
+        self._game_match.sample_opponent()?;
 
-        let state_wrapper = self._game_match.get_reset_state(&self._prev_state);
+        let state_wrapper = self._game_match.get_reset_state(&self._prev_state)?;
 
         // set the sim state and get the state from the sim
-        let gym_state = if self.renderer.is_some() {
-            let (gym_state, sim_state) = self._game_match.sim_wrapper.set_state(state_wrapper, true);
-
-            let render_op = self.renderer.as_mut().unwrap().step(vec![sim_state.unwrap()]);
-            match render_op {
-                Ok(_) => (),
-                Err(e) => {
-                    println!("Unable to do rendering in reset due to error: {e}, attempting to close renderer");
-                    let close_op = self.renderer.as_mut().unwrap().close();
-                    match close_op {
-                        Ok(_) => (),
-                        Err(e) => {
-                            println!("Unable to close renderer in reset due to error: {e}");
-                        }
-                    }
-                }
-            }
+        let render = self.renderer.is_some();
+        let (mut gym_state, sim_state) = self._game_match.sim_wrapper.set_state(state_wrapper, render)?;
+        if let Some(sim_state) = sim_state {
+            self.render(vec![sim_state])?;
+        }
+        self._game_match.reset_match_clock(&mut gym_state);
 
-            gym_state
-        } else {
-            let (gym_state, _) = self._game_match.sim_wrapper.set_state(state_wrapper, false);
+        self._game_match.episode_reset(&gym_state);
 
-            gym_state
-        };
+        let mut obs = self._game_match.build_observations(&gym_state)?;
+        self._game_match.pad_observations(&mut obs)?;
+        self._prev_state = gym_state.clone();
 
-        self._game_match.episode_reset(&gym_state);
+        Ok(ResetResult {
+            obs,
+            stats: self._game_match.get_stats(),
+            tick_count: gym_state.tick_num,
+            state: gym_state,
+            agent_mask: self._game_match.agent_mask(),
+        })
+    }
 
-        let obs = self._game_match.build_observations(&gym_state);
-        self._prev_state = gym_state;
-        // TODO return Option except that state and get_result don't match
-        // if _return_info {
-        //     let mut h_m = HashMap::<&str,f64>::new();
-        //     h_m.insert("result", self._game_match.get_result(state) as f64);
-        // }
+    /// Steps the gym with the given actions, see [StepResult::into_legacy] for the old tuple form.
+    ///
+    /// If rendering fails then the renderer is closed and the error is returned, the sim will have still been stepped.
+    pub fn step(&mut self, actions: Vec<Vec<f32>>) -> Result<StepResult> {
+        let (gym_state, sub_states) = self.step_sim(actions)?;
+
+        let mut obs = self._game_match.build_observations(&gym_state)?;
+        self._game_match.pad_observations(&mut obs)?;
+        let terminal_signal = self._game_match.get_terminal_signal(&gym_state);
+        let mut rewards = self._game_match.get_rewards(&gym_state, terminal_signal.is_done());
+        self._game_match.pad_rewards(&mut rewards);
+        let result = self._game_match.get_result(&gym_state);
+        let stats = self._game_match.get_stats();
+        let opponent = self._game_match.opponent_report(terminal_signal.is_done().then_some(result));
+        self._prev_state = gym_state.clone();
+
+        let mut state = gym_state;
+        let mut terminal_obs = None;
+        let mut terminal_state = None;
+        if self.auto_reset && terminal_signal.is_done() {
+            let reset_result = self.reset(None, None)?;
+            terminal_obs = Some(std::mem::replace(&mut obs, reset_result.obs));
+            terminal_state = Some(std::mem::replace(&mut state, reset_result.state));
+        }
 
-        obs
+        Ok(StepResult {
+            obs,
+            rewards,
+            terminated: terminal_signal.terminated,
+            truncated: terminal_signal.truncated,
+            stats,
+            result,
+            terminal_reasons: terminal_signal.reasons,
+            tick_count: state.tick_num,
+            state,
+            terminal_obs,
+            terminal_state,
+            sub_states,
+            agent_mask: self._game_match.agent_mask(),
+            opponent,
+        })
     }
 
-    pub fn step(&mut self, actions: Vec<Vec<f32>>) -> (Vec<Vec<f32>>, Vec<f32>, bool, HashMap<String, f32>) {
-        let actions = self._game_match.parse_actions(actions, &self._prev_state);
+    /// Steps the gym like [step](Gym::step) but writes the observations and rewards into the given buffers
+    /// instead of allocating new ones every step, the state and stats are not returned (see [Gym::_prev_state]).
+    ///
+    /// `obs` is row major with one row per player (`[players, obs_len]`) and `rewards` has one value per player.
+    ///
+    /// The obs and rewards are only written without allocating for a plain match, this still allocates:
+    /// - the actions, which are `Vec<Vec<f32>>`, and the parsed actions of the action parser
+    /// - the obs with [GameMatch::team_sizes], bots or an opponent pool, these are built with
+    ///   [build_observations](GameMatch::build_observations) and then copied into `obs`
+    /// - the id of the opponent in [StepInfo::opponent] with an opponent pool
+    /// - the terminal obs and state and the reset when an episode ends
+    pub fn step_into(&mut self, actions: Vec<Vec<f32>>, obs: &mut [f32], rewards: &mut [f32]) -> Result<StepInfo> {
+        let (gym_state, _) = self.step_sim(actions)?;
 
-        // set the sim state and get the state from the sim
-        let gym_state = if self.renderer.is_some() {
-            let (mut gym_state, sim_state) = self._game_match.sim_wrapper.step(actions, true);
-            
-            let render_op = self.renderer.as_mut().unwrap().step(sim_state.unwrap());
-            match render_op {
-                Ok(val) => {
-                    // if this is true then we need to "reset" the gym to the new state
-                    if let Some(val) = val {
-                        // irregular reset process (no state setters from the gym involved basically)
-                        gym_state = self._game_match.sim_wrapper.set_state_sim(val);
-                        self._game_match.episode_reset(&gym_state);
-                    }
-                },
-                Err(e) => {
-                    println!("Unable to do rendering in reset due to error: {e}, attempting to close renderer");
-                    let close_op = self.renderer.as_mut().unwrap().close();
-                    match close_op {
-                        Ok(_) => {
-                            // self.renderer = Some(val);
-                            // return Ok(())
-                        },
-                        Err(e) => {
-                            println!("Unable to close renderer in reset due to error: {e}");
-                            // return Err(e)
-                        }
-                    }
-                }
+        self._game_match.build_observations_into(&gym_state, obs)?;
+        let terminal_signal = self._game_match.get_terminal_signal(&gym_state);
+        self._game_match.get_rewards_into(&gym_state, terminal_signal.is_done(), rewards)?;
+        let result = self._game_match.get_result(&gym_state);
+        let opponent = self._game_match.opponent_report(terminal_signal.is_done().then_some(result));
+        let mut tick_count = gym_state.tick_num;
+        self._prev_state = gym_state;
+
+        let mut terminal_obs = None;
+        let mut terminal_state = None;
+        if self.auto_reset && terminal_signal.is_done() {
+            // allocating here is fine since it only happens once per episode
+            let obs_len = obs.len() / self._game_match.max_agents().max(1);
+            terminal_obs = Some(obs.chunks(obs_len.max(1)).map(|player_obs| player_obs.to_vec()).collect());
+            terminal_state = Some(self._prev_state.clone());
+
+            let reset_result = self.reset(None, None)?;
+            tick_count = reset_result.tick_count;
+            let reset_obs = reset_result.obs.concat();
+            if reset_obs.len() != obs.len() {
+                return Err(Error::BufferLength { buffer: "obs", len: obs.len(), expected: reset_obs.len() });
             }
+            obs.copy_from_slice(&reset_obs);
+        }
 
-            gym_state
-        } else {
-            let (gym_state, _) = self._game_match.sim_wrapper.step(actions, false);
+        Ok(StepInfo {
+            terminated: terminal_signal.terminated,
+            truncated: terminal_signal.truncated,
+            result,
+            terminal_reasons: terminal_signal.reasons,
+            tick_count,
+            terminal_obs,
+            terminal_state,
+            active_agents: self._game_match.learners(),
+            opponent,
+        })
+    }
 
-            gym_state
+    /// Same as [step_into](Gym::step_into) with an ndarray obs buffer of shape `[players, obs_len]`
+    pub fn step_into_array(&mut self, actions: Vec<Vec<f32>>, mut obs: ArrayViewMut2<f32>, rewards: &mut [f32]) -> Result<StepInfo> {
+        let player_len = self._game_match.max_agents();
+        if obs.nrows() != player_len {
+            return Err(Error::BufferLength { buffer: "obs rows", len: obs.nrows(), expected: player_len });
+        }
+        let obs = match obs.as_slice_mut() {
+            Some(val) => val,
+            None => return Err(Error::BufferLayout("obs")),
         };
+        self.step_into(actions, obs, rewards)
+    }
 
-        let obs = self._game_match.build_observations(&gym_state);
-        let done = self._game_match.is_done(&gym_state);
-        let reward = self._game_match.get_rewards(&gym_state, done);
-        let mut info = HashMap::<String, f32>::new();
-        info.insert("result".to_string(), self._game_match.get_result(&gym_state) as f32);
-        self._prev_state = gym_state;
-        (obs, reward, done, info)
-    }
-
-    pub fn close_renderer(&mut self) {
-        if self.renderer.is_some() {
-            let close_op = self.renderer.as_mut().unwrap().close();
-            match close_op {
-                Ok(_) => {
-                    // self.renderer = Some(val);
-                    // return Ok(())
-                },
-                Err(e) => {
-                    println!("Unable to close renderer due to error: {e}");
-                    // return Err(e)
-                }
+    /// parses the actions and steps the sim (and renderer), returns the new state and the sub-step states
+    /// (which have already been given to the components)
+    fn step_sim(&mut self, mut actions: Vec<Vec<f32>>) -> Result<(GameState, Vec<GameState>)> {
+        // the actions of padded agents are ignored
+        if self._game_match.team_sizes.is_some() && actions.len() > self._game_match.learners() {
+            actions.truncate(self._game_match.learners());
+        }
+        // the step after a goal in a full match starts from a kickoff, the actions were picked for the goal so they are
+        // only parsed (the cars are frozen by the countdown anyway)
+        if let Some(kickoff_state) = self._game_match.match_kickoff()? {
+            self._prev_state = kickoff_state;
+        }
+        let actions = self._game_match.parse_actions(actions, &self._prev_state)?;
+
+        // set the sim state and get the state from the sim
+        let render = self.renderer.is_some();
+        let SimStep { state: mut gym_state, mut sub_states, sim_states } = self._game_match.sim_wrapper.step(actions, render)?;
+        self._game_match.update_match_clock(&mut gym_state, &mut sub_states);
+        self._game_match.sub_steps(&sub_states);
+        if let Some(sim_states) = sim_states {
+            // if this is Some then we need to "reset" the gym to the new state
+            if let Some(val) = self.render(sim_states)? {
+                // irregular reset process (no state setters from the gym involved basically)
+                gym_state = self._game_match.sim_wrapper.set_state_sim(val)?;
+                self._game_match.episode_reset(&gym_state);
             }
-            self.renderer = None;
-        } else {
-            println!("Close rendered was called but did nothing as there was no renderer")
+        }
+
+        Ok((gym_state, sub_states))
+    }
+
+    /// Takes a snapshot of the gym that can be restored later with [Gym::restore].
+    ///
+    /// Returns [Error::Snapshot] if one of the components does not implement `snapshot`.
+    pub fn snapshot(&self) -> Result<Snapshot> {
+        Snapshot::new(&self._game_match, &self._prev_state)
+    }
+
+    /// Restores the gym to the point at which the snapshot was taken, nothing is changed if an error is returned.
+    pub fn restore(&mut self, snapshot: &Snapshot) -> Result<()> {
+        snapshot.restore(&mut self._game_match)?;
+        self._prev_state = snapshot.state.clone();
+        Ok(())
+    }
+
+    /// Closes the renderer if there is one
+    pub fn close_renderer(&mut self) -> Result<()> {
+        match self.renderer.take() {
+            Some(mut renderer) => renderer.close(),
+            None => Ok(()),
         }
     }
 
-    pub fn try_render(&mut self, render_config: RenderConfig) -> Result<(), io::Error> {
-        let render_op = Renderer::new(render_config);
-        match render_op {
-            Ok(val) => {
-                self.renderer = Some(val);
-                Ok(())
-            },
+    pub fn try_render(&mut self, render_config: RenderConfig) -> Result<()> {
+        self.renderer = Some(Renderer::new(render_config)?);
+        Ok(())
+    }
+
+    pub fn update_config(&mut self, new_config: GameConfig, new_obs: Option<Vec<Box<dyn ObsBuilder>>>) -> Result<()> {
+        self._prev_state = self._game_match.update_settings(new_config, new_obs)?;
+        Ok(())
+    }
+
+    /// Sends the states to the renderer, if that fails then the renderer is closed and dropped
+    fn render(&mut self, states: Vec<GameState_sim>) -> Result<Option<GameState_sim>> {
+        let renderer = match self.renderer.as_mut() {
+            Some(val) => val,
+            None => return Ok(None),
+        };
+
+        match renderer.step(states) {
+            Ok(val) => Ok(val),
             Err(e) => {
-                println!("Unable to do rendering due to error: {e}");
+                // the renderer may already be unreachable so the close error does not matter here
+                let _ = renderer.close();
+                self.renderer = None;
                 Err(e)
             }
         }
     }
 
-    pub fn update_config(&mut self, new_config: GameConfig, new_obs: Option<Vec<Box<dyn ObsBuilder>>>) {
-        self._prev_state = self._game_match.update_settings(new_config, new_obs);
-    }
-
     // pub fn close(&mut self) {
     //     self._game_process.terminate().unwrap();
     //     self._comm_handler.close_pipe();
     // }
 
-    fn receive_state(&mut self) -> GameState {
+    fn receive_state(&mut self) -> Result<GameState> {
         // let message = self._comm_handler.receive_message(Some(RLGYM_STATE_MESSAGE_HEADER.to_vec()));
         // if message.body[0] == -999999. {
         //     self.close();
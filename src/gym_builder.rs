@@ -0,0 +1,300 @@
+//! Builder for a [Gym] that fills in defaults and checks the config before and after the gym is made.
+
+use crate::{
+    action_parsers::{action_parser::ActionParser, continous_act::ContinuousAction},
+    bots::{bot::Bot, opponent_pool::OpponentPool},
+    common_values::{BLUE_TEAM, NUM_ACTIONS, ORANGE_TEAM},
+    conditionals::{common_conditions::NeverTerminalCondition, terminal_condition::TerminalCondition},
+    envs::{game_match::GameConfig, match_mode::MatchConfig, team_sizes::TeamSizes},
+    error::{Error, Result},
+    gym::Gym,
+    make::{check_opponent_pool, make, MakeConfig, RenderConfig},
+    obs_builders::{advanced_obs::AdvancedObs, obs_builder::ObsBuilder},
+    reward_functions::{common_rewards::misc_rewards::ZeroReward, reward_fn::RewardFn},
+    spaces::Space,
+    state_setters::{default_state::DefaultState, state_setter::StateSetter},
+};
+
+/// Builds a [Gym] from only the components that are needed, the rest are defaults:
+/// - the [GameConfig] default (1v1 soccar with a tick skip of 8)
+/// - [ZeroReward] and [NeverTerminalCondition], so a gym for inference does not need a reward or terminal condition
+/// - one [AdvancedObs] for every agent, [ContinuousAction] and [DefaultState]
+///
+/// [build](GymBuilder::build) checks the config before the arena is made (team size, bot slots and the amount of
+/// observation builders) and then checks the components against the made gym: the action parser must give
+/// [NUM_ACTIONS] values for each agent and the observations must have the length of the observation space.
+/// The observation and action space of the gym are filled in from the observation builder and action parser.
+///
+/// # Example
+/// ```
+/// use rlgym_sim_rs::{GymBuilder, envs::game_match::GameConfig, TimeoutCondition};
+///
+/// rocketsim_rs::init(None, false);
+///
+/// let mut gym = GymBuilder::new()
+///     .with_game_config(GameConfig { team_size: 2, ..Default::default() })
+///     .with_terminal_condition(Box::new(TimeoutCondition::new(300)))
+///     .build()
+///     .unwrap();
+/// let obs = gym.reset(None, None).unwrap().obs;
+/// assert_eq!(obs[0].len(), gym.observation_space.flat_len());
+/// ```
+pub struct GymBuilder {
+    game_config: GameConfig,
+    terminal_condition: Option<Box<dyn TerminalCondition>>,
+    reward_fn: Option<Box<dyn RewardFn>>,
+    obs_builder: Vec<Box<dyn ObsBuilder>>,
+    use_single_obs: bool,
+    action_parser: Option<Box<dyn ActionParser>>,
+    state_setter: Option<Box<dyn StateSetter>>,
+    auto_reset: bool,
+    team_sizes: Option<TeamSizes>,
+    bots: Vec<(usize, Box<dyn Bot>)>,
+    opponent_pool: Option<OpponentPool>,
+    seed: Option<u64>,
+    match_config: Option<MatchConfig>,
+    render_config: Option<RenderConfig>,
+}
+
+impl Default for GymBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GymBuilder {
+    pub fn new() -> Self {
+        GymBuilder {
+            game_config: GameConfig::default(),
+            terminal_condition: None,
+            reward_fn: None,
+            obs_builder: Vec::new(),
+            use_single_obs: true,
+            action_parser: None,
+            state_setter: None,
+            auto_reset: false,
+            team_sizes: None,
+            bots: Vec::new(),
+            opponent_pool: None,
+            seed: None,
+            match_config: None,
+            render_config: None,
+        }
+    }
+
+    pub fn with_game_config(mut self, game_config: GameConfig) -> Self {
+        self.game_config = game_config;
+        self
+    }
+
+    pub fn with_terminal_condition(mut self, terminal_condition: Box<dyn TerminalCondition>) -> Self {
+        self.terminal_condition = Some(terminal_condition);
+        self
+    }
+
+    pub fn with_reward_fn(mut self, reward_fn: Box<dyn RewardFn>) -> Self {
+        self.reward_fn = Some(reward_fn);
+        self
+    }
+
+    /// One observation builder that is used for every agent
+    pub fn with_obs_builder(mut self, obs_builder: Box<dyn ObsBuilder>) -> Self {
+        self.obs_builder = vec![obs_builder];
+        self.use_single_obs = true;
+        self
+    }
+
+    /// One observation builder for each agent, in the order of the agents
+    pub fn with_obs_builders(mut self, obs_builders: Vec<Box<dyn ObsBuilder>>) -> Self {
+        self.obs_builder = obs_builders;
+        self.use_single_obs = false;
+        self
+    }
+
+    pub fn with_action_parser(mut self, action_parser: Box<dyn ActionParser>) -> Self {
+        self.action_parser = Some(action_parser);
+        self
+    }
+
+    pub fn with_state_setter(mut self, state_setter: Box<dyn StateSetter>) -> Self {
+        self.state_setter = Some(state_setter);
+        self
+    }
+
+    /// see [Gym::auto_reset]
+    pub fn with_auto_reset(mut self, auto_reset: bool) -> Self {
+        self.auto_reset = auto_reset;
+        self
+    }
+
+    /// see [MakeConfig::team_sizes]
+    pub fn with_team_sizes(mut self, team_sizes: TeamSizes) -> Self {
+        self.team_sizes = Some(team_sizes);
+        self
+    }
+
+    /// Adds a bot that drives the car at `slot` (the index of the car in `state.players`)
+    pub fn with_bot(mut self, slot: usize, bot: Box<dyn Bot>) -> Self {
+        self.bots.push((slot, bot));
+        self
+    }
+
+    pub fn with_opponent_pool(mut self, opponent_pool: OpponentPool) -> Self {
+        self.opponent_pool = Some(opponent_pool);
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// see [MakeConfig::match_config]
+    pub fn with_match_config(mut self, match_config: MatchConfig) -> Self {
+        self.match_config = Some(match_config);
+        self
+    }
+
+    pub fn with_render_config(mut self, render_config: RenderConfig) -> Self {
+        self.render_config = Some(render_config);
+        self
+    }
+
+    /// Checks the config and fills in the defaults without making the gym
+    pub fn build_config(self) -> Result<MakeConfig> {
+        let config = &self.game_config;
+        if config.team_size == 0 {
+            return Err(Error::Config("team_size must be at least 1".to_string()));
+        }
+        if config.tick_skip == 0 {
+            return Err(Error::Config("tick_skip must be at least 1".to_string()));
+        }
+
+        let cars = if config.spawn_opponents { config.team_size * 2 } else { config.team_size };
+        for (i, (slot, _)) in self.bots.iter().enumerate() {
+            if *slot >= cars {
+                return Err(Error::Config(format!("bot slot {slot} is out of range for {cars} cars")));
+            }
+            if self.bots[..i].iter().any(|(other, _)| other == slot) {
+                return Err(Error::Config(format!("more than one bot drives slot {slot}")));
+            }
+        }
+
+        check_opponent_pool(config, self.team_sizes.as_ref(), self.opponent_pool.is_some())?;
+
+        let mut obs_builder = self.obs_builder;
+        if obs_builder.is_empty() {
+            if self.use_single_obs {
+                obs_builder.push(Box::new(AdvancedObs::new()));
+            } else {
+                return Err(Error::ObsBuilderCount { builders: 0, players: 1 });
+            }
+        }
+        // builders for the other team sizes are made by the factory if there is one
+        let has_factory = self.team_sizes.as_ref().is_some_and(|team_sizes| team_sizes.obs_builder_factory.is_some());
+        if !self.use_single_obs && !has_factory {
+            let opponent_team = self.opponent_pool.as_ref().map(|pool| pool.team());
+            let mut sizes = vec![(config.team_size, config.spawn_opponents)];
+            if let Some(team_sizes) = &self.team_sizes {
+                sizes.extend(team_sizes.options().iter().map(|option| (option.team_size, option.spawn_opponents)));
+            }
+            let learners = sizes
+                .into_iter()
+                .map(|(team_size, spawn_opponents)| learner_count(team_size, spawn_opponents, &self.bots, opponent_team))
+                .max()
+                .unwrap_or(0);
+            if obs_builder.len() < learners {
+                return Err(Error::ObsBuilderCount { builders: obs_builder.len(), players: learners });
+            }
+        }
+
+        Ok(MakeConfig {
+            game_config: self.game_config,
+            terminal_condition: self.terminal_condition.unwrap_or_else(|| Box::new(NeverTerminalCondition::new())),
+            reward_fn: self.reward_fn.unwrap_or_else(|| Box::new(ZeroReward::new())),
+            obs_builder,
+            use_single_obs: self.use_single_obs,
+            action_parser: self.action_parser.unwrap_or_else(|| Box::new(ContinuousAction::new())),
+            state_setter: self.state_setter.unwrap_or_else(|| Box::new(DefaultState::new(None))),
+            auto_reset: self.auto_reset,
+            team_sizes: self.team_sizes,
+            bots: self.bots,
+            opponent_pool: self.opponent_pool,
+            seed: self.seed,
+            match_config: self.match_config,
+        })
+    }
+
+    /// Makes the gym and checks the action parser and observation builders against it
+    pub fn build(self) -> Result<Gym> {
+        let render_config = self.render_config;
+        let config = self.build_config()?;
+        let mut gym = make(config, render_config)?;
+        check_action_parser(&mut gym)?;
+        check_obs_space(&gym)?;
+        Ok(gym)
+    }
+}
+
+/// amount of cars that are not driven by a bot or the opponent pool
+fn learner_count(team_size: usize, spawn_opponents: bool, bots: &[(usize, Box<dyn Bot>)], opponent_team: Option<i32>) -> usize {
+    let cars = if spawn_opponents { team_size * 2 } else { team_size };
+    (0..cars)
+        .filter(|slot| {
+            let team = if spawn_opponents && *slot >= team_size { ORANGE_TEAM } else { BLUE_TEAM };
+            !bots.iter().any(|(bot_slot, _)| bot_slot == slot) && opponent_team != Some(team)
+        })
+        .count()
+}
+
+fn check_action_parser(gym: &mut Gym) -> Result<()> {
+    let learners = gym._game_match.learners();
+    let actions = vec![gym.action_space.zeros(); learners];
+    let parsed_actions = gym._game_match._action_parser.parse_actions(actions, &gym._prev_state);
+    if parsed_actions.len() != learners {
+        return Err(Error::ActionCount { actions: parsed_actions.len(), players: learners });
+    }
+    match parsed_actions.iter().find(|action| action.len() != NUM_ACTIONS) {
+        Some(action) => Err(Error::ActionLength { len: action.len() }),
+        None => Ok(()),
+    }
+}
+
+fn check_obs_space(gym: &Gym) -> Result<()> {
+    let game_match = &gym._game_match;
+    let builders = if game_match.use_single_obs { &game_match._obs_builder[..1] } else { &game_match._obs_builder[..] };
+    // the spaces are read from copies so that the builders are not changed by the check
+    let mut copies = Vec::new();
+    for builder in builders {
+        match builder.snapshot() {
+            Some(copy) => copies.push(copy),
+            // builders that do not support copies are not checked
+            None => return Ok(()),
+        }
+    }
+
+    let spaces: Vec<Space> = copies.iter_mut().map(|builder| builder.get_obs_space()).collect();
+    if let Some(space) = spaces.iter().find(|space| **space != spaces[0]) {
+        return Err(Error::Config(format!("observation builders have different obs spaces, {:?} and {:?}", spaces[0], space)));
+    }
+    let expected = gym.observation_space.flat_len();
+    if expected == 0 {
+        return Ok(());
+    }
+
+    let padded = game_match.team_sizes.as_ref().is_some_and(|team_sizes| team_sizes.pad_obs_len.is_some());
+    let state = &gym._prev_state;
+    let learner_mask = game_match.learner_mask(state.players.len());
+    let learners = state.players.iter().zip(learner_mask).filter(|(_, is_learner)| *is_learner).map(|(player, _)| player);
+    for (i, player) in learners.enumerate() {
+        let builder = if game_match.use_single_obs { &mut copies[0] } else { &mut copies[i] };
+        let len = builder.build_obs(player, state, &game_match.game_config).len();
+        if len > expected || (!padded && len != expected) {
+            return Err(Error::Config(format!(
+                "the observation space is {:?} but an observation of length {len} was built",
+                gym.observation_space
+            )));
+        }
+    }
+    Ok(())
+}
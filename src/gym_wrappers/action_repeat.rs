@@ -0,0 +1,54 @@
+use crate::{
+    error::Result,
+    gym::{Gym, ResetResult, StepResult},
+};
+
+use super::GymWrapper;
+
+/// Repeats the same actions for `repeat` steps and sums the rewards, stops early if the episode ends.
+///
+/// This is on top of the tick skip of the gym so each step of this wrapper is `repeat * tick_skip` ticks.
+pub struct ActionRepeat<E: GymWrapper> {
+    env: E,
+    repeat: usize,
+}
+
+impl<E: GymWrapper> ActionRepeat<E> {
+    pub fn new(env: E, repeat: usize) -> Self {
+        assert!(repeat > 0, "ActionRepeat requires repeat to be at least 1");
+        ActionRepeat { env, repeat }
+    }
+
+    pub fn into_inner(self) -> E {
+        self.env
+    }
+}
+
+impl<E: GymWrapper> GymWrapper for ActionRepeat<E> {
+    fn reset(&mut self, seed: Option<u64>) -> Result<ResetResult> {
+        self.env.reset(seed)
+    }
+
+    fn step(&mut self, actions: Vec<Vec<f32>>) -> Result<StepResult> {
+        let mut step_result = self.env.step(actions.clone())?;
+        for _ in 1..self.repeat {
+            if step_result.done() {
+                break;
+            }
+            let rewards = step_result.rewards;
+            step_result = self.env.step(actions.clone())?;
+            for (reward, prev_reward) in step_result.rewards.iter_mut().zip(rewards) {
+                *reward += prev_reward;
+            }
+        }
+        Ok(step_result)
+    }
+
+    fn gym(&self) -> &Gym {
+        self.env.gym()
+    }
+
+    fn gym_mut(&mut self) -> &mut Gym {
+        self.env.gym_mut()
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::VecDeque;
+
+use crate::{
+    error::Result,
+    gym::{Gym, ResetResult, StepResult},
+};
+
+use super::GymWrapper;
+
+/// Stacks the last `num_frames` observations of each agent, oldest first, into a single observation.
+///
+/// On reset the stack is filled with the initial observation. With [Gym::auto_reset] the terminal obs are stacked
+/// with the frames of the episode that ended while the obs start a new stack.
+pub struct FrameStack<E: GymWrapper> {
+    env: E,
+    num_frames: usize,
+    frames: Vec<VecDeque<Vec<f32>>>,
+}
+
+impl<E: GymWrapper> FrameStack<E> {
+    pub fn new(env: E, num_frames: usize) -> Self {
+        assert!(num_frames > 0, "FrameStack requires num_frames to be at least 1");
+        FrameStack { env, num_frames, frames: Vec::new() }
+    }
+
+    pub fn into_inner(self) -> E {
+        self.env
+    }
+
+    fn fill(&mut self, obs: &[Vec<f32>]) {
+        self.frames = obs
+            .iter()
+            .map(|agent_obs| std::iter::repeat_n(agent_obs.clone(), self.num_frames).collect())
+            .collect();
+    }
+
+    fn push(&mut self, obs: &[Vec<f32>]) {
+        if self.frames.len() != obs.len() {
+            // the amount of agents changed without a reset
+            self.fill(obs);
+            return;
+        }
+        for (agent_frames, agent_obs) in self.frames.iter_mut().zip(obs) {
+            agent_frames.pop_front();
+            agent_frames.push_back(agent_obs.clone());
+        }
+    }
+
+    fn stacked(&self) -> Vec<Vec<f32>> {
+        self.frames.iter().map(|agent_frames| agent_frames.iter().flatten().copied().collect()).collect()
+    }
+}
+
+impl<E: GymWrapper> GymWrapper for FrameStack<E> {
+    fn reset(&mut self, seed: Option<u64>) -> Result<ResetResult> {
+        let mut reset_result = self.env.reset(seed)?;
+        self.fill(&reset_result.obs);
+        reset_result.obs = self.stacked();
+        Ok(reset_result)
+    }
+
+    fn step(&mut self, actions: Vec<Vec<f32>>) -> Result<StepResult> {
+        let mut step_result = self.env.step(actions)?;
+        match step_result.terminal_obs.take() {
+            Some(terminal_obs) => {
+                self.push(&terminal_obs);
+                step_result.terminal_obs = Some(self.stacked());
+                self.fill(&step_result.obs);
+            }
+            None => self.push(&step_result.obs),
+        }
+        step_result.obs = self.stacked();
+        Ok(step_result)
+    }
+
+    fn gym(&self) -> &Gym {
+        self.env.gym()
+    }
+
+    fn gym_mut(&mut self) -> &mut Gym {
+        self.env.gym_mut()
+    }
+}
@@ -0,0 +1,55 @@
+//! Wrappers that change the behavior of a [Gym] by intercepting `reset` and `step`.
+//!
+//! Every wrapper implements [GymWrapper] and wraps anything else that implements it (including [Gym] itself)
+//! so wrappers can be stacked, the outermost wrapper is applied last.
+//!
+//! ```no_run
+//! # fn example(gym: rlgym_sim_rs::Gym) -> rlgym_sim_rs::Result<()> {
+//! use rlgym_sim_rs::gym_wrappers::{
+//!     frame_stack::FrameStack, obs_norm::ObsNormalize, reward_scale::RewardScale, GymWrapper,
+//! };
+//!
+//! let mut env = RewardScale::new(FrameStack::new(ObsNormalize::new(gym, None), 4), 0.1);
+//! let reset_result = env.reset(None)?;
+//! let step_result = env.step(vec![vec![0.; 8]; reset_result.obs.len()])?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod action_repeat;
+pub mod frame_stack;
+pub mod monitor;
+pub mod obs_norm;
+pub mod reward_scale;
+
+use crate::{
+    error::Result,
+    gym::{Gym, ResetResult, StepResult},
+};
+
+/// Common interface of [Gym] and the wrappers around it
+pub trait GymWrapper: Send {
+    fn reset(&mut self, seed: Option<u64>) -> Result<ResetResult>;
+    fn step(&mut self, actions: Vec<Vec<f32>>) -> Result<StepResult>;
+    /// The innermost gym
+    fn gym(&self) -> &Gym;
+    fn gym_mut(&mut self) -> &mut Gym;
+}
+
+impl GymWrapper for Gym {
+    fn reset(&mut self, seed: Option<u64>) -> Result<ResetResult> {
+        Gym::reset(self, None, seed)
+    }
+
+    fn step(&mut self, actions: Vec<Vec<f32>>) -> Result<StepResult> {
+        Gym::step(self, actions)
+    }
+
+    fn gym(&self) -> &Gym {
+        self
+    }
+
+    fn gym_mut(&mut self) -> &mut Gym {
+        self
+    }
+}
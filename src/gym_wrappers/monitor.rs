@@ -0,0 +1,328 @@
+use std::{
+    collections::VecDeque,
+    fs::{File, OpenOptions},
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use serde::Serialize;
+
+use crate::{
+    common_values::BLUE_TEAM,
+    error::{Error, Result},
+    gamestates::game_state::GameState,
+    gym::{Gym, ResetResult, StepInfo, StepResult},
+};
+
+use super::GymWrapper;
+
+/// File format of the episode records written by [Monitor]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MonitorFormat {
+    /// One row per episode, per-agent values are joined with `;` in a single column
+    Csv,
+    /// One JSON object per line
+    Jsonl,
+}
+
+/// Summary of a finished episode, values that are per agent are in the same order as the obs and rewards of the gym.
+///
+/// Only the learning agents are included, not the cars of bots or of the opponent pool and not the padding of
+/// [TeamSizes](crate::envs::team_sizes::TeamSizes).
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct EpisodeRecord {
+    /// index of the episode since the monitor was created
+    pub episode: u64,
+    /// sum of the rewards of each agent
+    pub returns: Vec<f32>,
+    /// amount of steps in the episode
+    pub length: u64,
+    /// amount of physics ticks in the episode
+    pub ticks: u64,
+    /// goals scored by the team of each agent
+    pub goals_for: Vec<i32>,
+    /// goals scored against the team of each agent
+    pub goals_against: Vec<i32>,
+    /// goal difference (blue - orange) of the episode, same as [StepResult::result]
+    pub result: i32,
+    pub terminated: bool,
+    pub truncated: bool,
+}
+
+impl EpisodeRecord {
+    const CSV_HEADER: &'static str = "episode,returns,length,ticks,goals_for,goals_against,result,terminated,truncated";
+
+    fn to_csv_row(&self) -> String {
+        fn join<T: ToString>(vals: &[T]) -> String {
+            vals.iter().map(|val| val.to_string()).collect::<Vec<_>>().join(";")
+        }
+
+        format!(
+            "{},{},{},{},{},{},{},{},{}",
+            self.episode,
+            join(&self.returns),
+            self.length,
+            self.ticks,
+            join(&self.goals_for),
+            join(&self.goals_against),
+            self.result,
+            self.terminated,
+            self.truncated,
+        )
+    }
+}
+
+/// Means over the episodes that are in the rolling window of a [Monitor]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RollingMeans {
+    /// amount of episodes that the means are over
+    pub episodes: usize,
+    /// mean episode return over all agents
+    pub episode_return: f32,
+    pub length: f32,
+    pub ticks: f32,
+    /// mean goals for per agent
+    pub goals_for: f32,
+    /// mean goals against per agent
+    pub goals_against: f32,
+    pub result: f32,
+}
+
+/// Episode in progress
+#[derive(Clone, Debug, Default)]
+struct EpisodeTracker {
+    returns: Vec<f32>,
+    // team of each learning agent
+    teams: Vec<i32>,
+    length: u64,
+    start_tick: u64,
+    start_blue_score: i32,
+    start_orange_score: i32,
+}
+
+impl EpisodeTracker {
+    /// `learner_mask` is true for the players of `initial_state` that are learning agents
+    fn new(initial_state: &GameState, learner_mask: &[bool]) -> Self {
+        let teams: Vec<i32> = initial_state
+            .players
+            .iter()
+            .zip(learner_mask)
+            .filter(|(_, is_learner)| **is_learner)
+            .map(|(player, _)| player.team_num)
+            .collect();
+        EpisodeTracker {
+            returns: vec![0.; teams.len()],
+            teams,
+            length: 0,
+            start_tick: initial_state.tick_num,
+            start_blue_score: initial_state.blue_score,
+            start_orange_score: initial_state.orange_score,
+        }
+    }
+
+    /// `rewards` may be padded, the padding comes after the agents so it is left out by the zip
+    fn add_step(&mut self, rewards: &[f32]) {
+        for (ret, reward) in self.returns.iter_mut().zip(rewards) {
+            *ret += reward;
+        }
+        self.length += 1;
+    }
+
+    fn finish(&mut self, episode: u64, final_state: &GameState, result: i32, terminated: bool, truncated: bool) -> EpisodeRecord {
+        let blue_goals = final_state.blue_score - self.start_blue_score;
+        let orange_goals = final_state.orange_score - self.start_orange_score;
+        let (goals_for, goals_against) = self
+            .teams
+            .iter()
+            .map(|team| if *team == BLUE_TEAM { (blue_goals, orange_goals) } else { (orange_goals, blue_goals) })
+            .unzip();
+
+        EpisodeRecord {
+            episode,
+            returns: std::mem::take(&mut self.returns),
+            length: self.length,
+            ticks: final_state.tick_num.saturating_sub(self.start_tick),
+            goals_for,
+            goals_against,
+            result,
+            terminated,
+            truncated,
+        }
+    }
+}
+
+/// Tracks episode statistics of a [Gym] (or another wrapper), writes a record of every finished episode to a file and keeps rolling means.
+///
+/// Works with and without [Gym::auto_reset], without it the next episode is tracked from the terminal state
+/// until [Monitor::reset] is called.
+///
+/// # Example
+///
+/// ```no_run
+/// # fn example(gym: rlgym_sim_rs::Gym) -> rlgym_sim_rs::Result<()> {
+/// use rlgym_sim_rs::gym_wrappers::{monitor::{Monitor, MonitorFormat}, GymWrapper};
+///
+/// let mut monitor = Monitor::new(gym, Some("episodes.csv"), MonitorFormat::Csv, 100)?;
+/// monitor.reset(None)?;
+/// let step_result = monitor.step(vec![vec![0.; 8]; 2])?;
+/// println!("{:?}", monitor.rolling_means());
+/// # Ok(())
+/// # }
+/// ```
+pub struct Monitor<E: GymWrapper = Gym> {
+    env: E,
+    writer: Option<BufWriter<File>>,
+    format: MonitorFormat,
+    window: usize,
+    records: VecDeque<EpisodeRecord>,
+    current: EpisodeTracker,
+    total_episodes: u64,
+}
+
+impl<E: GymWrapper> Monitor<E> {
+    /// Wraps the env, `path` is the file that the records are appended to (nothing is written if it is `None`)
+    /// and `window` is the amount of episodes that the rolling means are over.
+    pub fn new<P: AsRef<Path>>(env: E, path: Option<P>, format: MonitorFormat, window: usize) -> Result<Self> {
+        let writer = match path {
+            Some(path) => {
+                let file = OpenOptions::new().create(true).append(true).open(path).map_err(Error::Monitor)?;
+                let is_empty = file.metadata().map_err(Error::Monitor)?.len() == 0;
+                let mut writer = BufWriter::new(file);
+                if is_empty && format == MonitorFormat::Csv {
+                    writeln!(writer, "{}", EpisodeRecord::CSV_HEADER).map_err(Error::Monitor)?;
+                    writer.flush().map_err(Error::Monitor)?;
+                }
+                Some(writer)
+            }
+            None => None,
+        };
+        let current = Self::tracker(env.gym(), &env.gym()._prev_state);
+
+        Ok(Monitor {
+            env,
+            writer,
+            format,
+            window: window.max(1),
+            records: VecDeque::with_capacity(window.max(1)),
+            current,
+            total_episodes: 0,
+        })
+    }
+
+    fn tracker(gym: &Gym, initial_state: &GameState) -> EpisodeTracker {
+        EpisodeTracker::new(initial_state, &gym._game_match.learner_mask(initial_state.players.len()))
+    }
+
+    fn finish_episode(&mut self, final_state: &GameState, next_state: &GameState, result: i32, terminated: bool, truncated: bool) -> Result<()> {
+        let record = self.current.finish(self.total_episodes, final_state, result, terminated, truncated);
+        self.current = Self::tracker(self.env.gym(), next_state);
+        self.total_episodes += 1;
+
+        if let Some(writer) = self.writer.as_mut() {
+            Self::write_record(writer, self.format, &record).map_err(Error::Monitor)?;
+        }
+
+        if self.records.len() == self.window {
+            self.records.pop_front();
+        }
+        self.records.push_back(record);
+        Ok(())
+    }
+
+    fn write_record(writer: &mut BufWriter<File>, format: MonitorFormat, record: &EpisodeRecord) -> io::Result<()> {
+        match format {
+            MonitorFormat::Csv => writeln!(writer, "{}", record.to_csv_row())?,
+            MonitorFormat::Jsonl => {
+                serde_json::to_writer(&mut *writer, record).map_err(io::Error::other)?;
+                writeln!(writer)?;
+            }
+        }
+        // flush every episode so that the file is usable while training
+        writer.flush()
+    }
+
+    /// Means over the last `window` episodes
+    pub fn rolling_means(&self) -> RollingMeans {
+        let episodes = self.records.len();
+        if episodes == 0 {
+            return RollingMeans::default();
+        }
+
+        let mut means = RollingMeans { episodes, ..Default::default() };
+        let mut agent_episodes = 0;
+        for record in &self.records {
+            means.episode_return += record.returns.iter().sum::<f32>();
+            means.goals_for += record.goals_for.iter().sum::<i32>() as f32;
+            means.goals_against += record.goals_against.iter().sum::<i32>() as f32;
+            agent_episodes += record.returns.len();
+            means.length += record.length as f32;
+            means.ticks += record.ticks as f32;
+            means.result += record.result as f32;
+        }
+
+        let agent_episodes = agent_episodes.max(1) as f32;
+        means.episode_return /= agent_episodes;
+        means.goals_for /= agent_episodes;
+        means.goals_against /= agent_episodes;
+        means.length /= episodes as f32;
+        means.ticks /= episodes as f32;
+        means.result /= episodes as f32;
+        means
+    }
+
+    /// Records of the episodes in the rolling window, oldest first
+    pub fn episodes(&self) -> impl Iterator<Item = &EpisodeRecord> {
+        self.records.iter()
+    }
+
+    /// Amount of episodes that have finished since the monitor was created
+    pub fn total_episodes(&self) -> u64 {
+        self.total_episodes
+    }
+
+    /// Returns the wrapped env, the file is flushed on every episode so nothing is lost
+    pub fn into_inner(self) -> E {
+        self.env
+    }
+}
+
+impl Monitor<Gym> {
+    /// Steps the gym, see [Gym::step_into]
+    pub fn step_into(&mut self, actions: Vec<Vec<f32>>, obs: &mut [f32], rewards: &mut [f32]) -> Result<StepInfo> {
+        let info = self.env.step_into(actions, obs, rewards)?;
+        self.current.add_step(rewards);
+        if info.done() {
+            let next_state = self.env._prev_state.clone();
+            let final_state = info.terminal_state.as_ref().unwrap_or(&next_state);
+            self.finish_episode(final_state, &next_state, info.result, info.terminated, info.truncated)?;
+        }
+        Ok(info)
+    }
+}
+
+impl<E: GymWrapper> GymWrapper for Monitor<E> {
+    /// Resets the env and starts a new episode, an unfinished episode is discarded
+    fn reset(&mut self, seed: Option<u64>) -> Result<ResetResult> {
+        let reset_result = self.env.reset(seed)?;
+        self.current = Self::tracker(self.env.gym(), &reset_result.state);
+        Ok(reset_result)
+    }
+
+    fn step(&mut self, actions: Vec<Vec<f32>>) -> Result<StepResult> {
+        let step_result = self.env.step(actions)?;
+        self.current.add_step(&step_result.rewards);
+        if step_result.done() {
+            let final_state = step_result.terminal_state.as_ref().unwrap_or(&step_result.state);
+            self.finish_episode(final_state, &step_result.state, step_result.result, step_result.terminated, step_result.truncated)?;
+        }
+        Ok(step_result)
+    }
+
+    fn gym(&self) -> &Gym {
+        self.env.gym()
+    }
+
+    fn gym_mut(&mut self) -> &mut Gym {
+        self.env.gym_mut()
+    }
+}
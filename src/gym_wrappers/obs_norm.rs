@@ -0,0 +1,141 @@
+use crate::{
+    error::Result,
+    gym::{Gym, ResetResult, StepResult},
+};
+
+use super::GymWrapper;
+
+/// Normalizes observations with a running mean and variance of every element of the observation.
+///
+/// The statistics are shared by all agents and are updated from every obs that goes through the wrapper
+/// until [ObsNormalize::set_update] turns that off (eg. for evaluation).
+/// Normalized values are clipped to `[-clip, clip]`. The padded rows of [TeamSizes](crate::envs::team_sizes::TeamSizes)
+/// are left at zero and are not counted in the statistics.
+pub struct ObsNormalize<E: GymWrapper> {
+    env: E,
+    clip: f32,
+    update: bool,
+    count: f64,
+    mean: Vec<f64>,
+    var: Vec<f64>,
+}
+
+impl<E: GymWrapper> ObsNormalize<E> {
+    const EPSILON: f64 = 1e-8;
+
+    /// `clip` defaults to 10
+    pub fn new(env: E, clip: Option<f32>) -> Self {
+        ObsNormalize {
+            env,
+            clip: clip.unwrap_or(10.),
+            update: true,
+            count: 0.,
+            mean: Vec::new(),
+            var: Vec::new(),
+        }
+    }
+
+    /// Turns updating of the running statistics on or off
+    pub fn set_update(&mut self, update: bool) {
+        self.update = update;
+    }
+
+    /// Running mean of every element of the observation
+    pub fn mean(&self) -> &[f64] {
+        &self.mean
+    }
+
+    /// Running variance of every element of the observation
+    pub fn var(&self) -> &[f64] {
+        &self.var
+    }
+
+    pub fn into_inner(self) -> E {
+        self.env
+    }
+
+    fn update_stats(&mut self, obs: &[Vec<f32>]) {
+        let obs_len = match obs.first() {
+            Some(val) => val.len(),
+            None => return,
+        };
+        if self.mean.len() != obs_len {
+            // the obs size changed (eg. a different team size) so the old statistics are no longer valid
+            self.count = 0.;
+            self.mean = vec![0.; obs_len];
+            self.var = vec![1.; obs_len];
+        }
+
+        // combines the running statistics with the statistics of this batch (parallel variance algorithm)
+        let batch_count = obs.len() as f64;
+        let total_count = self.count + batch_count;
+        for i in 0..obs_len {
+            let batch_mean = obs.iter().map(|agent_obs| agent_obs[i] as f64).sum::<f64>() / batch_count;
+            let batch_var = obs.iter().map(|agent_obs| (agent_obs[i] as f64 - batch_mean).powi(2)).sum::<f64>() / batch_count;
+
+            let delta = batch_mean - self.mean[i];
+            let m2 = self.var[i] * self.count + batch_var * batch_count + delta.powi(2) * self.count * batch_count / total_count;
+            self.mean[i] += delta * batch_count / total_count;
+            self.var[i] = m2 / total_count;
+        }
+        self.count = total_count;
+    }
+
+    /// amount of rows of `agent_mask` that are agents, the padding always comes after them
+    fn active_rows(agent_mask: &[bool]) -> usize {
+        agent_mask.iter().filter(|active| **active).count()
+    }
+
+    /// amount of agents in the current episode, this has to be taken before stepping since the agent mask of a step
+    /// that ends the episode (and the team size after an auto-reset) already belong to the next episode
+    fn episode_rows(&self) -> usize {
+        let gym = self.env.gym();
+        Self::active_rows(&gym._game_match.learner_mask(gym._prev_state.players.len()))
+    }
+
+    fn normalize(&self, obs: &mut [Vec<f32>]) {
+        for agent_obs in obs.iter_mut() {
+            for ((val, mean), var) in agent_obs.iter_mut().zip(&self.mean).zip(&self.var) {
+                let normalized = (*val as f64 - mean) / (var + Self::EPSILON).sqrt();
+                *val = (normalized as f32).clamp(-self.clip, self.clip);
+            }
+        }
+    }
+}
+
+impl<E: GymWrapper> GymWrapper for ObsNormalize<E> {
+    fn reset(&mut self, seed: Option<u64>) -> Result<ResetResult> {
+        let mut reset_result = self.env.reset(seed)?;
+        let rows = Self::active_rows(&reset_result.agent_mask);
+        if self.update {
+            self.update_stats(&reset_result.obs[..rows]);
+        }
+        self.normalize(&mut reset_result.obs[..rows]);
+        Ok(reset_result)
+    }
+
+    fn step(&mut self, actions: Vec<Vec<f32>>) -> Result<StepResult> {
+        let terminal_rows = self.episode_rows();
+        let mut step_result = self.env.step(actions)?;
+        let rows = Self::active_rows(&step_result.agent_mask);
+        if self.update {
+            if let Some(terminal_obs) = &step_result.terminal_obs {
+                self.update_stats(&terminal_obs[..terminal_rows]);
+            }
+            self.update_stats(&step_result.obs[..rows]);
+        }
+        self.normalize(&mut step_result.obs[..rows]);
+        if let Some(terminal_obs) = step_result.terminal_obs.as_mut() {
+            self.normalize(&mut terminal_obs[..terminal_rows]);
+        }
+        Ok(step_result)
+    }
+
+    fn gym(&self) -> &Gym {
+        self.env.gym()
+    }
+
+    fn gym_mut(&mut self) -> &mut Gym {
+        self.env.gym_mut()
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{
+    error::Result,
+    gym::{Gym, ResetResult, StepResult},
+};
+
+use super::GymWrapper;
+
+/// Multiplies every reward by `scale`
+pub struct RewardScale<E: GymWrapper> {
+    env: E,
+    scale: f32,
+}
+
+impl<E: GymWrapper> RewardScale<E> {
+    pub fn new(env: E, scale: f32) -> Self {
+        RewardScale { env, scale }
+    }
+
+    pub fn into_inner(self) -> E {
+        self.env
+    }
+}
+
+impl<E: GymWrapper> GymWrapper for RewardScale<E> {
+    fn reset(&mut self, seed: Option<u64>) -> Result<ResetResult> {
+        self.env.reset(seed)
+    }
+
+    fn step(&mut self, actions: Vec<Vec<f32>>) -> Result<StepResult> {
+        let mut step_result = self.env.step(actions)?;
+        for reward in step_result.rewards.iter_mut() {
+            *reward *= self.scale;
+        }
+        Ok(step_result)
+    }
+
+    fn gym(&self) -> &Gym {
+        self.env.gym()
+    }
+
+    fn gym_mut(&mut self) -> &mut Gym {
+        self.env.gym_mut()
+    }
+}
+
+/// Clips every reward to be within `min` and `max`
+pub struct RewardClip<E: GymWrapper> {
+    env: E,
+    min: f32,
+    max: f32,
+}
+
+impl<E: GymWrapper> RewardClip<E> {
+    pub fn new(env: E, min: f32, max: f32) -> Self {
+        assert!(min <= max, "RewardClip min ({min}) must not be greater than max ({max})");
+        RewardClip { env, min, max }
+    }
+
+    pub fn into_inner(self) -> E {
+        self.env
+    }
+}
+
+impl<E: GymWrapper> GymWrapper for RewardClip<E> {
+    fn reset(&mut self, seed: Option<u64>) -> Result<ResetResult> {
+        self.env.reset(seed)
+    }
+
+    fn step(&mut self, actions: Vec<Vec<f32>>) -> Result<StepResult> {
+        let mut step_result = self.env.step(actions)?;
+        for reward in step_result.rewards.iter_mut() {
+            *reward = reward.clamp(self.min, self.max);
+        }
+        Ok(step_result)
+    }
+
+    fn gym(&self) -> &Gym {
+        self.env.gym()
+    }
+
+    fn gym_mut(&mut self) -> &mut Gym {
+        self.env.gym_mut()
+    }
+}
@@ -5,12 +5,29 @@
 //! The first difference is that rlgym-sim-rs allows one observation builder per agent. 
 //! This means that if you have setup a 3v3 match or switch to a 3v3 match at any point, there must be 6 observation builders provided.
 //! You can either do this via the [`make()`] function initially or you can use the `.update_config()` of [Gym].
+//! To train across several team sizes, [TeamSizes](envs::team_sizes::TeamSizes) in the [MakeConfig] samples a size on every reset
+//! and creates the builders from a factory, the obs and rewards are then padded to the largest size.
 //! 
 //! The second difference is that terminal conditions are evaluated from one function instead of allowing for multiple to be used.
 //! You can use [CombinedTerminalConditions] in order to use multiple terminal conditions at once.
 //! 
+//! ## Threading
+//! All of the component traits ([ObsBuilder], [RewardFn], [TerminalCondition], [StateSetter], [ActionParser]) require `Send`
+//! which makes [Gym] `Send` as well, so a gym can be built on one thread and then moved to another.
+//! A gym is not `Sync` and should only be used from one thread at a time. Every gym owns its own arena, scores and stats.
+//! [VecGym] can be used to run many gyms across a pool of threads.
+//! 
+//! ## Reproducibility
+//! [MakeConfig::seed] (or the seed given to [Gym::reset]) is split into one seed per component by [seeding],
+//! so a gym with the same seed and actions steps through the same states. [GameState::state_hash](gamestates::game_state::GameState::state_hash)
+//! can be used to compare them.
+//! 
+//! ## Config files
+//! [make_from_config] makes a gym from a TOML, JSON or RON file so that experiments do not need a recompile,
+//! the components are built by name with a [Registry](registry::Registry) that custom components can be added to.
+//! 
 //! ## Example of usage
-//! See [`make()`] as a place to start.
+//! See [`make()`] as a place to start, or [GymBuilder] which fills in defaults and checks the config.
 //! The file `lib.rs` in examples also has an example scenario for Python bindings with PyO3 if necessary.
 //! Also, see `basic_example.rs` in examples.
 //! The tests are generally also a good place to look at.
@@ -24,25 +41,40 @@
 //! [RLViser]: https://github.com/VirxEC/rlviser/
 
 pub mod action_parsers;
+pub mod bots;
 pub mod common_values;
 pub mod conditionals;
 pub mod envs;
+pub mod error;
 pub mod gamestates;
 pub mod gym;
+pub mod gym_builder;
+pub mod gym_wrappers;
 pub mod make;
 pub mod math;
 pub mod obs_builders;
+pub mod registry;
 pub mod reward_functions;
+pub mod seeding;
 pub mod sim_wrapper;
+pub mod snapshot;
+pub mod spaces;
 pub mod state_setters;
 pub mod state_generator;
 pub mod render;
+pub mod vec_gym;
 
-pub use gym::Gym;
+pub use error::{Error, Result};
+pub use gym::{Gym, ResetResult, StepInfo, StepResult};
+pub use gym_builder::GymBuilder;
+pub use snapshot::Snapshot;
+pub use spaces::{Dtype, Space};
+pub use vec_gym::{VecGym, VecStepResult};
 pub use make::{
     MakeConfig,
     RenderConfig,
     make,
+    make_from_config,
 };
 pub use action_parsers::{
     action_parser::ActionParser, 
@@ -61,9 +93,9 @@ pub use obs_builders::{
     obs_builder::ObsBuilder,
 };
 pub use conditionals::{
-    common_conditions::{GoalScoredCondition, TimeoutCondition, NoTouchTimeoutCondition}, 
+    common_conditions::{GoalScoredCondition, TimeoutCondition, NoTouchTimeoutCondition, NeverTerminalCondition}, 
     extra_conditions::{NoTouchKickoffTimeoutCondition, CombinedTerminalConditions}, 
-    terminal_condition::TerminalCondition,
+    terminal_condition::{TerminalCondition, TerminalSignal},
 };
 pub use math::{
     clip, 
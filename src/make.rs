@@ -1,9 +1,15 @@
+use std::path::Path;
+
 use crate::{
     action_parsers::action_parser::ActionParser, 
     conditionals::terminal_condition::TerminalCondition, 
-    envs::game_match::{GameMatch, GameConfig}, 
+    bots::{bot::Bot, opponent_pool::OpponentPool},
+    envs::{game_match::{GameMatch, GameConfig}, match_mode::MatchConfig, team_sizes::TeamSizes},
+    error::{Error, Result},
     gym::Gym,
+    gym_builder::GymBuilder,
     obs_builders::obs_builder::ObsBuilder, 
+    registry::{MakeConfigFile, Registry},
     reward_functions::reward_fn::RewardFn, 
     state_setters::state_setter::StateSetter,
 };
@@ -17,7 +23,9 @@ use crate::{
 ///     obs_builders::advanced_obs::AdvancedObs,
 ///     action_parsers::test_parser::TestAction,
 ///     conditionals::common_conditions::GoalScoredCondition,
-///     envs::game_match::GameConfig,
+///     envs::car_bodies::CarBodies,
+///     envs::game_match::{GameConfig, GameMode, StepMode},
+///     envs::mutators::Mutators,
 ///     reward_functions::common_rewards::misc_rewards::EventReward,
 ///     state_setters::default_state::DefaultState,
 ///     make,
@@ -35,6 +43,11 @@ use crate::{
 ///     gravity: 1.,
 ///     boost_consumption: 1.,
 ///     car_config: CarConfig::octane(),
+///     car_bodies: CarBodies::Same,
+///     step_mode: StepMode::Final,
+///     game_mode: GameMode::Soccar,
+///     mutators: Mutators::default(),
+///     kickoff_phase: None,
 /// };
 /// 
 /// let game_config = make::MakeConfig {
@@ -45,9 +58,15 @@ use crate::{
 ///     use_single_obs: true,
 ///     action_parser: Box::new(TestAction::new()),
 ///     state_setter: Box::new(DefaultState::new(None)), 
+///     auto_reset: false,
+///     team_sizes: None,
+///     bots: Vec::new(),
+///     opponent_pool: None,
+///     seed: None,
+///     match_config: None,
 /// };
 /// 
-/// let mut gym = make::make(game_config, None);
+/// let mut gym = make::make(game_config, None).unwrap();
 /// ```
 pub struct MakeConfig {
     pub game_config: GameConfig,
@@ -57,6 +76,27 @@ pub struct MakeConfig {
     pub use_single_obs: bool,
     pub action_parser: Box<dyn ActionParser>,
     pub state_setter: Box<dyn StateSetter>, 
+    /// Resets the gym in `step` when the episode ends, see [Gym::auto_reset]
+    pub auto_reset: bool,
+    /// Samples the team size (and `spawn_opponents`) on every reset instead of using the one of the game config
+    pub team_sizes: Option<TeamSizes>,
+    /// Cars that are driven by scripted bots, as the index of the car in `state.players` and the bot.
+    /// The gym only takes actions (and gives obs and rewards) for the other cars.
+    pub bots: Vec<(usize, Box<dyn Bot>)>,
+    /// Self-play opponents that drive one of the teams, which is then not given actions (or obs and rewards) either
+    /// (requires `spawn_opponents`, for every option of `team_sizes` as well)
+    pub opponent_pool: Option<OpponentPool>,
+    /// Seeds every random component of the gym when it is made, see [GameMatch::set_seeds]
+    pub seed: Option<u64>,
+    /// Plays full matches with a game clock, kickoffs after goals and overtime, see [MatchClock](crate::envs::match_mode::MatchClock)
+    pub match_config: Option<MatchConfig>,
+}
+
+impl Default for MakeConfig {
+    /// The defaults of [GymBuilder], so that only the changed fields have to be given with `..Default::default()`
+    fn default() -> Self {
+        GymBuilder::new().build_config().expect("the default config of the gym builder is valid")
+    }
 }
 
 /// Render configuration struct for the `make` function. 
@@ -82,6 +122,8 @@ impl Default for RenderConfig {
 /// General generator function for the gym.
 /// 
 /// Use this in order to create a gym instance.
+/// This errors if the renderer could not be started, if an opponent pool is used without `spawn_opponents`
+/// or if the initial reset fails (eg. from a misconfigured state setter).
 /// 
 /// # Example
 /// 
@@ -90,7 +132,9 @@ impl Default for RenderConfig {
 ///     obs_builders::advanced_obs::AdvancedObs,
 ///     action_parsers::test_parser::TestAction,
 ///     conditionals::common_conditions::GoalScoredCondition,
-///     envs::game_match::GameConfig,
+///     envs::car_bodies::CarBodies,
+///     envs::game_match::{GameConfig, GameMode, StepMode},
+///     envs::mutators::Mutators,
 ///     reward_functions::common_rewards::misc_rewards::EventReward,
 ///     state_setters::default_state::DefaultState,
 ///     make,
@@ -107,6 +151,11 @@ impl Default for RenderConfig {
 ///     gravity: 1.,
 ///     boost_consumption: 1.,
 ///     car_config: CarConfig::octane(),
+///     car_bodies: CarBodies::Same,
+///     step_mode: StepMode::Final,
+///     game_mode: GameMode::Soccar,
+///     mutators: Mutators::default(),
+///     kickoff_phase: None,
 /// };
 /// 
 /// let game_config = make::MakeConfig {
@@ -117,11 +166,17 @@ impl Default for RenderConfig {
 ///     use_single_obs: true,
 ///     action_parser: Box::new(TestAction::new()),
 ///     state_setter: Box::new(DefaultState::new(None)), 
+///     auto_reset: false,
+///     team_sizes: None,
+///     bots: Vec::new(),
+///     opponent_pool: None,
+///     seed: None,
+///     match_config: None,
 /// };
 /// 
-/// let mut gym = make::make(game_config, None);
+/// let mut gym = make::make(game_config, None).unwrap();
 /// ```
-pub fn make(mut config: MakeConfig, render_config: Option<RenderConfig>) -> Gym {
+pub fn make(mut config: MakeConfig, render_config: Option<RenderConfig>) -> Result<Gym> {
     let tick_skip = config.game_config.tick_skip;
     config.game_config.tick_skip = if tick_skip == 0 {
         println!("tick_skip was set to 0, regular RLGym has the same behavior as 1 here");
@@ -133,9 +188,40 @@ pub fn make(mut config: MakeConfig, render_config: Option<RenderConfig>) -> Gym
         tick_skip
     };
 
+    check_opponent_pool(&config.game_config, config.team_sizes.as_ref(), config.opponent_pool.is_some())?;
+
+    let auto_reset = config.auto_reset;
     let game_match = GameMatch::new(
         config,
     );
 
-    Gym::new(game_match, render_config.unwrap_or_default())
+    let mut gym = Gym::new(game_match, render_config.unwrap_or_default())?;
+    gym.auto_reset = auto_reset;
+    Ok(gym)
+}
+
+/// An opponent pool needs an orange team, without `spawn_opponents` every car is blue so the pool would either drive
+/// all of the cars or none of them
+pub(crate) fn check_opponent_pool(game_config: &GameConfig, team_sizes: Option<&TeamSizes>, has_pool: bool) -> Result<()> {
+    if !has_pool {
+        return Ok(());
+    }
+    let sampled_without_opponents = team_sizes.is_some_and(|team_sizes| team_sizes.options().iter().any(|option| !option.spawn_opponents));
+    if !game_config.spawn_opponents || sampled_without_opponents {
+        return Err(Error::Config("an opponent pool can not be used with spawn_opponents set to false".to_string()));
+    }
+    Ok(())
+}
+
+/// Makes a gym from a TOML, JSON or RON config file (picked by the extension of `path`), see [registry](crate::registry) for the format.
+///
+/// The components are built with `registry`, or with the components of the crate if it is `None`.
+/// To change the config before the gym is made (eg. to add bots) use [MakeConfigFile::load] and [MakeConfigFile::build] instead.
+pub fn make_from_config(path: impl AsRef<Path>, registry: Option<&Registry>, render_config: Option<RenderConfig>) -> Result<Gym> {
+    let config_file = MakeConfigFile::load(path)?;
+    let config = match registry {
+        Some(registry) => config_file.build(registry)?,
+        None => config_file.build(&Registry::new())?,
+    };
+    make(config, render_config)
 }
@@ -7,12 +7,19 @@ use crate::gamestates::game_state::GameState;
 use crate::gamestates::physics_object::PhysicsObject;
 use crate::gamestates::player_data::PlayerData;
 
-use super::obs_builder::ObsBuilder;
+use crate::envs::game_match::{GameConfig, GameMode};
+use crate::error::{Error, Result};
+use crate::spaces::Space;
+
+use super::obs_builder::{ObsBuilder, ObsWriter};
 
 /// Matrix's observation builder, holds a stack of previous ball positions and shows the stack in the observation
+#[derive(Clone)]
 pub struct AdvancedObs {
     pos_std: f32,
     ang_std: f32,
+    // length of the obs of the last reset, this is the obs space
+    obs_len: usize,
 }
 
 impl Default for AdvancedObs {
@@ -29,10 +36,21 @@ impl AdvancedObs {
         //     None => false
         // };
 
-        AdvancedObs { pos_std: 2300., ang_std: PI }
+        AdvancedObs { pos_std: 2300., ang_std: PI, obs_len: Self::obs_len(2) }
+    }
+
+    /// length of the observation with `player_count` players in a soccar game
+    pub fn obs_len(player_count: usize) -> usize {
+        Self::obs_len_for_mode(GameMode::Soccar, player_count)
     }
 
-    fn _add_player_to_obs(&self, obs: &mut Vec<f32>, car: &PlayerData, ball: &PhysicsObject, inverted: bool, player: Option<&PhysicsObject>) -> PhysicsObject {
+    /// length of the observation with `player_count` players, the pads of the arena of `game_mode` are in the observation
+    pub fn obs_len_for_mode(game_mode: GameMode, player_count: usize) -> usize {
+        // ball + previous actions + pads + this player, then each other player also has its position and velocity relative to this player
+        9 + 8 + game_mode.num_pads() + 25 + 31 * player_count.saturating_sub(1)
+    }
+
+    fn _add_player_to_obs<E: Extend<f32>>(&self, obs: &mut E, car: &PlayerData, ball: &PhysicsObject, inverted: bool, player: Option<&PhysicsObject>) -> PhysicsObject {
         let player_car: PhysicsObject = if inverted {
             car.inverted_car_data
         } else {
@@ -51,7 +69,7 @@ impl AdvancedObs {
         obs.extend(player_car.up());
         obs.extend(player_car.linear_velocity.divide_by_var(self.pos_std));
         obs.extend(player_car.angular_velocity.divide_by_var(self.ang_std));
-        obs.extend(vec![car.boost_amount, car.on_ground as i32 as f32, car.has_flip as i32 as f32, car.is_demoed as i32 as f32]);
+        obs.extend([car.boost_amount, car.on_ground as i32 as f32, car.has_flip as i32 as f32, car.is_demoed as i32 as f32]);
 
         if let Some(player) = player {
             obs.extend((player_car.position - player.position).divide_by_var(self.pos_std));
@@ -60,16 +78,8 @@ impl AdvancedObs {
 
         player_car
     }
-}
-
-impl ObsBuilder for AdvancedObs {
-    fn reset(&mut self, _initial_state: &GameState) {}
-
-    fn get_obs_space(&mut self) -> Vec<usize> {
-        vec![276]
-    }
 
-    fn build_obs(&mut self, player: &PlayerData, state: &GameState, _config: &crate::envs::game_match::GameConfig) -> Vec<f32> {
+    fn _build_obs<E: Extend<f32>>(&self, obs: &mut E, player: &PlayerData, state: &GameState, game_mode: GameMode) {
         let inverted: bool;
         let ball: &PhysicsObject;
         let pads;
@@ -94,29 +104,55 @@ impl ObsBuilder for AdvancedObs {
         let lin_std = lin.divide_by_var(self.pos_std);
         let ang_std = ang.divide_by_var(self.ang_std);
 
-        let mut obs = Vec::<f32>::with_capacity(276);
-
         obs.extend(pos_std);
         obs.extend(lin_std);
         obs.extend(ang_std);
         obs.extend(player.last_actions.into_array());
-        obs.extend(pads.iter().map(|pad| pad.state.is_active as i32 as f32));
+        obs.extend(pads.iter().take(game_mode.num_pads()).map(|pad| pad.state.is_active as i32 as f32));
 
         // self.add_ball_to_stack(pos_std, lin_std, ang_std, player.car_id as usize);
 
         // let ball_stack = self.ball_stack[player.car_id as usize].make_contiguous().as_ref();
         // for ball_vec in self.ball_stack[player.car_id as usize].make_contiguous().as_ref() {
 
-        let player_car = self._add_player_to_obs(&mut obs, player, ball, inverted, None);
+        let player_car = self._add_player_to_obs(obs, player, ball, inverted, None);
 
         for other in &state.players {
             if other.car_id == player.car_id {
                 continue;
             }
 
-            self._add_player_to_obs(&mut obs, other, ball, inverted, Some(&player_car));
+            self._add_player_to_obs(obs, other, ball, inverted, Some(&player_car));
         }
+    }
+}
+
+impl ObsBuilder for AdvancedObs {
+    fn reset(&mut self, initial_state: &GameState) {
+        self.obs_len = Self::obs_len_for_mode(initial_state.game_mode(), initial_state.players.len());
+    }
+
+    /// The obs length of the game of the last reset (a 1v1 soccar game before the first reset)
+    fn get_obs_space(&mut self) -> Space {
+        Space::box_f32(f32::NEG_INFINITY, f32::INFINITY, self.obs_len)
+    }
 
+    fn build_obs(&mut self, player: &PlayerData, state: &GameState, config: &GameConfig) -> Vec<f32> {
+        let mut obs = Vec::<f32>::with_capacity(Self::obs_len_for_mode(config.game_mode, state.players.len()));
+        self._build_obs(&mut obs, player, state, config.game_mode);
         obs
     }
+
+    fn build_obs_into(&mut self, player: &PlayerData, state: &GameState, config: &GameConfig, obs: &mut [f32]) -> Result<()> {
+        let obs_len = Self::obs_len_for_mode(config.game_mode, state.players.len());
+        if obs.len() != obs_len {
+            return Err(Error::BufferLength { buffer: "obs", len: obs.len(), expected: obs_len });
+        }
+        self._build_obs(&mut ObsWriter::new(obs), player, state, config.game_mode);
+        Ok(())
+    }
+
+    fn snapshot(&self) -> Option<Box<dyn ObsBuilder>> {
+        Some(Box::new(self.clone()))
+    }
 }
@@ -1,11 +1,84 @@
 use crate::{
     envs::game_match::GameConfig,
+    error::{Error, Result},
     gamestates::{game_state::GameState, player_data::PlayerData},
+    spaces::Space,
 };
 
-pub trait ObsBuilder {
+pub trait ObsBuilder: Send {
     fn reset(&mut self, initial_state: &GameState);
-    fn get_obs_space(&mut self) -> Vec<usize>;
+    /// Space of the observation of one player
+    fn get_obs_space(&mut self) -> Space;
     fn pre_step(&mut self, _state: &GameState, _config: &GameConfig) {}
+    /// States after each tick of the tick skip except the last, oldest first. Only called with
+    /// [StepMode::History](crate::envs::game_match::StepMode::History), useful for builders that stack sub-step frames.
+    fn sub_steps(&mut self, _states: &[GameState], _config: &GameConfig) {}
     fn build_obs(&mut self, player: &PlayerData, state: &GameState, config: &GameConfig) -> Vec<f32>;
+    /// Seeds builders that add noise etc., every builder of the gym gets a different seed
+    fn set_seed(&mut self, _seed: u64) {}
+    /// Copy of this observation builder for [Gym::snapshot](crate::Gym::snapshot), builders that keep history (eg. stacks)
+    /// must copy it as well. `None` (the default) means snapshots are not supported.
+    fn snapshot(&self) -> Option<Box<dyn ObsBuilder>> {
+        None
+    }
+
+    /// Writes the observation of `player` into `obs`, which must be exactly the length of the observation.
+    ///
+    /// The default copies the output of [build_obs](ObsBuilder::build_obs) so it still allocates,
+    /// override this to write into the buffer directly.
+    fn build_obs_into(&mut self, player: &PlayerData, state: &GameState, config: &GameConfig, obs: &mut [f32]) -> Result<()> {
+        let built = self.build_obs(player, state, config);
+        if built.len() != obs.len() {
+            return Err(Error::BufferLength { buffer: "obs", len: obs.len(), expected: built.len() });
+        }
+        obs.copy_from_slice(&built);
+        Ok(())
+    }
+
+    /// Writes the observations of every player in `state` into `obs`.
+    ///
+    /// `obs` is split into equal rows, one per player in the order of `state.players`,
+    /// so it is laid out the same as a row major `[players, obs_len]` array.
+    fn build_all_obs_into(&mut self, state: &GameState, config: &GameConfig, obs: &mut [f32]) -> Result<()> {
+        let player_len = state.players.len();
+        if player_len == 0 {
+            return Ok(());
+        }
+        if !obs.len().is_multiple_of(player_len) {
+            return Err(Error::BufferLength { buffer: "obs", len: obs.len(), expected: obs.len() / player_len * player_len });
+        }
+        let obs_len = obs.len() / player_len;
+        for (i, player) in state.players.iter().enumerate() {
+            self.build_obs_into(player, state, config, &mut obs[i * obs_len..(i + 1) * obs_len])?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes into a slice through [Extend] so that the same code can build an observation into a `Vec` or a caller-provided buffer.
+///
+/// Panics if more values are written than the slice can hold, so check the length of the slice before writing.
+pub struct ObsWriter<'a> {
+    buf: &'a mut [f32],
+    pos: usize,
+}
+
+impl<'a> ObsWriter<'a> {
+    pub fn new(buf: &'a mut [f32]) -> Self {
+        ObsWriter { buf, pos: 0 }
+    }
+
+    /// amount of values written so far
+    pub fn written(&self) -> usize {
+        self.pos
+    }
+}
+
+impl Extend<f32> for ObsWriter<'_> {
+    fn extend<T: IntoIterator<Item = f32>>(&mut self, iter: T) {
+        for val in iter {
+            self.buf[self.pos] = val;
+            self.pos += 1;
+        }
+    }
 }
@@ -0,0 +1,475 @@
+//! Building a [MakeConfig] from a TOML, JSON or RON file, see [make_from_config](crate::make::make_from_config).
+//!
+//! Components are given by name and parameters and are built by a [Registry], which knows all of the components
+//! of the crate and can be given more with its `register_*` methods. The parameters of the built-in components
+//! are the arguments of their `new` functions, eg. in TOML:
+//!
+//! ```toml
+//! use_single_obs = true
+//!
+//! [game_config]
+//! team_size = 2
+//! tick_skip = 8
+//!
+//! [reward_fn]
+//! name = "CombinedReward"
+//! params.reward_weights = [1.0, 0.1]
+//! params.reward_structs = [
+//!     { name = "EventReward", params = { team_goal = 1.0, concede = -1.0 } },
+//!     { name = "VelocityPlayerToBallReward" },
+//! ]
+//!
+//! [terminal_condition]
+//! name = "CombinedTerminalConditions"
+//! params.conditionals = [{ name = "GoalScoredCondition" }, { name = "TimeoutCondition", params = { max_steps = 300 } }]
+//!
+//! [obs_builder]
+//! name = "AdvancedObs"
+//!
+//! [action_parser]
+//! name = "ContinuousAction"
+//!
+//! [state_setter]
+//! name = "DefaultState"
+//! ```
+//!
+//! Team sizes, bots and the opponent pool are not part of the file, they can be set on the [MakeConfig] that
+//! [MakeConfigFile::build] returns.
+
+use std::{cell::RefCell, collections::{HashMap, HashSet}, fs, path::Path};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{
+    action_parsers::{action_parser::ActionParser, continous_act::ContinuousAction, discrete_act::DiscreteAction, test_parser::TestAction},
+    conditionals::{
+        common_conditions::{GoalScoredCondition, NeverTerminalCondition, NoTouchTimeoutCondition, TimeoutCondition},
+        extra_conditions::{CombinedTerminalConditions, NoTouchKickoffTimeoutCondition},
+        terminal_condition::TerminalCondition,
+    },
+    envs::{
+        car_bodies::{CarBodies, HitboxType},
+        game_match::{GameConfig, GameMode, StepMode},
+        kickoff::KickoffPhase,
+        match_mode::MatchConfig,
+        mutators::Mutators,
+    },
+    error::{Error, Result},
+    make::MakeConfig,
+    obs_builders::{advanced_obs::AdvancedObs, obs_builder::ObsBuilder},
+    reward_functions::{
+        combined_reward::CombinedReward,
+        common_rewards::{
+            ball_goal_rewards::VelocityBallToGoalReward,
+            misc_rewards::{EventReward, SaveBoostReward, VelocityReward, ZeroReward},
+            player_ball_rewards::VelocityPlayerToBallReward,
+        },
+        reward_fn::RewardFn,
+    },
+    state_setters::{
+        default_state::DefaultState, random_state::RandomState, replay_setter::ReplaySetter, state_setter::StateSetter,
+        weighted_state_setter::WeightedSampleSetter,
+    },
+};
+
+/// Builds a component from its parameters, the registry is given so that components can build the components they hold
+pub type ComponentFactory<T> = Box<dyn Fn(&Params, &Registry) -> Result<Box<T>> + Send + Sync>;
+
+/// A component in a config file, its name in the [Registry] and the parameters it is built with
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ComponentSpec {
+    pub name: String,
+    #[serde(default)]
+    pub params: Map<String, Value>,
+}
+
+impl ComponentSpec {
+    pub fn new(name: &str, params: Option<Map<String, Value>>) -> Self {
+        ComponentSpec { name: name.to_string(), params: params.unwrap_or_default() }
+    }
+}
+
+/// Parameters of a component, building a component fails if it does not read all of them (eg. on a typo)
+pub struct Params {
+    params: Map<String, Value>,
+    used: RefCell<HashSet<String>>,
+}
+
+impl Params {
+    pub fn new(params: Map<String, Value>) -> Self {
+        Params { params, used: RefCell::new(HashSet::new()) }
+    }
+
+    /// Value of the parameter `key`, `None` if it is not set (or is null)
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
+        self.used.borrow_mut().insert(key.to_string());
+        match self.params.get(key) {
+            None | Some(Value::Null) => Ok(None),
+            Some(value) => T::deserialize(value)
+                .map(Some)
+                .map_err(|err| Error::Config(format!("invalid value for parameter `{key}`: {err}"))),
+        }
+    }
+
+    /// Value of the parameter `key`, which must be set
+    pub fn require<T: DeserializeOwned>(&self, key: &str) -> Result<T> {
+        self.get(key)?.ok_or_else(|| Error::Config(format!("missing parameter `{key}`")))
+    }
+
+    fn unused(&self) -> Vec<&str> {
+        let used = self.used.borrow();
+        let mut unused: Vec<&str> = self.params.keys().filter(|key| !used.contains(*key)).map(|key| key.as_str()).collect();
+        unused.sort_unstable();
+        unused
+    }
+}
+
+/// Constructors of the components by name, [Registry::new] has all of the components of the crate
+pub struct Registry {
+    reward_fns: HashMap<String, ComponentFactory<dyn RewardFn>>,
+    obs_builders: HashMap<String, ComponentFactory<dyn ObsBuilder>>,
+    action_parsers: HashMap<String, ComponentFactory<dyn ActionParser>>,
+    terminal_conditions: HashMap<String, ComponentFactory<dyn TerminalCondition>>,
+    state_setters: HashMap<String, ComponentFactory<dyn StateSetter>>,
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+
+        registry.register_reward_fn("EventReward", |params, _| {
+            Ok(Box::new(EventReward::new(
+                params.get("goal")?,
+                params.get("team_goal")?,
+                params.get("concede")?,
+                params.get("touch")?,
+                params.get("shot")?,
+                params.get("save")?,
+                params.get("demo")?,
+                params.get("boost_pickup")?,
+            )))
+        });
+        registry.register_reward_fn("VelocityReward", |params, _| Ok(Box::new(VelocityReward::new(params.get("negative")?))));
+        registry.register_reward_fn("SaveBoostReward", |_, _| Ok(Box::new(SaveBoostReward::new())));
+        registry.register_reward_fn("ZeroReward", |_, _| Ok(Box::new(ZeroReward::new())));
+        registry.register_reward_fn("VelocityPlayerToBallReward", |params, _| {
+            Ok(Box::new(VelocityPlayerToBallReward::new(params.get("use_scalar_projection")?)))
+        });
+        registry.register_reward_fn("VelocityBallToGoalReward", |params, _| {
+            Ok(Box::new(VelocityBallToGoalReward::new(params.get("own_goal")?, params.get("use_scalar_projection")?)))
+        });
+        registry.register_reward_fn("CombinedReward", |params, registry| {
+            let specs: Vec<ComponentSpec> = params.require("reward_structs")?;
+            let weights: Vec<f32> = params.require("reward_weights")?;
+            check_lengths(specs.len(), weights.len(), "reward_weights")?;
+            let reward_fns = specs.iter().map(|spec| registry.build_reward_fn(spec)).collect::<Result<Vec<_>>>()?;
+            Ok(Box::new(CombinedReward::new(reward_fns, weights)))
+        });
+
+        registry.register_obs_builder("AdvancedObs", |_, _| Ok(Box::new(AdvancedObs::new())));
+
+        registry.register_action_parser("ContinuousAction", |_, _| Ok(Box::new(ContinuousAction::new())));
+        registry.register_action_parser("DiscreteAction", |_, _| Ok(Box::new(DiscreteAction::new())));
+        registry.register_action_parser("TestAction", |_, _| Ok(Box::new(TestAction::new())));
+
+        registry.register_terminal_condition("TimeoutCondition", |params, _| {
+            Ok(Box::new(TimeoutCondition::new(params.require("max_steps")?)))
+        });
+        registry.register_terminal_condition("NoTouchTimeoutCondition", |params, _| {
+            Ok(Box::new(NoTouchTimeoutCondition::new(params.require("max_steps")?)))
+        });
+        registry.register_terminal_condition("NoTouchKickoffTimeoutCondition", |params, _| {
+            Ok(Box::new(NoTouchKickoffTimeoutCondition::new(params.require("max_steps")?)))
+        });
+        registry.register_terminal_condition("GoalScoredCondition", |_, _| Ok(Box::new(GoalScoredCondition::new())));
+        registry.register_terminal_condition("NeverTerminalCondition", |_, _| Ok(Box::new(NeverTerminalCondition::new())));
+        registry.register_terminal_condition("CombinedTerminalConditions", |params, registry| {
+            let specs: Vec<ComponentSpec> = params.require("conditionals")?;
+            let conditionals = specs.iter().map(|spec| registry.build_terminal_condition(spec)).collect::<Result<Vec<_>>>()?;
+            Ok(Box::new(CombinedTerminalConditions::new(conditionals)))
+        });
+
+        registry.register_state_setter("DefaultState", |params, _| Ok(Box::new(DefaultState::new(params.get("seed")?))));
+        registry.register_state_setter("RandomState", |params, _| {
+            Ok(Box::new(RandomState::new(
+                params.get("ball_rand_speed")?,
+                params.get("cars_rand_speed")?,
+                params.get("cars_on_ground")?,
+                params.get("seed")?,
+            )))
+        });
+        registry.register_state_setter("ReplaySetter", |params, _| {
+            let file_str: String = params.require("file_str")?;
+            // the setter panics on a missing file
+            if !Path::new(&file_str).is_file() {
+                return Err(Error::Config(format!("replay file {file_str} does not exist")));
+            }
+            Ok(Box::new(ReplaySetter::new(&file_str, params.get("random_boost")?, params.get("random_pads")?)))
+        });
+        registry.register_state_setter("WeightedSampleSetter", |params, registry| {
+            let specs: Vec<ComponentSpec> = params.require("state_setters")?;
+            let weights: Vec<f64> = params.require("weights")?;
+            check_lengths(specs.len(), weights.len(), "weights")?;
+            if weights.iter().any(|weight| weight.is_nan() || *weight < 0.) || weights.iter().sum::<f64>() <= 0. {
+                return Err(Error::Config("weights must not be negative and must not all be 0".to_string()));
+            }
+            let state_setters = specs.iter().map(|spec| registry.build_state_setter(spec)).collect::<Result<Vec<_>>>()?;
+            Ok(Box::new(WeightedSampleSetter::new(state_setters, weights, params.get("seed")?)))
+        });
+
+        registry
+    }
+
+    /// A registry without any components
+    pub fn empty() -> Self {
+        Registry {
+            reward_fns: HashMap::new(),
+            obs_builders: HashMap::new(),
+            action_parsers: HashMap::new(),
+            terminal_conditions: HashMap::new(),
+            state_setters: HashMap::new(),
+        }
+    }
+
+    /// Adds a reward function, replacing the one with the same name if there is one
+    pub fn register_reward_fn(
+        &mut self,
+        name: &str,
+        factory: impl Fn(&Params, &Registry) -> Result<Box<dyn RewardFn>> + Send + Sync + 'static,
+    ) {
+        self.reward_fns.insert(name.to_string(), Box::new(factory));
+    }
+
+    /// Adds an observation builder, replacing the one with the same name if there is one
+    pub fn register_obs_builder(
+        &mut self,
+        name: &str,
+        factory: impl Fn(&Params, &Registry) -> Result<Box<dyn ObsBuilder>> + Send + Sync + 'static,
+    ) {
+        self.obs_builders.insert(name.to_string(), Box::new(factory));
+    }
+
+    /// Adds an action parser, replacing the one with the same name if there is one
+    pub fn register_action_parser(
+        &mut self,
+        name: &str,
+        factory: impl Fn(&Params, &Registry) -> Result<Box<dyn ActionParser>> + Send + Sync + 'static,
+    ) {
+        self.action_parsers.insert(name.to_string(), Box::new(factory));
+    }
+
+    /// Adds a terminal condition, replacing the one with the same name if there is one
+    pub fn register_terminal_condition(
+        &mut self,
+        name: &str,
+        factory: impl Fn(&Params, &Registry) -> Result<Box<dyn TerminalCondition>> + Send + Sync + 'static,
+    ) {
+        self.terminal_conditions.insert(name.to_string(), Box::new(factory));
+    }
+
+    /// Adds a state setter, replacing the one with the same name if there is one
+    pub fn register_state_setter(
+        &mut self,
+        name: &str,
+        factory: impl Fn(&Params, &Registry) -> Result<Box<dyn StateSetter>> + Send + Sync + 'static,
+    ) {
+        self.state_setters.insert(name.to_string(), Box::new(factory));
+    }
+
+    pub fn build_reward_fn(&self, spec: &ComponentSpec) -> Result<Box<dyn RewardFn>> {
+        self.build(&self.reward_fns, "reward function", spec)
+    }
+
+    pub fn build_obs_builder(&self, spec: &ComponentSpec) -> Result<Box<dyn ObsBuilder>> {
+        self.build(&self.obs_builders, "observation builder", spec)
+    }
+
+    pub fn build_action_parser(&self, spec: &ComponentSpec) -> Result<Box<dyn ActionParser>> {
+        self.build(&self.action_parsers, "action parser", spec)
+    }
+
+    pub fn build_terminal_condition(&self, spec: &ComponentSpec) -> Result<Box<dyn TerminalCondition>> {
+        self.build(&self.terminal_conditions, "terminal condition", spec)
+    }
+
+    pub fn build_state_setter(&self, spec: &ComponentSpec) -> Result<Box<dyn StateSetter>> {
+        self.build(&self.state_setters, "state setter", spec)
+    }
+
+    fn build<T: ?Sized>(&self, factories: &HashMap<String, ComponentFactory<T>>, kind: &str, spec: &ComponentSpec) -> Result<Box<T>> {
+        let factory = match factories.get(&spec.name) {
+            Some(val) => val,
+            None => {
+                let mut known: Vec<&str> = factories.keys().map(|name| name.as_str()).collect();
+                known.sort_unstable();
+                return Err(Error::Config(format!("unknown {kind} `{}`, the registered ones are: {}", spec.name, known.join(", "))));
+            }
+        };
+
+        let params = Params::new(spec.params.clone());
+        let component = factory(&params, self).map_err(|err| match err {
+            Error::Config(msg) => Error::Config(format!("{} ({kind}): {msg}", spec.name)),
+            err => err,
+        })?;
+        let unused = params.unused();
+        if !unused.is_empty() {
+            return Err(Error::Config(format!("{} ({kind}): unknown parameters {}", spec.name, unused.join(", "))));
+        }
+        Ok(component)
+    }
+}
+
+fn check_lengths(components: usize, weights: usize, weights_name: &str) -> Result<()> {
+    if components != weights {
+        return Err(Error::Config(format!("{weights_name} has {weights} values for {components} components")));
+    }
+    Ok(())
+}
+
+/// [CarBodies] in a config file
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum CarBodiesConfig {
+    #[default]
+    Same,
+    PerTeam { blue: HitboxType, orange: HitboxType },
+    PerSlot(Vec<HitboxType>),
+    Random(Vec<HitboxType>),
+}
+
+impl CarBodiesConfig {
+    /// The lists are leaked since [CarBodies] holds static slices, this is a few bytes for each built config
+    pub fn into_car_bodies(self) -> CarBodies {
+        match self {
+            CarBodiesConfig::Same => CarBodies::Same,
+            CarBodiesConfig::PerTeam { blue, orange } => CarBodies::PerTeam { blue, orange },
+            CarBodiesConfig::PerSlot(bodies) => CarBodies::PerSlot(Box::leak(bodies.into_boxed_slice())),
+            CarBodiesConfig::Random(bodies) => CarBodies::Random(Box::leak(bodies.into_boxed_slice())),
+        }
+    }
+}
+
+/// [GameConfig] in a config file, the car config is given as one of the car bodies
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameConfigFile {
+    pub gravity: f32,
+    pub boost_consumption: f32,
+    pub team_size: usize,
+    pub tick_skip: usize,
+    pub spawn_opponents: bool,
+    pub car_config: HitboxType,
+    pub car_bodies: CarBodiesConfig,
+    pub step_mode: StepMode,
+    pub game_mode: GameMode,
+    pub mutators: Mutators,
+    pub kickoff_phase: Option<KickoffPhase>,
+}
+
+impl Default for GameConfigFile {
+    fn default() -> Self {
+        let config = GameConfig::default();
+        GameConfigFile {
+            gravity: config.gravity,
+            boost_consumption: config.boost_consumption,
+            team_size: config.team_size,
+            tick_skip: config.tick_skip,
+            spawn_opponents: config.spawn_opponents,
+            car_config: HitboxType::Octane,
+            car_bodies: CarBodiesConfig::Same,
+            step_mode: config.step_mode,
+            game_mode: config.game_mode,
+            mutators: config.mutators,
+            kickoff_phase: config.kickoff_phase,
+        }
+    }
+}
+
+impl GameConfigFile {
+    pub fn into_game_config(self) -> GameConfig {
+        GameConfig {
+            gravity: self.gravity,
+            boost_consumption: self.boost_consumption,
+            team_size: self.team_size,
+            tick_skip: self.tick_skip,
+            spawn_opponents: self.spawn_opponents,
+            car_config: self.car_config.config(),
+            car_bodies: self.car_bodies.into_car_bodies(),
+            step_mode: self.step_mode,
+            game_mode: self.game_mode,
+            mutators: self.mutators,
+            kickoff_phase: self.kickoff_phase,
+        }
+    }
+}
+
+/// Contents of a config file for [make_from_config](crate::make::make_from_config)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MakeConfigFile {
+    #[serde(default)]
+    pub game_config: GameConfigFile,
+    pub terminal_condition: ComponentSpec,
+    pub reward_fn: ComponentSpec,
+    /// with `use_single_obs` set to false this is built once for every agent
+    pub obs_builder: ComponentSpec,
+    #[serde(default = "default_use_single_obs")]
+    pub use_single_obs: bool,
+    pub action_parser: ComponentSpec,
+    pub state_setter: ComponentSpec,
+    #[serde(default)]
+    pub auto_reset: bool,
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// plays full matches, missing fields of the table are the [MatchConfig] defaults
+    #[serde(default)]
+    pub match_config: Option<MatchConfig>,
+}
+
+fn default_use_single_obs() -> bool {
+    true
+}
+
+impl MakeConfigFile {
+    /// Reads a config file, the format is picked from the extension (`.toml`, `.json` or `.ron`)
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|err| Error::Config(format!("unable to read {}: {err}", path.display())))?;
+        let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or_default();
+        let parsed = match extension {
+            "toml" => toml::from_str(&text).map_err(|err| err.to_string()),
+            "json" => serde_json::from_str(&text).map_err(|err| err.to_string()),
+            "ron" => ron::from_str(&text).map_err(|err| err.to_string()),
+            _ => return Err(Error::Config(format!("unknown config format of {}, expected .toml, .json or .ron", path.display()))),
+        };
+        parsed.map_err(|err| Error::Config(format!("unable to parse {}: {err}", path.display())))
+    }
+
+    /// Builds the components with `registry`
+    pub fn build(self, registry: &Registry) -> Result<MakeConfig> {
+        let game_config = self.game_config.into_game_config();
+        let agents = if game_config.spawn_opponents { game_config.team_size * 2 } else { game_config.team_size };
+        let obs_builders = if self.use_single_obs { 1 } else { agents };
+
+        Ok(MakeConfig {
+            game_config,
+            terminal_condition: registry.build_terminal_condition(&self.terminal_condition)?,
+            reward_fn: registry.build_reward_fn(&self.reward_fn)?,
+            obs_builder: (0..obs_builders).map(|_| registry.build_obs_builder(&self.obs_builder)).collect::<Result<Vec<_>>>()?,
+            use_single_obs: self.use_single_obs,
+            action_parser: registry.build_action_parser(&self.action_parser)?,
+            state_setter: registry.build_state_setter(&self.state_setter)?,
+            auto_reset: self.auto_reset,
+            team_sizes: None,
+            bots: Vec::new(),
+            opponent_pool: None,
+            seed: self.seed,
+            match_config: self.match_config,
+        })
+    }
+}
@@ -19,7 +19,7 @@ use rocketsim_rs::{
     GameState as GameState_sim,
 };
 
-use crate::make::RenderConfig;
+use crate::{error::{Error, Result}, make::RenderConfig};
 
 #[repr(u8)]
 #[derive(Debug, Clone, Copy)]
@@ -32,20 +32,26 @@ enum UdpPacketTypes {
     Render,
 }
 
-impl From<u8> for UdpPacketTypes {
-    fn from(val: u8) -> Self {
+impl TryFrom<u8> for UdpPacketTypes {
+    type Error = Error;
+
+    fn try_from(val: u8) -> Result<Self> {
         match val {
-            0 => Self::Quit,
-            1 => Self::GameState,
-            2 => Self::Connection,
-            3 => Self::Paused,
-            4 => Self::Speed,
-            5 => Self::Render,
-            _ => panic!("Invalid packet type"),
+            0 => Ok(Self::Quit),
+            1 => Ok(Self::GameState),
+            2 => Ok(Self::Connection),
+            3 => Ok(Self::Paused),
+            4 => Ok(Self::Speed),
+            5 => Ok(Self::Render),
+            _ => Err(invalid_packet(format!("invalid packet type {val}"))),
         }
     }
 }
 
+fn invalid_packet(msg: String) -> Error {
+    Error::Render(io::Error::new(io::ErrorKind::InvalidData, msg))
+}
+
 pub struct Renderer {
     socket: UdpSocket,
     interval: Duration,
@@ -62,76 +68,20 @@ const RLVISER_PATH: &str = if cfg!(windows) { "./rlviser.exe" } else { "./rlvise
 impl Renderer {
     pub fn new(
         render_config: RenderConfig, 
-    ) -> Result<Self, io::Error> {
-        let socket_op = UdpSocket::bind("0.0.0.0:34254");
-        // print the socket address
-        let socket = match socket_op {
-            Ok(val) => {
-                match val.local_addr() {
-                    Ok(val) => {
-                        println!("Renderer in gym listening on {val}");
-                    }
-                    Err(e) => {
-                        println!("Couldn't get local address due to err: {e}, continuing rendering anyways");
-                    }
-                };
-
-                val
-            },
-            Err(e) => {
-                println!("Unable to bind to socket, err: {e}, continuing but not rendering");
-                return Err(e)
-            }
-        };
+    ) -> Result<Self> {
+        let socket = UdpSocket::bind("0.0.0.0:34254").map_err(Error::Render)?;
 
-        if let Err(e) = Command::new(RLVISER_PATH).spawn() {
-            println!("Failed to launch RLViser ({RLVISER_PATH}): {e}");
-        }
+        // the RLViser binary has to be next to the executable, the connection below times out if it does not connect
+        Command::new(RLVISER_PATH).spawn().map_err(Error::Render)?;
 
         let mut buf = [0; 1];
 
-        let res = socket.set_read_timeout(Some(Duration::from_secs(30)));
-        match res {
-            Ok(val) => val,
-            Err(e) => {
-                println!("Could not set timeout, err: {e}, stopping rendering");
-                return Err(e)
-            },
-        };
-
-        let sock_res = socket.recv_from(&mut buf);
-        let (_, src) = match sock_res {
-            Ok(val) => val,
-            Err(e) => {
-                println!("Couldn't receive from buffer, err: {e}, stopping rendering");
-                return Err(e)
-            }
-        };
-
-        if buf[0] == 2 {
-            println!("Renderer connection established to {src}");
-        }
+        socket.set_read_timeout(Some(Duration::from_secs(30))).map_err(Error::Render)?;
+        let (_, src) = socket.recv_from(&mut buf).map_err(Error::Render)?;
 
-        let res = socket.set_read_timeout(None);
-        match res {
-            Ok(val) => val,
-            Err(e) => println!("Could not set timeout, err: {e}, continuing"),
-        };
-
-        let res = socket.set_nonblocking(true);
-        match res {
-            Ok(val) => val,
-            Err(e) => println!("Could not set to nonblocking, err: {e}, continuing"),
-        };
-
-        let res = socket.send_to(&[UdpPacketTypes::Connection as u8], src);
-        match res {
-            Ok(_) => (),
-            Err(e) => {
-                println!("Could not send connection packet, err: {e}, stopping rendering"); 
-                return Err(e)
-            },
-        };
+        socket.set_read_timeout(None).map_err(Error::Render)?;
+        socket.set_nonblocking(true).map_err(Error::Render)?;
+        socket.send_to(&[UdpPacketTypes::Connection as u8], src).map_err(Error::Render)?;
 
         // let (sender, receiver) = channel();
     
@@ -151,38 +101,16 @@ impl Renderer {
         //     }
         // };
 
+        // the speed and pause packets are not needed to render, RLViser keeps its own speed and pause state
+        // when they are lost so these errors are ignored
         let step_speed = render_config.update_rate / 120.;
         let step_speed_f_bytes = step_speed.to_le_bytes();
-        let res = socket.send_to(&[UdpPacketTypes::Speed as u8], src);
-        match res {
-            Ok(_) => (),
-            Err(e) => {
-                println!("Could not send speed packet, err: {e}, continuing"); 
-            },
-        };
-        let res = socket.send_to(&step_speed_f_bytes, src);
-        match res {
-            Ok(_) => (),
-            Err(e) => {
-                println!("Could not send speed packet float, err: {e}, continuing"); 
-            },
-        };
+        let _ = socket.send_to(&[UdpPacketTypes::Speed as u8], src);
+        let _ = socket.send_to(&step_speed_f_bytes, src);
 
         let paused = [UdpPacketTypes::Paused as u8];
-        let res = socket.send_to(&paused, src);
-        match res {
-            Ok(_) => (),
-            Err(e) => {
-                println!("Could not send pause packet, err: {e}, continuing"); 
-            },
-        };
-        let res = socket.send_to(&[0], src);
-        match res {
-            Ok(_) => (),
-            Err(e) => {
-                println!("Could not send pause u8, err: {e}, continuing"); 
-            },
-        };
+        let _ = socket.send_to(&paused, src);
+        let _ = socket.send_to(&[0], src);
 
         // set the update rate for the rendering
         let interval = Duration::from_secs_f32(1. / render_config.update_rate);
@@ -200,19 +128,12 @@ impl Renderer {
             pause: false,
         };
 
-        let res = inst.handle_ret_msg_init();
-        match res {
-            Ok(_) => (),
-            Err(e) => {
-                println!("Could not receive message from rlviser, err: {e}");
-                return Err(e)
-            }
-        };
+        inst.handle_ret_msg_init()?;
 
         Ok(inst)
     }
 
-    pub fn step(&mut self, states: Vec<GameState_sim>) -> io::Result<Option<GameState_sim>> {
+    pub fn step(&mut self, states: Vec<GameState_sim>) -> Result<Option<GameState_sim>> {
         // if self.ctrlc_recv.try_recv().is_ok() {
         //     let res = self.socket.send_to(&[UdpPacketTypes::Quit as u8], self.sock_addr);
         //     match res {
@@ -229,36 +150,13 @@ impl Renderer {
         let mut state_set_data = None;
         for state in states.into_iter() {
             // this is more just to handle if anything gets sent back
-            let res = self.handle_ret_msg();
-            match res {
-                Ok(val) => {
-                    state_set_data = val
-                },
-                Err(e) => {
-                    println!("Could not receive state signal from rlviser, err: {e}");
-                    return Err(e)
-                }
-            };
+            state_set_data = self.handle_ret_msg()?;
     
             // send packet type
-            let res = self.socket.send_to(&[UdpPacketTypes::GameState as u8], self.sock_addr);
-            match res {
-                Ok(val) => val,
-                Err(e) => {
-                    println!("Could not send state signal to rlviser, err: {e}");
-                    return Err(e)
-                }
-            };
+            self.socket.send_to(&[UdpPacketTypes::GameState as u8], self.sock_addr).map_err(Error::Render)?;
     
             // then send the data
-            let res = self.socket.send_to(&state.to_bytes(), self.sock_addr);
-            match res {
-                Ok(val) => val,
-                Err(e) => {
-                    println!("Could not send state data to rlviser, err: {e}");
-                    return Err(e)
-                }
-            };
+            self.socket.send_to(&state.to_bytes(), self.sock_addr).map_err(Error::Render)?;
 
             // sleep timer for tps
             let wait_time = self.next_time - Instant::now();
@@ -277,16 +175,7 @@ impl Renderer {
             // slightly more robust potentially in case of delays (probably unnecessary)
             self.next_time = Instant::now() + Duration::from_secs_f32(0.05);
             
-            let res = self.handle_ret_msg();
-            match res {
-                Ok(val) => {
-                    state_set_data = val
-                },
-                Err(e) => {
-                    println!("Could not receive state signal from rlviser, err: {e}");
-                    return Err(e)
-                }
-            };
+            state_set_data = self.handle_ret_msg()?;
         }
         if let Some(data) = state_set_data {
             return Ok(Some(GameState_sim::from_bytes(&data)))
@@ -295,15 +184,8 @@ impl Renderer {
         Ok(None)
     }
 
-    pub fn close(&mut self) -> io::Result<()> {
-        let res = self.socket.send_to(&[UdpPacketTypes::Quit as u8], self.sock_addr);
-        match res {
-            Ok(val) => val,
-            Err(e) => {
-                println!("Could not send quit signal to rlviser when closing, err: {e}");
-                return Err(e)
-            }
-        };
+    pub fn close(&mut self) -> Result<()> {
+        self.socket.send_to(&[UdpPacketTypes::Quit as u8], self.sock_addr).map_err(Error::Render)?;
 
         Ok(())
     }
@@ -339,37 +221,36 @@ impl Renderer {
 
     fn handle_ret_msg(
         &mut self,
-    ) -> io::Result<Option<Vec<u8>>> {
+    ) -> Result<Option<Vec<u8>>> {
         let mut state_set_buf = Vec::new();
     
         let mut byte_buffer = [0];
 
-        while let Ok((_, src)) = self.socket.recv_from(&mut byte_buffer) {
-            let packet_type = UdpPacketTypes::from(byte_buffer[0]);
+        while self.socket.recv_from(&mut byte_buffer).is_ok() {
+            let packet_type = UdpPacketTypes::try_from(byte_buffer[0])?;
 
             match packet_type {
                 UdpPacketTypes::GameState => {
-                    self.socket.peek_from(&mut self.min_buf)?;
+                    self.socket.peek_from(&mut self.min_buf).map_err(Error::Render)?;
 
                     let num_bytes = GameState_sim::get_num_bytes(&self.min_buf);
                     state_set_buf.resize(num_bytes, 0);
-                    self.socket.recv_from(&mut state_set_buf)?;
-                }
-                UdpPacketTypes::Connection => {
-                    println!("Connection established to {src}");
+                    self.socket.recv_from(&mut state_set_buf).map_err(Error::Render)?;
                 }
+                // RLViser is already connected to by new
+                UdpPacketTypes::Connection => {}
                 UdpPacketTypes::Speed => {
                     let mut speed_buffer = [0; f32::NUM_BYTES];
-                    self.socket.recv_from(&mut speed_buffer)?;
+                    self.socket.recv_from(&mut speed_buffer).map_err(Error::Render)?;
                     let speed = f32::from_bytes(&speed_buffer);
                     self.interval = Duration::from_secs_f32(1. / (120. * speed));
                 }
                 UdpPacketTypes::Paused => {
-                    self.socket.recv_from(&mut byte_buffer)?;
+                    self.socket.recv_from(&mut byte_buffer).map_err(Error::Render)?;
                     self.pause = byte_buffer[0] == 1;
                 }
                 UdpPacketTypes::Quit | UdpPacketTypes::Render => {
-                    panic!("We shouldn't be receiving packets of type {packet_type:?}")
+                    return Err(invalid_packet(format!("We shouldn't be receiving packets of type {packet_type:?}")))
                 }
             }
         }
@@ -385,37 +266,36 @@ impl Renderer {
     // just to make sure the speed value doesn't get updated from the initial speed sent back
     fn handle_ret_msg_init(
         &mut self,
-    ) -> io::Result<bool> {
+    ) -> Result<bool> {
         let mut state_set_buf = Vec::new();
     
         let mut byte_buffer = [0];
 
-        while let Ok((_, src)) = self.socket.recv_from(&mut byte_buffer) {
-            let packet_type = UdpPacketTypes::from(byte_buffer[0]);
+        while self.socket.recv_from(&mut byte_buffer).is_ok() {
+            let packet_type = UdpPacketTypes::try_from(byte_buffer[0])?;
 
             match packet_type {
                 UdpPacketTypes::GameState => {
-                    self.socket.peek_from(&mut self.min_buf)?;
+                    self.socket.peek_from(&mut self.min_buf).map_err(Error::Render)?;
 
                     let num_bytes = GameState_sim::get_num_bytes(&self.min_buf);
                     state_set_buf.resize(num_bytes, 0);
-                    self.socket.recv_from(&mut state_set_buf)?;
-                }
-                UdpPacketTypes::Connection => {
-                    println!("Connection established to {src}");
+                    self.socket.recv_from(&mut state_set_buf).map_err(Error::Render)?;
                 }
+                // RLViser is already connected to by new
+                UdpPacketTypes::Connection => {}
                 UdpPacketTypes::Speed => {
                     let mut speed_buffer = [0; f32::NUM_BYTES];
-                    self.socket.recv_from(&mut speed_buffer)?;
+                    self.socket.recv_from(&mut speed_buffer).map_err(Error::Render)?;
                     // let speed = f32::from_bytes(&speed_buffer);
                     // self.interval = Duration::from_secs_f32(1. / (120. * speed));
                 }
                 UdpPacketTypes::Paused => {
-                    self.socket.recv_from(&mut byte_buffer)?;
+                    self.socket.recv_from(&mut byte_buffer).map_err(Error::Render)?;
                     // self.paused = byte_buffer[0] == 1;
                 }
                 UdpPacketTypes::Quit | UdpPacketTypes::Render => {
-                    panic!("We shouldn't be receiving packets of type {packet_type:?}")
+                    return Err(invalid_packet(format!("We shouldn't be receiving packets of type {packet_type:?}")))
                 }
             }
         }
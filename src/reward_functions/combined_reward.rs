@@ -1,4 +1,5 @@
 use crate::{
+    seeding::derive_seed,
     gamestates::{game_state::GameState, player_data::PlayerData},
     math::element_mult_vec,
 };
@@ -25,6 +26,12 @@ impl RewardFn for CombinedReward {
         }
     }
 
+    fn sub_steps(&mut self, states: &[GameState]) {
+        for struc in &mut self.reward_structs {
+            struc.sub_steps(states);
+        }
+    }
+
     fn get_reward(&mut self, player: &PlayerData, state: &GameState) -> f32 {
         let mut rewards = Vec::<f32>::new();
         for struc in &mut self.reward_structs {
@@ -42,4 +49,15 @@ impl RewardFn for CombinedReward {
         let ret = element_mult_vec(&rewards, &self.reward_weights);
         ret.iter().sum()
     }
+
+    fn set_seed(&mut self, seed: u64) {
+        for (i, struc) in self.reward_structs.iter_mut().enumerate() {
+            struc.set_seed(derive_seed(seed, i as u64));
+        }
+    }
+
+    fn snapshot(&self) -> Option<Box<dyn RewardFn>> {
+        let reward_structs = self.reward_structs.iter().map(|struc| struc.snapshot()).collect::<Option<Vec<_>>>()?;
+        Some(Box::new(CombinedReward { reward_structs, reward_weights: self.reward_weights.clone() }))
+    }
 }
@@ -1,9 +1,10 @@
 use crate::{
-    common_values::{BALL_MAX_SPEED, BLUE_GOAL_BACK, BLUE_TEAM, ORANGE_GOAL_BACK, ORANGE_TEAM},
+    common_values::{BALL_MAX_SPEED, BLUE_TEAM, ORANGE_TEAM},
     gamestates::{game_state::GameState, physics_object::Position, player_data::PlayerData},
     reward_functions::reward_fn::RewardFn,
 };
 
+#[derive(Clone)]
 pub struct VelocityBallToGoalReward {
     own_goal: bool,
     use_scalar_projection: bool,
@@ -22,10 +23,11 @@ impl RewardFn for VelocityBallToGoalReward {
     fn reset(&mut self, _initial_state: &GameState) {}
 
     fn get_reward(&mut self, player: &PlayerData, state: &GameState) -> f32 {
+        // the goal of the game mode of the state, eg. the rims in hoops
         let objective: Position = if (player.team_num == BLUE_TEAM && !self.own_goal) || (player.team_num == ORANGE_TEAM && self.own_goal) {
-            ORANGE_GOAL_BACK
+            state.game_mode().goal_back(ORANGE_TEAM)
         } else {
-            BLUE_GOAL_BACK
+            state.game_mode().goal_back(BLUE_TEAM)
         };
 
         // let pos_diff = element_sub_vec(&objective, &state.ball.position);
@@ -49,4 +51,8 @@ impl RewardFn for VelocityBallToGoalReward {
     fn get_final_reward(&mut self, player: &PlayerData, state: &GameState) -> f32 {
         self.get_reward(player, state)
     }
+
+    fn snapshot(&self) -> Option<Box<dyn RewardFn>> {
+        Some(Box::new(self.clone()))
+    }
 }
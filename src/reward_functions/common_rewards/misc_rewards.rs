@@ -6,6 +6,7 @@ use crate::{
 };
 use std::collections::HashMap;
 
+#[derive(Clone)]
 pub struct EventReward {
     weights: Vec<f32>,
     last_registered_values: HashMap<i32, Vec<f32>>,
@@ -87,8 +88,13 @@ impl RewardFn for EventReward {
     fn get_final_reward(&mut self, player: &PlayerData, state: &GameState) -> f32 {
         self.get_reward(player, state)
     }
+
+    fn snapshot(&self) -> Option<Box<dyn RewardFn>> {
+        Some(Box::new(self.clone()))
+    }
 }
 
+#[derive(Clone)]
 pub struct VelocityReward {
     negative: bool,
 }
@@ -112,8 +118,13 @@ impl RewardFn for VelocityReward {
     fn get_final_reward(&mut self, player: &PlayerData, state: &GameState) -> f32 {
         self.get_reward(player, state)
     }
+
+    fn snapshot(&self) -> Option<Box<dyn RewardFn>> {
+        Some(Box::new(self.clone()))
+    }
 }
 
+#[derive(Clone)]
 pub struct SaveBoostReward {}
 
 impl SaveBoostReward {
@@ -138,4 +149,34 @@ impl RewardFn for SaveBoostReward {
     fn get_final_reward(&mut self, player: &PlayerData, state: &GameState) -> f32 {
         self.get_reward(player, state)
     }
+
+    fn snapshot(&self) -> Option<Box<dyn RewardFn>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// Always 0, for gyms that are only used for inference
+#[derive(Clone, Copy, Default)]
+pub struct ZeroReward;
+
+impl ZeroReward {
+    pub fn new() -> Self {
+        ZeroReward
+    }
+}
+
+impl RewardFn for ZeroReward {
+    fn reset(&mut self, _initial_state: &GameState) {}
+
+    fn get_reward(&mut self, _player: &PlayerData, _state: &GameState) -> f32 {
+        0.
+    }
+
+    fn get_final_reward(&mut self, _player: &PlayerData, _state: &GameState) -> f32 {
+        0.
+    }
+
+    fn snapshot(&self) -> Option<Box<dyn RewardFn>> {
+        Some(Box::new(*self))
+    }
 }
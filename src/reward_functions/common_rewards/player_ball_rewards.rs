@@ -4,6 +4,7 @@ use crate::{
     reward_functions::reward_fn::RewardFn,
 };
 
+#[derive(Clone)]
 pub struct VelocityPlayerToBallReward {
     use_scalar_projection: bool,
 }
@@ -36,4 +37,8 @@ impl RewardFn for VelocityPlayerToBallReward {
     fn get_final_reward(&mut self, player: &PlayerData, state: &GameState) -> f32 {
         self.get_reward(player, state)
     }
+
+    fn snapshot(&self) -> Option<Box<dyn RewardFn>> {
+        Some(Box::new(self.clone()))
+    }
 }
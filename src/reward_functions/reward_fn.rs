@@ -1,8 +1,19 @@
 use crate::gamestates::{game_state::GameState, player_data::PlayerData};
 
-pub trait RewardFn {
+pub trait RewardFn: Send {
     fn reset(&mut self, initial_state: &GameState);
     fn pre_step(&mut self, _state: &GameState) {}
+    /// Called before `pre_step` with the states of the ticks in between the previous step and this one,
+    /// only when the game config uses [StepMode::History](crate::envs::game_match::StepMode::History).
+    fn sub_steps(&mut self, _states: &[GameState]) {}
     fn get_reward(&mut self, player: &PlayerData, state: &GameState) -> f32;
     fn get_final_reward(&mut self, player: &PlayerData, state: &GameState) -> f32;
+    /// Seeds the randomness of this reward function (if any), see [GameMatch::set_seeds](crate::envs::game_match::GameMatch::set_seeds)
+    fn set_seed(&mut self, _seed: u64) {}
+    /// Copy of this reward function (including any internal state) for [Gym::snapshot](crate::Gym::snapshot).
+    ///
+    /// Returns `None` by default which means that the gym can not be snapshotted with this reward function.
+    fn snapshot(&self) -> Option<Box<dyn RewardFn>> {
+        None
+    }
 }
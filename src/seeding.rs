@@ -0,0 +1,37 @@
+//! Splitting the seed of a gym into independent streams, see [GameMatch::set_seeds](crate::envs::game_match::GameMatch::set_seeds).
+//!
+//! Every component gets its own seed that is derived from the gym's seed, so changing or adding one stochastic
+//! component (eg. a reward with noise) does not change the random numbers that the others see.
+
+/// The components that get their own seed
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SeedStream {
+    /// random kickoffs of the arena when the cars are rebuilt
+    Arena,
+    StateSetter,
+    RewardFn,
+    TerminalCondition,
+    ObsBuilder,
+    ActionParser,
+    TeamSizes,
+    OpponentPool,
+    Bots,
+}
+
+impl SeedStream {
+    /// Seed of this stream for the gym seed `seed`
+    pub fn derive(self, seed: u64) -> u64 {
+        derive_seed(seed, self as u64)
+    }
+}
+
+/// Derives the seed of child `index` from `seed`, used for the streams and for components that hold other
+/// components (eg. the seed of each reward function of a [CombinedReward](crate::CombinedReward)).
+///
+/// This is a splitmix64 step so nearby seeds and indices give unrelated results.
+pub fn derive_seed(seed: u64, index: u64) -> u64 {
+    let mut z = seed ^ index.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
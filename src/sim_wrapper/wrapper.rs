@@ -1,18 +1,21 @@
 use rocketsim_rs::{
-    cxx::UniquePtr, math::{RotMat, Vec3}, sim::{Arena, BallState, CarConfig, CarControls, CarState, Team}, BoostPad, GameState as GameState_sim
+    cxx::UniquePtr, math::{RotMat, Vec3}, sim::{Arena, ArenaConfig, BallState, CarConfig, CarControls, CarState, MutatorConfig, Team}, BoostPad, GameState as GameState_sim, NoCarFound
 };
+use rand::{rng, rngs::SmallRng, Rng, SeedableRng};
 // use std::cell::RefCell;
-use std::{collections::HashMap, sync::RwLock};
+use std::{collections::HashMap, pin::Pin};
 
 use crate::{
-    common_values::{BLUE_TEAM, GRAVITY_Z, ORANGE_TEAM, ROCKETSIM_BOOST_PER_SEC},
+    common_values::{BLUE_TEAM, GRAVITY_Z, ORANGE_TEAM, ROCKETSIM_BOOST_PER_SEC, TICKS_PER_SECOND, VOID_BALL_POSITION},
+    error::{Error, Result},
     gamestates::{
         game_state::GameState as GameState_rlgym,
         physics_object::{PhysicsObject, Position, Velocity},
-        player_data::PlayerData,
+        player_data::{PlayerData, StepEvents},
     },
     state_setters::wrappers::state_wrapper::StateWrapper, 
-    envs::game_match::GameConfig,
+    envs::{car_bodies::{same_car_config, HitboxType}, game_match::{GameConfig, GameMode, StepMode}, kickoff::KickoffPhase, mutators::ActiveMutators},
+    seeding::derive_seed,
 };
 
 /// used as a means to store stats for a particular agent
@@ -31,223 +34,367 @@ pub struct Stats {
     pub bumped_count: u32,
 }
 
+/// States from [RocketsimWrapper::step]
+#[derive(Clone, Debug)]
+pub struct SimStep {
+    /// the state that the gym uses, after the last tick unless the step mode is [StepMode::FirstTick]
+    pub state: GameState_rlgym,
+    /// states after each tick before the last one, only filled with [StepMode::History]
+    pub sub_states: Vec<GameState_rlgym>,
+    /// RocketSim states of every tick if they were requested
+    pub sim_states: Option<Vec<GameState_sim>>,
+}
+
+/// Scores and stats of a single arena.
+///
+/// This is updated from the arena callbacks which receive a pointer to it as their user data.
+#[derive(Clone, Debug, Default)]
+struct ArenaBookkeeping {
+    blue_score: i32,
+    orange_score: i32,
+    last_goal_tick: u64,
+    tick_skip: u64,
+    stats: Vec<(u32, Stats)>,
+}
+
+impl ArenaBookkeeping {
+    fn reset_stats(&mut self, car_ids: &[u32]) {
+        self.stats.clear();
+        self.stats.extend(car_ids.iter().map(|id| (*id, Stats::default())));
+    }
+
+    fn get_stats_mut(&mut self, car_id: u32) -> Option<&mut Stats> {
+        self.stats.iter_mut().find(|(id, _)| *id == car_id).map(|(_, stats)| stats)
+    }
+}
+
+/// Accumulates the [StepEvents] of every car over the ticks of a step, keyed by RocketSim car id
+#[derive(Clone, Debug, Default)]
+struct StepEventTracker {
+    events: HashMap<u32, StepEvents>,
+    hit_ticks: HashMap<u32, u64>,
+    on_ground: HashMap<u32, bool>,
+    pad_cooldowns: Vec<f32>,
+    // stats at the start of the step, demos and bumps are taken from the difference since the callbacks already count them
+    stats_start: Vec<(u32, Stats)>,
+}
+
+impl StepEventTracker {
+    fn clear(&mut self) {
+        self.events.clear();
+        self.hit_ticks.clear();
+        self.on_ground.clear();
+        self.pad_cooldowns.clear();
+        self.stats_start.clear();
+    }
+
+    fn get_events(&self, car_id: u32, stats: &Stats) -> StepEvents {
+        let mut events = self.events.get(&car_id).copied().unwrap_or_default();
+        if let Some((_, start)) = self.stats_start.iter().find(|(id, _)| *id == car_id) {
+            events.demos_dealt = stats.demolitions.saturating_sub(start.demolitions);
+            events.demos_received = stats.demoed.saturating_sub(start.demoed);
+            events.bumps_dealt = stats.bumps_count.saturating_sub(start.bumps_count);
+            events.bumps_received = stats.bumped_count.saturating_sub(start.bumped_count);
+        }
+        events
+    }
+}
+
+/// Kickoff that is in progress, from the tick that the kickoff was set until the first touch
+#[derive(Clone, Copy, Debug, Default)]
+struct KickoffTracker {
+    active: bool,
+    start_tick: u64,
+    countdown_ticks: u64,
+    hold_ball: bool,
+    // ball of the kickoff, which is released with this velocity at the end of the countdown
+    ball: BallState,
+}
+
+impl KickoffTracker {
+    fn launches_ball(&self) -> bool {
+        self.ball.vel.x != 0. || self.ball.vel.y != 0. || self.ball.vel.z != 0.
+    }
+}
+
 pub struct RocketsimWrapper {
     arena: UniquePtr<Arena>,
     car_ids: Vec<u32>,
     tick_skip: usize,
-    car_config: &'static CarConfig,
+    step_mode: StepMode,
+    game_mode: GameMode,
+    mutators: ActiveMutators,
+    // car config and body of each slot, in the order of car_ids
+    car_configs: Vec<&'static CarConfig>,
+    hitbox_types: Vec<HitboxType>,
     jump_timer: f32,
     prev_touched_ticks: HashMap<u32, u64>,
     car_id_map: HashMap<u32, i32>,
     on_ground_vec: Vec<bool>,
+    step_events: StepEventTracker,
+    kickoff_phase: Option<KickoffPhase>,
+    kickoff: KickoffTracker,
+    // picks the seed of each random kickoff so that they can be reproduced
+    kickoff_rng: SmallRng,
+    // picks the bodies with CarBodies::Random
+    car_body_rng: SmallRng,
+    // owned by the wrapper and freed in drop, this is a raw pointer since the arena callbacks also write to it
+    bookkeeping: *mut ArenaBookkeeping,
+}
+
+// SAFETY: the arena is Send and the bookkeeping is owned by the wrapper, it is only accessed from the arena callbacks
+// while stepping (which requires &mut self) so moving the wrapper to another thread along with the arena is fine
+unsafe impl Send for RocketsimWrapper {}
+
+impl Drop for RocketsimWrapper {
+    fn drop(&mut self) {
+        // SAFETY: the pointer came from Box::into_raw in new and is only freed here
+        unsafe { drop(Box::from_raw(self.bookkeeping)) };
+    }
+}
+
+impl Clone for RocketsimWrapper {
+    /// Deep copy of the arena (physics, tick count, etc.) with its own copy of the scores and stats
+    fn clone(&self) -> Self {
+        // the callbacks are not copied since they would still point to the bookkeeping of this wrapper
+        let mut arena = Arena::clone(&self.arena, false);
+        let bookkeeping = Box::into_raw(Box::new(self.bookkeeping().clone()));
+        Self::set_callbacks(&mut arena, bookkeeping);
+
+        RocketsimWrapper {
+            arena,
+            car_ids: self.car_ids.clone(),
+            tick_skip: self.tick_skip,
+            step_mode: self.step_mode,
+            game_mode: self.game_mode,
+            mutators: self.mutators,
+            car_configs: self.car_configs.clone(),
+            hitbox_types: self.hitbox_types.clone(),
+            jump_timer: self.jump_timer,
+            prev_touched_ticks: self.prev_touched_ticks.clone(),
+            car_id_map: self.car_id_map.clone(),
+            on_ground_vec: self.on_ground_vec.clone(),
+            step_events: self.step_events.clone(),
+            kickoff_phase: self.kickoff_phase,
+            kickoff: self.kickoff,
+            kickoff_rng: self.kickoff_rng.clone(),
+            car_body_rng: self.car_body_rng.clone(),
+            bookkeeping,
+        }
+    }
 }
 
 impl RocketsimWrapper {
-    thread_local!(
-        static BLUE_SCORE: RwLock<i32> = const { RwLock::new(0) };
-        static ORANGE_SCORE: RwLock<i32> = const { RwLock::new(0) };
-        static LAST_GOAL_TICK: RwLock<u64> = const { RwLock::new(0) };
-        static STATS: RwLock<Vec<(u32, Stats)>> = const { RwLock::new(Vec::new()) };
-    );
 
     pub fn new(config: GameConfig) -> Self {
         // TODO: input more game config stuff here
         // rocketsim start
         // required only once for all threads so we should do it before the multithreading parts instead of here
         // rocketsim_rs::init(None, false);
-        let mut rocket_sim_instance = Arena::default_standard();
+        let mut rocket_sim_instance = Arena::new(config.game_mode.into(), ArenaConfig::default(), 120);
 
-        let mut sim_mutator_config = rocket_sim_instance.get_mutator_config();
-        sim_mutator_config.gravity.z = GRAVITY_Z * config.gravity;
-        sim_mutator_config.boost_used_per_second = ROCKETSIM_BOOST_PER_SEC * config.boost_consumption;
+        let sim_mutator_config = Self::mutator_config(&config);
         rocket_sim_instance.pin_mut().set_mutator_config(sim_mutator_config);
 
-        rocket_sim_instance.pin_mut().reset_to_random_kickoff(None);
-        let mut car_ids = Vec::new();
-        let mut car_id_map = HashMap::new();
-        if config.spawn_opponents {
-            let mut i = 1;
-            // spawn blue cars
-            for _ in 0..config.team_size {
-                let car_id = rocket_sim_instance.pin_mut().add_car(Team::Blue, config.car_config);
-                car_id_map.insert(car_id, i);
-                car_ids.push(car_id);
-                i += 1;
-            }
-            // spawn orange cars
-            for _ in 0..config.team_size {
-                let car_id = rocket_sim_instance.pin_mut().add_car(Team::Orange, config.car_config);
-                car_id_map.insert(car_id, i);
-                car_ids.push(car_id);
-                i += 1;
-            }
-        } else {
-            let mut i = 1;
-            // spawn blue cars
-            for _ in 0..config.team_size as u32 {
-                let car_id = rocket_sim_instance.pin_mut().add_car(Team::Blue, config.car_config);
-                car_id_map.insert(car_id, i);
-                car_ids.push(car_id);
-                i += 1;
-            }
-        }
+        let mut kickoff_rng = SmallRng::seed_from_u64(rng().random_range(0..10000));
+        rocket_sim_instance.pin_mut().reset_to_random_kickoff(Some(kickoff_rng.random_range(0..i32::MAX)));
+        let mut car_body_rng = SmallRng::seed_from_u64(rng().random_range(0..10000));
+        let car_configs = config.car_bodies.resolve(config.car_config, config.team_size, config.spawn_opponents, &mut car_body_rng);
+        let (car_ids, car_id_map) = Self::add_cars(&mut rocket_sim_instance, &car_configs, config.team_size);
 
         // init on_ground array
         let num_cars = if config.spawn_opponents { config.team_size * 2 } else { config.team_size };
         let on_ground_vec = vec![false; num_cars];
 
         // init stats
-        Self::STATS.with(|stats| {
-            let mut guard = stats.write().unwrap();
-            for id in car_ids.iter() {
-                guard.push((*id, Stats::default()));
-            }
-        });
-
-        rocket_sim_instance.pin_mut().set_goal_scored_callback(
-            |mut arena, team, tick_skip| {
-                let curr_tick = arena.as_mut().get_tick_count();
-                let tick_skip = tick_skip as u64;
-
-                // -- This section is for orange and blue scores --
-                let last_goal_tick = Self::LAST_GOAL_TICK.with(|val| *val.read().unwrap());
-
-                // make it so that tick skip doesn't count multiple goals scored
-                if curr_tick < last_goal_tick + tick_skip {
-                    Self::LAST_GOAL_TICK.with(|val| {
-                        let mut ref_val = val.write().unwrap();
-                        *ref_val = curr_tick;
-                    });
-                    return;
-                }
-
-                if team == Team::Blue {
-                    Self::BLUE_SCORE.with(|val| *val.write().unwrap() += 1);
-                } else {
-                    Self::ORANGE_SCORE.with(|val| *val.write().unwrap() += 1);
-                }
+        let mut bookkeeping = Box::new(ArenaBookkeeping { tick_skip: config.tick_skip as u64, ..Default::default() });
+        bookkeeping.reset_stats(&car_ids);
+        let bookkeeping = Box::into_raw(bookkeeping);
 
-                // value that holds the last tick the goal was scored from
-                Self::LAST_GOAL_TICK.with(|val| *val.write().unwrap() = curr_tick);
-                // -- end of section --
-
-                // -- start of stats section --
-                // section adapted from stat_tracker in bindings made by VirxEC
-
-                // Collect all valid ball touches
-                let mut all_ball_touches = arena
-                    .as_mut()
-                    .get_car_infos()
-                    .into_iter()
-                    .filter_map(|car_info| {
-                        if car_info.state.ball_hit_info.is_valid {
-                            Some((car_info.id, car_info.team, car_info.state.ball_hit_info.tick_count_when_hit))
-                        } else {
-                            None
-                        }
-                    })
-                    .collect::<Vec<_>>();
-                // Sort by ball touch time
-                all_ball_touches.sort_by_key(|(_, _, tick_count_when_hit)| *tick_count_when_hit);
-
-                // Sort ball touches by team
-                let ball_touches = [
-                    all_ball_touches.iter().filter(|(_, team, _)| *team == Team::Blue).map(|(id, _, _)| *id).collect::<Vec<_>>(),
-                    all_ball_touches
-                        .iter()
-                        .filter(|(_, team, _)| *team == Team::Orange)
-                        .map(|(id, _, _)| *id)
-                        .collect::<Vec<_>>(),
-                ];
-
-                // update stats
-                let t_index = team as u8 as usize;
-
-                Self::STATS.with(|stats| {
-                    // it's possible no car touched the ball on the team that got the goal
-                    // so ensure that were was at least one ball touch
-                    if !ball_touches[t_index].is_empty() {
-                        // the latest ball touch on the same team is the scorer
-                        let scorer = ball_touches[t_index].last().copied().unwrap();
-                        // println!("Car {scorer} SCORED");
-
-                        let mut guard = stats.write().unwrap();
-                        // +1 to the car's goals stat
-                        guard.iter_mut().find(|(id, _)| *id == scorer).unwrap().1.goals += 1;
-
-                        if ball_touches[t_index].len() > 1 {
-                            // if there were two ball touches, they get the assist
-                            let assist = ball_touches[t_index][ball_touches[t_index].len() - 2];
-
-                            // Get the tick count of when the scorer and assist touched the ball
-                            let scorer_tick = arena.as_mut().get_car(scorer).ball_hit_info.tick_count_when_hit;
-                            let assist_tick = arena.as_mut().get_car(assist).ball_hit_info.tick_count_when_hit;
-
-                            // ensure that the assist is < 5s before the touch of the scoring player
-                            if (scorer_tick - assist_tick) as f32 / arena.get_tick_rate() < 5. {
-                                // println!("CAR {assist} got an ASSIST");
-
-                                // +1 to the car's assists stat
-                                guard.iter_mut().find(|(id, _)| id == &assist).unwrap().1.assists += 1;
-                            }
-                        }
-
-                        if let Some(latest_hit_id) = all_ball_touches.last().map(|(id, _, _)| *id) {
-                            // if the last hit was not the scorer, they get the own goal
-                            // rocket league tracks this stat in secret and isn't shown on the scoreboard
-                            if latest_hit_id != scorer {
-                                // println!("CAR {latest_hit_id} OWN GOALED");
-
-                                // +1 to the car's own goals stat
-                                guard.iter_mut().find(|(id, _)| *id == latest_hit_id).unwrap().1.own_goals += 1;
-                            }
-                        }
-                    }
-                });
-                // -- end of stats section --
-            },
-            config.tick_skip,
-        );
-
-        rocket_sim_instance.pin_mut().set_car_bump_callback(
-            |_, bumper, victim, is_demo, _| {
-                Self::STATS.with(|stats| {
-                    let mut guard = stats.write().unwrap();
-                    // get bumper stats
-                    let stats_for_bumper_id = guard.iter_mut().find(|(id, _)| *id == bumper).unwrap();
-                    // bumper stats adjustment
-                    if is_demo {
-                        stats_for_bumper_id.1.demolitions += 1;
-                    }
-                    stats_for_bumper_id.1.bumps_count += 1;
-                    stats_for_bumper_id.1.last_car_bumped_id = victim;
-
-                    // get victim stats
-                    let stats_for_victim_id = guard.iter_mut().find(|(id, _)| *id == victim).unwrap();
-                    // victim stats adjustment
-                    if is_demo {
-                        stats_for_victim_id.1.demoed += 1;
-                    }
-                    stats_for_victim_id.1.bumped_count += 1;
-                    stats_for_victim_id.1.last_bumped_by_id = bumper;
-                });
-            },
-            0,
-        );
+        Self::set_callbacks(&mut rocket_sim_instance, bookkeeping);
 
         RocketsimWrapper {
             arena: rocket_sim_instance,
             car_ids,
             tick_skip: config.tick_skip,
-            car_config: config.car_config,
+            step_mode: config.step_mode,
+            game_mode: config.game_mode,
+            mutators: ActiveMutators::from(&sim_mutator_config),
+            hitbox_types: car_configs.iter().map(|car_config| HitboxType::from_config(car_config)).collect(),
+            car_configs,
             jump_timer: 1.25,
             prev_touched_ticks: HashMap::new(),
             car_id_map,
             on_ground_vec,
+            step_events: StepEventTracker::default(),
+            kickoff_phase: config.kickoff_phase,
+            kickoff: KickoffTracker::default(),
+            kickoff_rng,
+            car_body_rng,
+            bookkeeping,
+        }
+    }
+
+    /// Adds a car for each config, the first `team_size` cars are blue and the rest are orange.
+    /// Returns the RocketSim ids of the cars and the map from them to the rlgym ids (which start at 1).
+    fn add_cars(arena: &mut UniquePtr<Arena>, car_configs: &[&'static CarConfig], team_size: usize) -> (Vec<u32>, HashMap<u32, i32>) {
+        let mut car_ids = Vec::with_capacity(car_configs.len());
+        let mut car_id_map = HashMap::new();
+        for (i, car_config) in car_configs.iter().enumerate() {
+            let team = if i < team_size { Team::Blue } else { Team::Orange };
+            let car_id = arena.pin_mut().add_car(team, car_config);
+            car_id_map.insert(car_id, i as i32 + 1);
+            car_ids.push(car_id);
         }
+        (car_ids, car_id_map)
+    }
+
+    /// RocketSim mutators of the game mode of `config` with the gravity and boost multipliers and the mutators of `config` applied
+    fn mutator_config(config: &GameConfig) -> MutatorConfig {
+        let mut sim_mutator_config = MutatorConfig::default(config.game_mode.into());
+        sim_mutator_config.gravity.z = GRAVITY_Z * config.gravity;
+        sim_mutator_config.boost_used_per_second = ROCKETSIM_BOOST_PER_SEC * config.boost_consumption;
+        config.mutators.apply(&mut sim_mutator_config);
+        sim_mutator_config
+    }
+
+    fn set_callbacks(arena: &mut UniquePtr<Arena>, bookkeeping: *mut ArenaBookkeeping) {
+        // RocketSim does not allow a goal callback without goals
+        if arena.get_game_mode() != rocketsim_rs::sim::GameMode::TheVoid {
+            arena.pin_mut().set_goal_scored_callback(Self::goal_scored_callback, bookkeeping as usize);
+        }
+        arena.pin_mut().set_car_bump_callback(Self::car_bump_callback, bookkeeping as usize);
+    }
+
+    fn bookkeeping(&self) -> &ArenaBookkeeping {
+        // SAFETY: the pointer is valid for the lifetime of the wrapper and the callbacks only write to it
+        // while the arena is being stepped, which requires &mut self
+        unsafe { &*self.bookkeeping }
+    }
+
+    fn bookkeeping_mut(&mut self) -> &mut ArenaBookkeeping {
+        // SAFETY: see bookkeeping()
+        unsafe { &mut *self.bookkeeping }
     }
 
-    pub fn set_state(&mut self, state_wrapper: StateWrapper, get_sim_state: bool) -> (GameState_rlgym, Option<GameState_sim>) {
+    fn goal_scored_callback(mut arena: Pin<&mut Arena>, team: Team, user_data: usize) {
+        // SAFETY: user_data is the bookkeeping pointer of the wrapper that owns this arena
+        let bookkeeping = unsafe { &mut *(user_data as *mut ArenaBookkeeping) };
+        let curr_tick = arena.as_mut().get_tick_count();
+
+        // -- This section is for orange and blue scores --
+        // make it so that tick skip doesn't count multiple goals scored
+        if curr_tick < bookkeeping.last_goal_tick + bookkeeping.tick_skip {
+            bookkeeping.last_goal_tick = curr_tick;
+            return;
+        }
+
+        if team == Team::Blue {
+            bookkeeping.blue_score += 1;
+        } else {
+            bookkeeping.orange_score += 1;
+        }
+
+        // value that holds the last tick the goal was scored from
+        bookkeeping.last_goal_tick = curr_tick;
+        // -- end of section --
+
+        // -- start of stats section --
+        // section adapted from stat_tracker in bindings made by VirxEC
+
+        // Collect all valid ball touches
+        let mut all_ball_touches = arena
+            .as_mut()
+            .get_car_infos()
+            .into_iter()
+            .filter_map(|car_info| {
+                if car_info.state.ball_hit_info.is_valid {
+                    Some((car_info.id, car_info.team, car_info.state.ball_hit_info.tick_count_when_hit))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        // Sort by ball touch time
+        all_ball_touches.sort_by_key(|(_, _, tick_count_when_hit)| *tick_count_when_hit);
+
+        // Sort ball touches by team
+        let ball_touches = [
+            all_ball_touches.iter().filter(|(_, team, _)| *team == Team::Blue).map(|(id, _, _)| *id).collect::<Vec<_>>(),
+            all_ball_touches
+                .iter()
+                .filter(|(_, team, _)| *team == Team::Orange)
+                .map(|(id, _, _)| *id)
+                .collect::<Vec<_>>(),
+        ];
+
+        // update stats
+        let t_index = team as u8 as usize;
+
+        // it's possible no car touched the ball on the team that got the goal
+        // so ensure that were was at least one ball touch
+        if let Some(scorer) = ball_touches[t_index].last().copied() {
+            // the latest ball touch on the same team is the scorer
+            // +1 to the car's goals stat
+            if let Some(stats) = bookkeeping.get_stats_mut(scorer) {
+                stats.goals += 1;
+            }
+
+            if ball_touches[t_index].len() > 1 {
+                // if there were two ball touches, they get the assist
+                let assist = ball_touches[t_index][ball_touches[t_index].len() - 2];
+
+                // Get the tick count of when the scorer and assist touched the ball
+                let scorer_tick = arena.as_mut().get_car(scorer).ball_hit_info.tick_count_when_hit;
+                let assist_tick = arena.as_mut().get_car(assist).ball_hit_info.tick_count_when_hit;
+
+                // ensure that the assist is < 5s before the touch of the scoring player
+                if (scorer_tick - assist_tick) as f32 / arena.get_tick_rate() < 5. {
+                    // +1 to the car's assists stat
+                    if let Some(stats) = bookkeeping.get_stats_mut(assist) {
+                        stats.assists += 1;
+                    }
+                }
+            }
+
+            if let Some(latest_hit_id) = all_ball_touches.last().map(|(id, _, _)| *id) {
+                // if the last hit was not the scorer, they get the own goal
+                // rocket league tracks this stat in secret and isn't shown on the scoreboard
+                if latest_hit_id != scorer {
+                    // +1 to the car's own goals stat
+                    if let Some(stats) = bookkeeping.get_stats_mut(latest_hit_id) {
+                        stats.own_goals += 1;
+                    }
+                }
+            }
+        }
+        // -- end of stats section --
+    }
+
+    fn car_bump_callback(_arena: Pin<&mut Arena>, bumper: u32, victim: u32, is_demo: bool, user_data: usize) {
+        // SAFETY: user_data is the bookkeeping pointer of the wrapper that owns this arena
+        let bookkeeping = unsafe { &mut *(user_data as *mut ArenaBookkeeping) };
+
+        // bumper stats adjustment
+        if let Some(stats) = bookkeeping.get_stats_mut(bumper) {
+            if is_demo {
+                stats.demolitions += 1;
+            }
+            stats.bumps_count += 1;
+            stats.last_car_bumped_id = victim;
+        }
+
+        // victim stats adjustment
+        if let Some(stats) = bookkeeping.get_stats_mut(victim) {
+            if is_demo {
+                stats.demoed += 1;
+            }
+            stats.bumped_count += 1;
+            stats.last_bumped_by_id = bumper;
+        }
+    }
+
+    pub fn set_state(&mut self, state_wrapper: StateWrapper, get_sim_state: bool) -> Result<(GameState_rlgym, Option<GameState_sim>)> {
         let mut sim_state = self.arena.pin_mut().get_game_state();
 
         // reset boost pads
@@ -258,11 +405,14 @@ impl RocketsimWrapper {
         // cars
         for car_info in sim_state.cars.iter_mut() {
             // find the rocketsim car with the correct id
-            let rlgym_id = *self.car_id_map.get(&(car_info.id)).unwrap();
+            let rlgym_id = match self.car_id_map.get(&(car_info.id)) {
+                Some(val) => *val,
+                None => return Err(NoCarFound(car_info.id).into()),
+            };
             let car_wrapper_op = state_wrapper.cars.iter().find(|car| car.get_car_id() == rlgym_id);
             let car_wrapper = match car_wrapper_op {
                 Some(val) => val,
-                None => panic!("Unable to find car that had the correct id in the sim from the state wrapper. This is likely an error from the state setter.")
+                None => return Err(Error::MissingCar(rlgym_id)),
             };
             // dbg
             // let mut car_wrapper = &CarWrapper::new(None, None, None);
@@ -294,7 +444,7 @@ impl RocketsimWrapper {
                 car_info.state.boost = car_wrapper.boost * 100.;
             }
 
-            self.arena.pin_mut().set_car_controls(car_info.id, CarControls::default()).unwrap();
+            self.arena.pin_mut().set_car_controls(car_info.id, CarControls::default())?;
         }
 
         // ball
@@ -313,19 +463,49 @@ impl RocketsimWrapper {
             state_wrapper.ball.angular_velocity.z,
         );
 
-        self.arena.pin_mut().set_game_state(&sim_state).unwrap();
+        if self.game_mode == GameMode::TheVoid {
+            sim_state.ball.pos = Vec3::new(VOID_BALL_POSITION.x, VOID_BALL_POSITION.y, VOID_BALL_POSITION.z);
+            sim_state.ball.vel = Vec3::new(0., 0., 0.);
+            sim_state.ball.ang_vel = Vec3::new(0., 0., 0.);
+        }
 
+        self.arena.pin_mut().set_game_state(&sim_state)?;
+        self.step_events.clear();
+        if state_wrapper.kickoff {
+            self.start_kickoff(self.kickoff_phase);
+        } else {
+            self.kickoff = KickoffTracker::default();
+        }
 
         self.get_rlgym_gamestate(get_sim_state)
     }
 
     // used for state setting from RLViser (which returns a sim state)
-    pub fn set_state_sim(&mut self, sim_state: GameState_sim) -> GameState_rlgym {
-        self.arena.pin_mut().set_game_state(&sim_state).unwrap();
+    pub fn set_state_sim(&mut self, sim_state: GameState_sim) -> Result<GameState_rlgym> {
+        self.arena.pin_mut().set_game_state(&sim_state)?;
+        self.step_events.clear();
+        self.kickoff = KickoffTracker::default();
         self.decode_gamestate(&sim_state)
     }
 
-    fn decode_gamestate(&mut self, sim_gamestate: &GameState_sim) -> GameState_rlgym {
+    /// The void has no ball in play, it is held still at [VOID_BALL_POSITION] (it would fall forever otherwise)
+    fn park_void_ball(&mut self) {
+        if self.game_mode != GameMode::TheVoid {
+            return;
+        }
+        let mut ball = self.arena.pin_mut().get_ball();
+        ball.pos = Vec3::new(VOID_BALL_POSITION.x, VOID_BALL_POSITION.y, VOID_BALL_POSITION.z);
+        ball.vel = Vec3::new(0., 0., 0.);
+        ball.ang_vel = Vec3::new(0., 0., 0.);
+        self.arena.pin_mut().set_ball(ball);
+    }
+
+    fn decode_gamestate(&mut self, sim_gamestate: &GameState_sim) -> Result<GameState_rlgym> {
+        self.decode_gamestate_inner(sim_gamestate, true)
+    }
+
+    /// `commit_touches` is false for intermediate states so that the touch tracking is not advanced by them
+    fn decode_gamestate_inner(&mut self, sim_gamestate: &GameState_sim, commit_touches: bool) -> Result<GameState_rlgym> {
         let curr_tick = self.arena.get_tick_count();
 
         let mut ball = PhysicsObject::new();
@@ -364,8 +544,8 @@ impl RocketsimWrapper {
 
         let mut players = Vec::with_capacity(sim_gamestate.cars.len());
 
-        let orange_score = Self::ORANGE_SCORE.with(|val| *val.read().unwrap());
-        let blue_score = Self::BLUE_SCORE.with(|val| *val.read().unwrap());
+        let orange_score = self.bookkeeping().orange_score;
+        let blue_score = self.bookkeeping().blue_score;
 
         for (car_info, on_ground_car) in sim_gamestate.cars.iter().zip(&self.on_ground_vec) {
             let car = car_info.state;
@@ -433,14 +613,15 @@ impl RocketsimWrapper {
                 }
             };
 
-            let stats = Self::STATS.with(|stats| {
-                let guard = stats.read().unwrap();
-                guard.iter().find(|(id, _)| *id == car_info.id).unwrap().1
-            });
+            let stats_op = self.bookkeeping().stats.iter().find(|(id, _)| *id == car_info.id).map(|(_, stats)| *stats);
+            let stats = match stats_op {
+                Some(val) => val,
+                None => return Err(NoCarFound(car_info.id).into()),
+            };
 
             // to get the last time the ball was touched by this player, otherwise tick = 0
             let last_touch_tick = if car.ball_hit_info.is_valid {
-                if prev_touched_tick != car.ball_hit_info.tick_count_when_hit {
+                if prev_touched_tick != car.ball_hit_info.tick_count_when_hit && commit_touches {
                     self.prev_touched_ticks.insert(car_info.id, car.ball_hit_info.tick_count_when_hit).unwrap()
                 } else {
                     prev_touched_tick
@@ -449,10 +630,12 @@ impl RocketsimWrapper {
                 0
             };
             
+            let step_events = self.step_events.get_events(car_info.id, &stats);
+
             let car_id_op = self.car_id_map.get(&car_info.id);
             let car_id = match car_id_op {
                 Some(val) => *val,
-                None => panic!("unable to find id in car id map")
+                None => return Err(NoCarFound(car_info.id).into()),
             };
 
             let car_bumped_by_id_op = self.car_id_map.get(&stats.last_bumped_by_id);
@@ -467,7 +650,7 @@ impl RocketsimWrapper {
             };
             
             let player = PlayerData {
-                car_id: car_id as i32,
+                car_id,
                 team_num: if car_info.team == Team::Blue { BLUE_TEAM } else { ORANGE_TEAM },
                 match_goals: (orange_score + blue_score) as i64,
                 // TODO: adapt PlayerData struct to structs that represent better RocketSim data
@@ -483,9 +666,9 @@ impl RocketsimWrapper {
                 on_ground: *on_ground_car || car.is_on_ground,
                 // ball_touched: if self.prev_touched_ticks != car.ball_hit_info.tick_count_when_hit && !car.ball_hit_info.is_valid { self.prev_touched_ticks = car.ball_hit_info.tick_count_when_hit; true } else { false },
                 ball_touched: if car.ball_hit_info.is_valid {
-                    prev_touched_tick != car.ball_hit_info.tick_count_when_hit
+                    prev_touched_tick != car.ball_hit_info.tick_count_when_hit || step_events.touches > 0
                 } else {
-                    false
+                    step_events.touches > 0
                 },
                 ball_info: car.ball_hit_info,
                 has_jump: !car.has_jumped,
@@ -495,6 +678,8 @@ impl RocketsimWrapper {
                 inverted_car_data,
                 last_ball_touch_tick: last_touch_tick,
                 last_actions: car_info.state.last_controls,
+                step_events,
+                hitbox_type: self.hitbox_types.get(car_id as usize - 1).copied().unwrap_or_default(),
             };
             players.push(player);
         }
@@ -521,10 +706,13 @@ impl RocketsimWrapper {
 
             *vec_item = *pad;
         }
+        // only the pads that the arena has are mirrored, hoops has fewer and the void has none
         let mut pad_reversed = pad_vec;
-        pad_reversed.reverse();
-        GameState_rlgym {
-            game_type: 0,
+        let num_pads = sim_gamestate.pads.len().min(pad_reversed.len());
+        pad_reversed[..num_pads].reverse();
+        Ok(GameState_rlgym {
+            game_type: self.game_mode.game_type(),
+            mutators: self.mutators,
             blue_score,
             orange_score,
             last_touch: 0,
@@ -534,14 +722,99 @@ impl RocketsimWrapper {
             boost_pads: pad_vec,
             inverted_boost_pads: pad_reversed,
             tick_num: curr_tick,
+            is_kickoff: self.kickoff.active,
+            kickoff_time_remaining: self.kickoff.countdown_ticks as f32 / TICKS_PER_SECOND as f32,
+            is_kickoff_pause: self.kickoff.countdown_ticks > 0,
+            // set by the match clock of the game match if there is one
+            game_seconds_remaining: 0.,
+            is_overtime: false,
+            round_num: 0,
+        })
+    }
+
+    /// Resets the ball and cars to a random kickoff with the kickoff phase `phase`, keeping the scores and stats
+    /// (used between the rounds of a full match)
+    pub fn reset_to_kickoff(&mut self, phase: Option<KickoffPhase>) -> Result<GameState_rlgym> {
+        let kickoff_seed = self.kickoff_rng.random_range(0..i32::MAX);
+        self.arena.pin_mut().reset_to_random_kickoff(Some(kickoff_seed));
+        self.park_void_ball();
+        self.step_events.clear();
+        self.start_kickoff(phase);
+        Ok(self.get_rlgym_gamestate(false)?.0)
+    }
+
+    /// Starts a kickoff from the current state of the arena, the countdown and ball hold of `phase` run from here.
+    /// Without a phase the cars can drive right away but the kickoff is still tracked until the first touch.
+    pub fn start_kickoff(&mut self, phase: Option<KickoffPhase>) {
+        self.kickoff = KickoffTracker {
+            active: true,
+            start_tick: self.arena.get_tick_count(),
+            countdown_ticks: phase.map(|phase| (phase.countdown.max(0.) * TICKS_PER_SECOND as f32).round() as u64).unwrap_or(0),
+            hold_ball: phase.is_some_and(|phase| phase.hold_ball),
+            ball: self.arena.pin_mut().get_ball(),
+        };
+    }
+
+    /// Writes the kickoff that is in progress to `state`
+    pub fn write_kickoff(&self, state: &mut GameState_rlgym) {
+        state.is_kickoff = self.kickoff.active;
+        state.kickoff_time_remaining = self.kickoff.countdown_ticks as f32 / TICKS_PER_SECOND as f32;
+        state.is_kickoff_pause = self.kickoff.countdown_ticks > 0;
+    }
+
+    /// Advances the kickoff by the tick that was just stepped, returns true if the cars were unfrozen on this tick
+    fn track_kickoff(&mut self) -> bool {
+        if !self.kickoff.active {
+            return false;
         }
+        let countdown_ended = self.kickoff.countdown_ticks == 1;
+        self.kickoff.countdown_ticks = self.kickoff.countdown_ticks.saturating_sub(1);
+
+        let start_tick = self.kickoff.start_tick;
+        let touched = self.arena.pin_mut().get_car_infos().iter().any(|car_info| {
+            let hit_info = car_info.state.ball_hit_info;
+            hit_info.is_valid && hit_info.tick_count_when_hit > start_tick
+        });
+        if touched {
+            // a touch also ends the countdown
+            let was_frozen = countdown_ended || self.kickoff.countdown_ticks > 0;
+            self.kickoff = KickoffTracker::default();
+            return was_frozen;
+        }
+
+        if self.kickoff.hold_ball {
+            let launches_ball = self.kickoff.launches_ball();
+            if countdown_ended {
+                self.arena.pin_mut().set_ball(self.kickoff.ball);
+            } else if self.kickoff.countdown_ticks > 0 || !launches_ball {
+                let mut ball = self.kickoff.ball;
+                ball.vel = Vec3::new(0., 0., 0.);
+                ball.ang_vel = Vec3::new(0., 0., 0.);
+                self.arena.pin_mut().set_ball(ball);
+            }
+        }
+        countdown_ended
     }
 
-    pub fn set_game_config(&mut self, new_config: GameConfig, get_sim_state: bool) -> (GameState_rlgym, Option<GameState_sim>) {
-        let mut sim_mutator_config = self.arena.get_mutator_config();
-        sim_mutator_config.gravity.z = GRAVITY_Z * new_config.gravity;
-        sim_mutator_config.boost_used_per_second = ROCKETSIM_BOOST_PER_SEC * new_config.boost_consumption;
+    /// Seeds the random kickoffs of the arena and the random car bodies (both used when the cars are rebuilt by [set_game_config](RocketsimWrapper::set_game_config))
+    pub fn set_seed(&mut self, seed: u64) {
+        self.kickoff_rng = SmallRng::seed_from_u64(seed);
+        self.car_body_rng = SmallRng::seed_from_u64(derive_seed(seed, 0));
+    }
+
+    pub fn set_game_config(&mut self, new_config: GameConfig, get_sim_state: bool) -> Result<(GameState_rlgym, Option<GameState_sim>)> {
+        if new_config.game_mode != self.game_mode {
+            // a different mode needs a new arena, the cars are added to it below since it has none
+            let mut arena = Arena::new(new_config.game_mode.into(), ArenaConfig::default(), 120);
+            Self::set_callbacks(&mut arena, self.bookkeeping);
+            self.arena = arena;
+            self.game_mode = new_config.game_mode;
+        }
+
+        // start from the defaults so that mutators which are no longer set go back to them
+        let sim_mutator_config = Self::mutator_config(&new_config);
         self.arena.pin_mut().set_mutator_config(sim_mutator_config);
+        self.mutators = ActiveMutators::from(&sim_mutator_config);
 
         let mut car_ids = self.arena.get_cars();
         let mut car_blue = 0;
@@ -564,54 +837,29 @@ impl RocketsimWrapper {
         let car_count_blue = new_config.team_size;
         let car_count_orange = if new_config.spawn_opponents {new_config.team_size} else {0};
 
-        // NOTE: need to check if the old car and new car settings are the same, sadly don't think we can check via if the reference points to the same thing?
-        let new_hitbox_size = new_config.car_config.hitbox_size;
-        let old_hitbox_size = self.car_config.hitbox_size;
+        // the cars are rebuilt if any slot changes body, which also happens on every call with CarBodies::Random
+        let new_car_configs = new_config.car_bodies.resolve(
+            new_config.car_config,
+            new_config.team_size,
+            new_config.spawn_opponents,
+            &mut self.car_body_rng,
+        );
+        let bodies_changed = new_car_configs.len() != self.car_configs.len()
+            || new_car_configs.iter().zip(&self.car_configs).any(|(new, old)| !same_car_config(new, old));
 
-        if car_blue != car_count_blue || car_orange != car_count_orange || new_hitbox_size == old_hitbox_size {
+        if car_blue != car_count_blue || car_orange != car_count_orange || bodies_changed {
             for car_id in car_ids.iter() {
-                let err = self.arena.pin_mut().remove_car(*car_id);
-                match err {
-                    Ok(val) => val,
-                    Err(err) => println!("unable to remove car: {err}")
-                };
+                self.arena.pin_mut().remove_car(*car_id)?;
             }
 
-            car_ids.clear();
-            self.car_id_map.clear();
+            let (new_car_ids, car_id_map) = Self::add_cars(&mut self.arena, &new_car_configs, new_config.team_size);
+            car_ids = new_car_ids;
+            self.car_id_map = car_id_map;
             self.prev_touched_ticks.clear();
-    
-            // let mut car_ids = Vec::new();
-            if new_config.spawn_opponents {
-                let mut i = 1;
-                // spawn blue cars
-                for _ in 0..new_config.team_size {
-                    let car_id = self.arena.pin_mut().add_car(Team::Blue, new_config.car_config);
-                    self.car_id_map.insert(car_id, i);
-                    // not really necessary to insert 0 here but why not
-                    self.prev_touched_ticks.insert(car_id, 0);
-                    car_ids.push(car_id);
-                    i += 1;
-                }
-                // spawn orange cars
-                for _ in 0..new_config.team_size {
-                    let car_id = self.arena.pin_mut().add_car(Team::Orange, new_config.car_config);
-                    self.car_id_map.insert(car_id, i);
-                    self.prev_touched_ticks.insert(car_id, 0);
-                    car_ids.push(car_id);
-                    i += 1;
-                }
-            } else {
-                let mut i = 1;
-                // spawn blue cars
-                for _ in 0..new_config.team_size as u32 {
-                    let car_id = self.arena.pin_mut().add_car(Team::Blue, new_config.car_config);
-                    self.car_id_map.insert(car_id, i);
-                    self.prev_touched_ticks.insert(car_id, 0);
-                    car_ids.push(car_id);
-                    i += 1;
-                }
-            }
+            // not really necessary to insert 0 here but why not
+            self.prev_touched_ticks.extend(car_ids.iter().map(|car_id| (*car_id, 0)));
+            self.hitbox_types = new_car_configs.iter().map(|car_config| HitboxType::from_config(car_config)).collect();
+            self.car_configs = new_car_configs;
         }
         // for car_id in car_ids {
         //     let err = self.arena.pin_mut().remove_car(car_id);
@@ -638,49 +886,125 @@ impl RocketsimWrapper {
         //     }
         // }
 
-        self.arena.pin_mut().reset_to_random_kickoff(None);
+        let kickoff_seed = self.kickoff_rng.random_range(0..i32::MAX);
+        self.arena.pin_mut().reset_to_random_kickoff(Some(kickoff_seed));
+        self.kickoff_phase = new_config.kickoff_phase;
+        self.start_kickoff(self.kickoff_phase);
 
         // init stats
-        Self::STATS.with(|stats| {
-            let mut guard = stats.write().unwrap();
-            guard.clear();
-            for id in car_ids.iter() {
-                guard.push((*id, Stats::default()));
-            }
-        });
+        self.bookkeeping_mut().reset_stats(&car_ids);
+        self.bookkeeping_mut().tick_skip = new_config.tick_skip as u64;
 
         self.car_ids = car_ids;
         self.tick_skip = new_config.tick_skip;
-        self.car_config = new_config.car_config;
+        self.step_mode = new_config.step_mode;
 
         self.on_ground_vec = vec![false; self.car_ids.len()];
+        self.step_events.clear();
 
         self.get_rlgym_gamestate(get_sim_state)
     }
 
-    pub fn get_rlgym_gamestate(&mut self, get_sim_state: bool) -> (GameState_rlgym, Option<GameState_sim>) {
+    pub fn get_rlgym_gamestate(&mut self, get_sim_state: bool) -> Result<(GameState_rlgym, Option<GameState_sim>)> {
         let rlsim_gamestate = self.arena.pin_mut().get_game_state();
         if get_sim_state {
-            (self.decode_gamestate(&rlsim_gamestate), Some(rlsim_gamestate))
+            Ok((self.decode_gamestate(&rlsim_gamestate)?, Some(rlsim_gamestate)))
         } else {
-            (self.decode_gamestate(&rlsim_gamestate), None)
+            Ok((self.decode_gamestate(&rlsim_gamestate)?, None))
         }
         // self.decode_gamestate(&rlsim_gamestate)
     }
 
-    fn check_on_ground(&mut self) {
-        let new_iter = self.arena
-        .get_cars()
-        .into_iter()
-        .map(|id| self.arena.pin_mut().get_car(id).is_on_ground);
+    /// Gets the current RocketSim state of the arena
+    pub fn get_sim_state(&mut self) -> GameState_sim {
+        self.arena.pin_mut().get_game_state()
+    }
 
-        for (on_ground_arr, on_ground_new) in self.on_ground_vec.iter_mut().zip(new_iter) {
-            *on_ground_arr = *on_ground_arr || on_ground_new;
+    /// Gets the stats of each agent in the same order as the players of the gamestate (sorted by car id).
+    ///
+    /// Bump ids are converted to gamestate car ids (0 if there is no bump yet).
+    pub fn get_stats(&self) -> Vec<Stats> {
+        let mut stats_vec = self
+            .bookkeeping()
+            .stats
+            .iter()
+            .filter_map(|(id, stats)| self.car_id_map.get(id).map(|car_id| (*car_id, *stats)))
+            .collect::<Vec<_>>();
+        stats_vec.sort_unstable_by_key(|(car_id, _)| *car_id);
+
+        stats_vec
+            .into_iter()
+            .map(|(_, mut stats)| {
+                stats.last_bumped_by_id = self.car_id_map.get(&stats.last_bumped_by_id).map(|id| *id as u32).unwrap_or(0);
+                stats.last_car_bumped_id = self.car_id_map.get(&stats.last_car_bumped_id).map(|id| *id as u32).unwrap_or(0);
+                stats
+            })
+            .collect()
+    }
+
+    /// resets the step events to the current state of the arena, called before the first tick of a step
+    fn begin_step_events(&mut self) {
+        self.on_ground_vec.fill(false);
+        self.step_events.clear();
+        let stats_start = self.bookkeeping().stats.clone();
+        self.step_events.stats_start = stats_start;
+
+        for id in self.arena.get_cars() {
+            let car = self.arena.pin_mut().get_car(id);
+            let hit_tick = if car.ball_hit_info.is_valid { car.ball_hit_info.tick_count_when_hit } else { 0 };
+            self.step_events.hit_ticks.insert(id, hit_tick);
+            self.step_events.on_ground.insert(id, car.is_on_ground);
+        }
+        for i in 0..self.arena.num_pads() {
+            self.step_events.pad_cooldowns.push(self.arena.get_pad_state(i).cooldown);
+        }
+    }
+
+    /// accumulates the events of the tick that was just stepped (including on_ground)
+    fn track_tick(&mut self) {
+        let curr_tick = self.arena.get_tick_count();
+        let tracker = &mut self.step_events;
+
+        for (i, id) in self.arena.get_cars().into_iter().enumerate() {
+            let car = self.arena.pin_mut().get_car(id);
+            if let Some(on_ground_arr) = self.on_ground_vec.get_mut(i) {
+                *on_ground_arr = *on_ground_arr || car.is_on_ground;
+            }
+
+            let events = tracker.events.entry(id).or_default();
+            if car.ball_hit_info.is_valid {
+                let hit_tick = tracker.hit_ticks.entry(id).or_insert(0);
+                if *hit_tick != car.ball_hit_info.tick_count_when_hit {
+                    *hit_tick = car.ball_hit_info.tick_count_when_hit;
+                    events.touches += 1;
+                    if events.first_touch_tick == 0 {
+                        events.first_touch_tick = curr_tick;
+                    }
+                    events.last_touch_tick = curr_tick;
+                }
+            }
+
+            let was_on_ground = tracker.on_ground.insert(id, car.is_on_ground).unwrap_or(false);
+            events.touched_ground |= car.is_on_ground;
+            events.left_ground |= was_on_ground && !car.is_on_ground;
+        }
+
+        for (i, pad_cooldown) in tracker.pad_cooldowns.iter_mut().enumerate() {
+            let pad = self.arena.get_pad_state(i);
+            // a pickup restarts the cooldown, this also catches a pad that reactivated and was taken on the same tick.
+            // the car that picked up the pad is still the locked car after the tick
+            if pad.cooldown > *pad_cooldown && pad.prev_locked_car_id != 0 {
+                tracker.events.entry(pad.prev_locked_car_id).or_default().pads_collected += 1;
+            }
+            *pad_cooldown = pad.cooldown;
         }
     }
 
     /// clone actions before this to set prev_acts
-    pub fn step(&mut self, actions: Vec<Vec<f32>>, get_sim_state: bool) -> (GameState_rlgym, Option<Vec<GameState_sim>>) {
+    ///
+    /// Steps `tick_skip` ticks, which state(s) get decoded depends on the [StepMode] of the game config.
+    /// The sim states of every tick are returned if `get_sim_state` is true (used for rendering).
+    pub fn step(&mut self, actions: Vec<Vec<f32>>, get_sim_state: bool) -> Result<SimStep> {
         let mut acts = Vec::<(u32, CarControls)>::new();
 
         // package spectator ids with the corresponding action to send to arena
@@ -700,42 +1024,61 @@ impl RocketsimWrapper {
             ));
         }
 
-        self.arena.pin_mut().set_all_controls(&acts).unwrap();
-
-        self.on_ground_vec.fill(false);
-
-        self.arena.pin_mut().step(1);
+        // the cars are frozen by the countdown of a kickoff, the controls are set once it ends
+        let mut frozen = self.kickoff.countdown_ticks > 0;
+        if frozen {
+            let frozen_acts = acts.iter().map(|(id, _)| (*id, CarControls::default())).collect::<Vec<_>>();
+            self.arena.pin_mut().set_all_controls(&frozen_acts)?;
+        } else {
+            self.arena.pin_mut().set_all_controls(&acts)?;
+        }
 
-        self.check_on_ground();
+        self.begin_step_events();
 
-        let (gamestate_rlgym, gamestate_sim) = self.get_rlgym_gamestate(get_sim_state);
+        let ticks = self.tick_skip.max(1);
+        let mut state = None;
+        let mut sub_states = Vec::new();
+        let mut sim_states = if get_sim_state { Some(Vec::with_capacity(ticks)) } else { None };
 
-        // originally was here
-        // self.arena.pin_mut().step(self.tick_skip as i32);
+        for tick in 0..ticks {
+            self.arena.pin_mut().step(1);
+            self.park_void_ball();
+            if self.track_kickoff() && frozen {
+                self.arena.pin_mut().set_all_controls(&acts)?;
+                frozen = false;
+            }
+            self.track_tick();
 
-        // TODO: need to somehow extract ball hit information from every step probably
-        if get_sim_state {
-            let mut gamestate_sim_vec = Vec::new();
-            gamestate_sim_vec.push(gamestate_sim.unwrap());
-            
-            if self.tick_skip > 1 {
-                for _ in 0..self.tick_skip-1 {
-                    self.arena.pin_mut().step(1);
-                    self.check_on_ground();
-                    gamestate_sim_vec.push(self.arena.pin_mut().get_game_state());
-                }
+            let is_last = tick == ticks - 1;
+            let decode = match self.step_mode {
+                StepMode::Final => is_last,
+                StepMode::FirstTick => tick == 0,
+                StepMode::History => true,
+            };
+            if !decode && sim_states.is_none() {
+                continue;
             }
 
-            (gamestate_rlgym, Some(gamestate_sim_vec))
-        } else {
-            if self.tick_skip > 1 {
-                for _ in 0..self.tick_skip-1 {
-                    self.arena.pin_mut().step(1);
-                    self.check_on_ground();
+            let sim_state = self.arena.pin_mut().get_game_state();
+            if decode {
+                if self.step_mode == StepMode::History && !is_last {
+                    // touches are only committed on the returned state so that it still reports every touch of the step
+                    sub_states.push(self.decode_gamestate_inner(&sim_state, false)?);
+                } else {
+                    state = Some(self.decode_gamestate(&sim_state)?);
                 }
             }
-
-            (gamestate_rlgym, None)
+            if let Some(sim_states) = sim_states.as_mut() {
+                sim_states.push(sim_state);
+            }
         }
+
+        let state = match state {
+            Some(val) => val,
+            // only reachable if no ticks were stepped, which max(1) prevents
+            None => self.get_rlgym_gamestate(false)?.0,
+        };
+
+        Ok(SimStep { state, sub_states, sim_states })
     }
 }
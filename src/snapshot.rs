@@ -0,0 +1,160 @@
+//! Snapshots of the full state of a [Gym](crate::Gym), see [Gym::snapshot](crate::Gym::snapshot).
+
+use rand::rngs::SmallRng;
+use rocketsim_rs::GameState as GameState_sim;
+
+use crate::{
+    action_parsers::action_parser::ActionParser,
+    bots::{bot::Bot, opponent_pool::OpponentPool},
+    conditionals::terminal_condition::TerminalCondition,
+    envs::{game_match::{GameConfig, GameMatch}, match_mode::MatchClock},
+    error::{Error, Result},
+    gamestates::game_state::GameState,
+    obs_builders::obs_builder::ObsBuilder,
+    reward_functions::reward_fn::RewardFn,
+    sim_wrapper::wrapper::{RocketsimWrapper, Stats},
+    state_setters::state_setter::StateSetter,
+};
+
+/// Copies of the boxed components of a match
+struct Components {
+    reward_fn: Box<dyn RewardFn>,
+    terminal_condition: Box<dyn TerminalCondition>,
+    obs_builder: Vec<Box<dyn ObsBuilder>>,
+    action_parser: Box<dyn ActionParser>,
+    state_setter: Box<dyn StateSetter>,
+    bots: Vec<(usize, Box<dyn Bot>)>,
+}
+
+impl Components {
+    fn copy(
+        reward_fn: &dyn RewardFn,
+        terminal_condition: &dyn TerminalCondition,
+        obs_builder: &[Box<dyn ObsBuilder>],
+        action_parser: &dyn ActionParser,
+        state_setter: &dyn StateSetter,
+        bots: &[(usize, Box<dyn Bot>)],
+    ) -> Result<Self> {
+        let obs_builder = match obs_builder.iter().map(|builder| builder.snapshot()).collect::<Option<Vec<_>>>() {
+            Some(val) => val,
+            None => return Err(Error::Snapshot("observation builder")),
+        };
+        let bots = match bots.iter().map(|(slot, bot)| bot.snapshot().map(|bot| (*slot, bot))).collect::<Option<Vec<_>>>() {
+            Some(val) => val,
+            None => return Err(Error::Snapshot("bot")),
+        };
+
+        Ok(Components {
+            reward_fn: reward_fn.snapshot().ok_or(Error::Snapshot("reward function"))?,
+            terminal_condition: terminal_condition.snapshot().ok_or(Error::Snapshot("terminal condition"))?,
+            obs_builder,
+            action_parser: action_parser.snapshot().ok_or(Error::Snapshot("action parser"))?,
+            state_setter: state_setter.snapshot().ok_or(Error::Snapshot("state setter"))?,
+            bots,
+        })
+    }
+}
+
+/// Full state of a gym at one point in time.
+///
+/// This holds a deep copy of the arena (so the tick count and physics state are kept exactly), the scores and stats,
+/// the previous state and actions and copies of the reward function, terminal condition, observation builders, action parser,
+/// state setter and bots.
+/// The rng of the [TeamSizes](crate::envs::team_sizes::TeamSizes) and the [OpponentPool] (with its opponents, results and current pick)
+/// are copied as well.
+/// Restoring a snapshot and stepping with the same actions gives the same results as stepping from when the snapshot was taken.
+///
+/// The renderer is not affected.
+/// A snapshot can be restored any amount of times.
+pub struct Snapshot {
+    /// RocketSim state when the snapshot was taken
+    pub sim_state: GameState_sim,
+    /// gym state when the snapshot was taken
+    pub state: GameState,
+    /// stats of each player when the snapshot was taken, in the same order as `state.players`
+    pub stats: Vec<Stats>,
+    sim_wrapper: RocketsimWrapper,
+    game_config: GameConfig,
+    agents: usize,
+    prev_actions: Vec<Vec<f32>>,
+    spectator_ids: Vec<i32>,
+    initial_score: i32,
+    match_clock: Option<MatchClock>,
+    team_sizes_rng: Option<SmallRng>,
+    opponent_pool: Option<OpponentPool>,
+    opponent_obs: Vec<Vec<f32>>,
+    components: Components,
+}
+
+fn copy_pool(pool: &Option<OpponentPool>) -> Result<Option<OpponentPool>> {
+    match pool {
+        Some(pool) => pool.snapshot().map(Some).ok_or(Error::Snapshot("opponent")),
+        None => Ok(None),
+    }
+}
+
+impl Snapshot {
+    pub(crate) fn new(game_match: &GameMatch, prev_state: &GameState) -> Result<Self> {
+        let components = Components::copy(
+            game_match._reward_fn.as_ref(),
+            game_match._terminal_condition.as_ref(),
+            &game_match._obs_builder,
+            game_match._action_parser.as_ref(),
+            game_match._state_setter.as_ref(),
+            &game_match.bots,
+        )?;
+        let opponent_pool = copy_pool(&game_match.opponent_pool)?;
+        let mut sim_wrapper = game_match.sim_wrapper.clone();
+
+        Ok(Snapshot {
+            sim_state: sim_wrapper.get_sim_state(),
+            state: prev_state.clone(),
+            stats: sim_wrapper.get_stats(),
+            sim_wrapper,
+            game_config: game_match.game_config,
+            agents: game_match.agents,
+            prev_actions: game_match._prev_actions.clone(),
+            spectator_ids: game_match._spectator_ids.clone(),
+            initial_score: game_match._initial_score,
+            match_clock: game_match.match_clock.clone(),
+            team_sizes_rng: game_match.team_sizes.as_ref().map(|team_sizes| team_sizes.rng().clone()),
+            opponent_pool,
+            opponent_obs: game_match.opponent_obs.clone(),
+            components,
+        })
+    }
+
+    pub(crate) fn restore(&self, game_match: &mut GameMatch) -> Result<()> {
+        // copy again so that the snapshot can be restored more than once
+        let components = Components::copy(
+            self.components.reward_fn.as_ref(),
+            self.components.terminal_condition.as_ref(),
+            &self.components.obs_builder,
+            self.components.action_parser.as_ref(),
+            self.components.state_setter.as_ref(),
+            &self.components.bots,
+        )?;
+        let opponent_pool = copy_pool(&self.opponent_pool)?;
+
+        game_match.sim_wrapper = self.sim_wrapper.clone();
+        game_match.game_config = self.game_config;
+        game_match.agents = self.agents;
+        game_match._prev_actions = self.prev_actions.clone();
+        game_match._spectator_ids = self.spectator_ids.clone();
+        game_match._initial_score = self.initial_score;
+        game_match.match_clock = self.match_clock.clone();
+        if let (Some(team_sizes), Some(rng)) = (game_match.team_sizes.as_mut(), &self.team_sizes_rng) {
+            team_sizes.set_rng(rng.clone());
+        }
+        game_match.opponent_pool = opponent_pool;
+        game_match.opponent_obs = self.opponent_obs.clone();
+        game_match._reward_fn = components.reward_fn;
+        game_match._terminal_condition = components.terminal_condition;
+        game_match._obs_builder = components.obs_builder;
+        game_match._action_parser = components.action_parser;
+        game_match._state_setter = components.state_setter;
+        game_match.bots = components.bots;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+//! Descriptions of the observation and action spaces, see [ObsBuilder::get_obs_space](crate::ObsBuilder::get_obs_space)
+//! and [ActionParser::get_action_space](crate::ActionParser::get_action_space).
+//!
+//! These follow the Gymnasium spaces so that the Python side can build them without hardcoding any sizes.
+
+use serde::{Deserialize, Serialize};
+
+/// Type of the values of a [Space::Box]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Dtype {
+    #[default]
+    Float32,
+    Float64,
+    Int32,
+    Int64,
+}
+
+/// A Gymnasium space
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Space {
+    /// Values between `low` and `high` (which can be infinite) in an array of `shape`
+    Box { low: f32, high: f32, shape: Vec<usize>, dtype: Dtype },
+    /// One value in `0..n`
+    Discrete(usize),
+    /// One value in `0..n` for each of the entries
+    MultiDiscrete(Vec<usize>),
+    Tuple(Vec<Space>),
+    /// Named spaces, in order
+    Dict(Vec<(String, Space)>),
+}
+
+impl Default for Space {
+    /// An empty tuple, which holds no values
+    fn default() -> Self {
+        Space::Tuple(Vec::new())
+    }
+}
+
+impl Space {
+    /// Box of `f32` values with one dimension
+    pub fn box_f32(low: f32, high: f32, len: usize) -> Self {
+        Space::Box { low, high, shape: vec![len], dtype: Dtype::Float32 }
+    }
+
+    /// Amount of values in a flattened sample of this space, this is the length of an observation or an action
+    pub fn flat_len(&self) -> usize {
+        match self {
+            Space::Box { shape, .. } => shape.iter().product(),
+            Space::Discrete(_) => 1,
+            Space::MultiDiscrete(nvec) => nvec.len(),
+            Space::Tuple(spaces) => spaces.iter().map(|space| space.flat_len()).sum(),
+            Space::Dict(spaces) => spaces.iter().map(|(_, space)| space.flat_len()).sum(),
+        }
+    }
+
+    /// Flattened sample of only zeros (the first choice of discrete spaces), which is in the space
+    /// unless the bounds of a box do not include 0
+    pub fn zeros(&self) -> Vec<f32> {
+        vec![0.; self.flat_len()]
+    }
+
+    /// true if the flattened `values` are a sample of this space
+    pub fn contains(&self, values: &[f32]) -> bool {
+        if values.len() != self.flat_len() {
+            return false;
+        }
+        match self {
+            Space::Box { low, high, dtype, .. } => values.iter().all(|value| {
+                let integer = matches!(dtype, Dtype::Int32 | Dtype::Int64);
+                *value >= *low && *value <= *high && (!integer || value.fract() == 0.)
+            }),
+            Space::Discrete(n) => is_choice(values[0], *n),
+            Space::MultiDiscrete(nvec) => values.iter().zip(nvec).all(|(value, n)| is_choice(*value, *n)),
+            Space::Tuple(spaces) => contains_all(spaces.iter(), values),
+            Space::Dict(spaces) => contains_all(spaces.iter().map(|(_, space)| space), values),
+        }
+    }
+}
+
+fn is_choice(value: f32, n: usize) -> bool {
+    value >= 0. && value.fract() == 0. && (value as usize) < n
+}
+
+fn contains_all<'a>(spaces: impl Iterator<Item = &'a Space>, mut values: &[f32]) -> bool {
+    for space in spaces {
+        let (space_values, rest) = values.split_at(space.flat_len());
+        if !space.contains(space_values) {
+            return false;
+        }
+        values = rest;
+    }
+    true
+}
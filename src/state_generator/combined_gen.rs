@@ -1,4 +1,4 @@
-use crate::{gamestates::{game_state::GameState, player_data::PlayerData}, state_setters::{random_state::RandomState, state_setter::StateSetter, default_state::DefaultState, wrappers::state_wrapper::StateWrapper}};
+use crate::{error::Result, gamestates::{game_state::GameState, player_data::PlayerData}, state_setters::{random_state::RandomState, state_setter::StateSetter, default_state::DefaultState, wrappers::state_wrapper::StateWrapper}};
 
 use super::state_modifier::StateModifier;
 
@@ -23,10 +23,10 @@ impl CombinedStateGenerator {
         }
     }
 
-    pub fn generate_state(&mut self, blue_count: usize, orange_count: usize) -> GameState {
+    pub fn generate_state(&mut self, blue_count: usize, orange_count: usize) -> Result<GameState> {
         // let mut game_state = GameState::default();
         let mut wrapper = StateWrapper::new(Some(blue_count), Some(orange_count), None);
-        self.state_setter.reset(&mut wrapper);
+        self.state_setter.reset(&mut wrapper)?;
         
         let mut state_cars = Vec::<PlayerData>::new();
         for car_wrapper in wrapper.cars {
@@ -68,7 +68,7 @@ impl CombinedStateGenerator {
             player_state.inverted_car_data.quaternion = player_state.car_data.quaternion.invert();
             player_state.inverted_car_data.euler_angles = player_state.inverted_car_data.quaternion.quat_to_euler();
         }
-        state
+        Ok(state)
     }
     
     pub fn update_modifiers(&mut self, modifiers: Vec<Box<dyn StateModifier>>) {
@@ -1,6 +1,6 @@
 use crate::gamestates::game_state::GameState;
 
 /// default trait type for the modifiers that are used in things such as the CombinedStateGenerator
-pub trait StateModifier {
+pub trait StateModifier: Send {
     fn modify_state(&mut self, state: &mut GameState);
 }
\ No newline at end of file
@@ -1,11 +1,20 @@
 use rand::{rngs::SmallRng, rng, Rng, SeedableRng};
 use std::f32::consts::PI;
 
-use crate::gamestates::physics_object::{Position, Velocity, EulerAngle};
+use crate::{
+    common_values::{
+        HEATSEEKER_BALL_START, HEATSEEKER_BALL_START_VEL, HEATSEEKER_KICKOFF_SPAWNS, HOOPS_BALL_RADIUS, HOOPS_KICKOFF_BALL_VEL_Z,
+        HOOPS_KICKOFF_SPAWNS, VOID_BALL_POSITION,
+    },
+    envs::game_match::GameMode,
+    error::{Error, Result},
+    gamestates::physics_object::{Position, Velocity, EulerAngle},
+};
 
 use super::{state_setter::StateSetter, wrappers::state_wrapper::StateWrapper};
 
 /// State setter that creates a default Rocket League state
+#[derive(Clone)]
 pub struct DefaultState {
     spawn_blue_pos: Vec<Vec<f32>>,
     spawn_blue_yaw: Vec<f32>,
@@ -41,6 +50,37 @@ impl DefaultState {
             rng,
         }
     }
+
+    /// Amount of kickoff spots per team in `game_mode`
+    fn spawn_count(&self, game_mode: GameMode) -> usize {
+        match game_mode {
+            GameMode::Hoops => HOOPS_KICKOFF_SPAWNS.len(),
+            GameMode::Heatseeker => HEATSEEKER_KICKOFF_SPAWNS.len(),
+            GameMode::Soccar | GameMode::Snowday | GameMode::TheVoid => self.spawn_blue_pos.len(),
+        }
+    }
+
+    /// Position and yaw of kickoff spot `index` of a team in `game_mode`, the modes on the soccar field use the soccar spots
+    fn spawn(&self, game_mode: GameMode, blue: bool, index: usize) -> (Vec<f32>, f32) {
+        let spawns: &[[f32; 3]] = match game_mode {
+            GameMode::Hoops => &HOOPS_KICKOFF_SPAWNS,
+            GameMode::Heatseeker => &HEATSEEKER_KICKOFF_SPAWNS,
+            GameMode::Soccar | GameMode::Snowday | GameMode::TheVoid => {
+                return if blue {
+                    (self.spawn_blue_pos[index].clone(), self.spawn_blue_yaw[index])
+                } else {
+                    (self.spawn_orange_pos[index].clone(), self.spawn_orange_yaw[index])
+                }
+            }
+        };
+        let [x, y, yaw] = spawns[index];
+        // orange is mirrored through the center of the field
+        if blue {
+            (vec![x, y, 17.], yaw)
+        } else {
+            (vec![-x, -y, 17.], yaw - PI)
+        }
+    }
 }
 
 impl Default for DefaultState {
@@ -50,8 +90,19 @@ impl Default for DefaultState {
 }
 
 impl StateSetter for DefaultState {
-    fn reset(&mut self, state_wrapper: &mut StateWrapper) {
-        let mut spawn_inds = [0, 1, 2, 3, 4];
+    /// Returns [Error::Config] if a team has more cars than there are kickoff spots in the game mode
+    fn reset(&mut self, state_wrapper: &mut StateWrapper) -> Result<()> {
+        let spawn_count = self.spawn_count(state_wrapper.game_mode);
+        let blue_cars = state_wrapper.cars.iter().filter(|car| car.get_team_num() == 0).count();
+        let team_size = blue_cars.max(state_wrapper.cars.len() - blue_cars);
+        if team_size > spawn_count {
+            return Err(Error::Config(format!(
+                "DefaultState has {spawn_count} kickoff spots per team in {:?} but a team has {team_size} cars",
+                state_wrapper.game_mode
+            )));
+        }
+
+        let mut spawn_inds = (0..spawn_count).collect::<Vec<_>>();
         // let mut rng = rand::thread_rng();
 
         // this is to try to rearrange the order in a randomized way
@@ -60,18 +111,13 @@ impl StateSetter for DefaultState {
         let mut blue_count = 0;
         let mut orange_count = 0;
         for car in &mut state_wrapper.cars {
-            let pos;
-            let yaw: f32;
-
-            if car.get_team_num() == 0 {
-                pos = self.spawn_blue_pos[spawn_inds[blue_count]].clone();
-                yaw = self.spawn_blue_yaw[spawn_inds[blue_count]];
+            let (pos, yaw) = if car.get_team_num() == 0 {
                 blue_count += 1;
+                self.spawn(state_wrapper.game_mode, true, spawn_inds[blue_count - 1])
             } else {
-                pos = self.spawn_orange_pos[spawn_inds[orange_count]].clone();
-                yaw = self.spawn_orange_yaw[spawn_inds[orange_count]];
                 orange_count += 1;
-            }
+                self.spawn(state_wrapper.game_mode, false, spawn_inds[orange_count - 1])
+            };
 
             car.set_pos(Some(pos[0]), Some(pos[1]), Some(pos[2]));
             car.set_lin_vel(Some(0.), Some(0.), Some(0.));
@@ -83,14 +129,37 @@ impl StateSetter for DefaultState {
         state_wrapper.ball.position = Position { x: 0., y: 0., z: 91.25 };
         state_wrapper.ball.linear_velocity = Velocity { x: 0., y: 0., z: 0. };
         state_wrapper.ball.angular_velocity = Velocity { x: 0., y: 0., z: 0. };
+        match state_wrapper.game_mode {
+            GameMode::Hoops => {
+                state_wrapper.ball.position.z = HOOPS_BALL_RADIUS;
+                state_wrapper.ball.linear_velocity.z = HOOPS_KICKOFF_BALL_VEL_Z;
+            }
+            GameMode::Heatseeker => {
+                // the ball starts towards either side
+                let side = if self.rng.random_bool(0.5) { 1. } else { -1. };
+                state_wrapper.ball.position = Position { y: HEATSEEKER_BALL_START.y * side, ..HEATSEEKER_BALL_START };
+                state_wrapper.ball.linear_velocity = Velocity { y: HEATSEEKER_BALL_START_VEL.y * side, ..HEATSEEKER_BALL_START_VEL };
+            }
+            GameMode::TheVoid => state_wrapper.ball.position = VOID_BALL_POSITION,
+            GameMode::Soccar | GameMode::Snowday => (),
+        }
+        // the void has no ball in play to kick off
+        state_wrapper.kickoff = state_wrapper.game_mode.has_goals();
+
+        Ok(())
     }
 
     fn set_seed(&mut self, seed: u64) {
         self.rng = SmallRng::seed_from_u64(seed);
     }
+
+    fn snapshot(&self) -> Option<Box<dyn StateSetter>> {
+        Some(Box::new(self.clone()))
+    }
 }
 
 // specific state tester for testing states from RLBot
+#[derive(Clone)]
 pub struct ExactStateTester {}
 
 impl ExactStateTester {
@@ -106,7 +175,7 @@ impl Default for ExactStateTester {
 }
 
 impl StateSetter for ExactStateTester {
-    fn reset(&mut self, state_wrapper: &mut StateWrapper) {
+    fn reset(&mut self, state_wrapper: &mut StateWrapper) -> Result<()> {
         // let spawn_inds = vec![0, 1, 2, 3, 4];
         // let mut rng = rand::thread_rng();
         // spawn_inds.sort_by_key(|x| rng.gen::<usize>());
@@ -128,10 +197,17 @@ impl StateSetter for ExactStateTester {
         state_wrapper.ball.position = Position { x: 3987.0397, y: -707.8599, z: 187.4899 };
         state_wrapper.ball.linear_velocity = Velocity { x: 791.3609, y: -270.9309, z: 1775.5809 };
         state_wrapper.ball.angular_velocity = Velocity { x: 0.6609, y: 5.9448, z: 0.4714 };
+
+        Ok(())
+    }
+
+    fn snapshot(&self) -> Option<Box<dyn StateSetter>> {
+        Some(Box::new(self.clone()))
     }
 }
 
 // this has no randomization in the spawn indices for testing purposes
+#[derive(Clone)]
 pub struct DefaultStateTester {
     spawn_blue_pos: Vec<Vec<f32>>,
     spawn_blue_yaw: Vec<f32>,
@@ -169,7 +245,7 @@ impl Default for DefaultStateTester {
 }
 
 impl StateSetter for DefaultStateTester {
-    fn reset(&mut self, state_wrapper: &mut StateWrapper) {
+    fn reset(&mut self, state_wrapper: &mut StateWrapper) -> Result<()> {
         let spawn_inds = [0, 1, 2, 3, 4];
         // let mut rng = rand::thread_rng();
         // spawn_inds.sort_by_key(|x| rng.gen::<usize>());
@@ -198,10 +274,17 @@ impl StateSetter for DefaultStateTester {
         state_wrapper.ball.position = Position { x: 0., y: 0., z: 91.25 };
         state_wrapper.ball.linear_velocity = Velocity { x: 0., y: 0., z: 0. };
         state_wrapper.ball.angular_velocity = Velocity { x: 0., y: 0., z: 0. };
+
+        Ok(())
+    }
+
+    fn snapshot(&self) -> Option<Box<dyn StateSetter>> {
+        Some(Box::new(self.clone()))
     }
 }
 
 // this has no randomization in the spawn indices for testing purposes
+#[derive(Clone)]
 pub struct DefaultStateTesterPitched {
     spawn_blue_pos: Vec<Vec<f32>>,
     spawn_blue_yaw: Vec<f32>,
@@ -239,7 +322,7 @@ impl Default for DefaultStateTesterPitched {
 }
 
 impl StateSetter for DefaultStateTesterPitched {
-    fn reset(&mut self, state_wrapper: &mut StateWrapper) {
+    fn reset(&mut self, state_wrapper: &mut StateWrapper) -> Result<()> {
         let spawn_inds = [0, 1, 2, 3, 4];
         // let mut rng = rand::thread_rng();
         // spawn_inds.sort_by_key(|x| rng.gen::<usize>());
@@ -268,10 +351,17 @@ impl StateSetter for DefaultStateTesterPitched {
         state_wrapper.ball.position = Position { x: 0., y: 0., z: 91.25 };
         state_wrapper.ball.linear_velocity = Velocity { x: 0., y: 0., z: 0. };
         state_wrapper.ball.angular_velocity = Velocity { x: 0., y: 0., z: 0. };
+
+        Ok(())
+    }
+
+    fn snapshot(&self) -> Option<Box<dyn StateSetter>> {
+        Some(Box::new(self.clone()))
     }
 }
 
 /// for testing blue goal rewards and such
+#[derive(Clone)]
 pub struct BlueGoalStateTester {
     spawn_blue_pos: Vec<Vec<f32>>,
     spawn_blue_yaw: Vec<f32>,
@@ -309,7 +399,7 @@ impl Default for BlueGoalStateTester {
 }
 
 impl StateSetter for BlueGoalStateTester {
-    fn reset(&mut self, state_wrapper: &mut StateWrapper) {
+    fn reset(&mut self, state_wrapper: &mut StateWrapper) -> Result<()> {
         let spawn_inds = [0, 1, 2, 3, 4];
         // let mut rng = rand::thread_rng();
         // spawn_inds.sort_by_key(|x| rng.gen::<usize>());
@@ -338,10 +428,17 @@ impl StateSetter for BlueGoalStateTester {
         state_wrapper.ball.position = Position { x: 0., y: 4500., z: 91.25 };
         state_wrapper.ball.linear_velocity = Velocity { x: 0., y: 1000., z: 0. };
         state_wrapper.ball.angular_velocity = Velocity { x: 0., y: 0., z: 0. };
+
+        Ok(())
+    }
+
+    fn snapshot(&self) -> Option<Box<dyn StateSetter>> {
+        Some(Box::new(self.clone()))
     }
 }
 
 /// for testing orange goal rewards and such
+#[derive(Clone)]
 pub struct OrangeGoalStateTester {
     spawn_blue_pos: Vec<Vec<f32>>,
     spawn_blue_yaw: Vec<f32>,
@@ -379,7 +476,7 @@ impl Default for OrangeGoalStateTester {
 }
 
 impl StateSetter for OrangeGoalStateTester {
-    fn reset(&mut self, state_wrapper: &mut StateWrapper) {
+    fn reset(&mut self, state_wrapper: &mut StateWrapper) -> Result<()> {
         let spawn_inds = [0, 1, 2, 3, 4];
         // let mut rng = rand::thread_rng();
         // spawn_inds.sort_by_key(|x| rng.gen::<usize>());
@@ -408,10 +505,17 @@ impl StateSetter for OrangeGoalStateTester {
         state_wrapper.ball.position = Position { x: 0., y: -4900., z: 91.25 };
         state_wrapper.ball.linear_velocity = Velocity { x: 0., y: -1000., z: 0. };
         state_wrapper.ball.angular_velocity = Velocity { x: 0., y: 0., z: 0. };
+
+        Ok(())
+    }
+
+    fn snapshot(&self) -> Option<Box<dyn StateSetter>> {
+        Some(Box::new(self.clone()))
     }
 }
 
 /// for testing orange goal rewards and such
+#[derive(Clone)]
 pub struct AgentBallHitStateTester {
     spawn_blue_pos: Vec<Vec<f32>>,
     // spawn_blue_yaw: Vec<f64>,
@@ -449,7 +553,7 @@ impl Default for AgentBallHitStateTester {
 }
 
 impl StateSetter for AgentBallHitStateTester {
-    fn reset(&mut self, state_wrapper: &mut StateWrapper) {
+    fn reset(&mut self, state_wrapper: &mut StateWrapper) -> Result<()> {
         let spawn_inds = [0, 1, 2, 3, 4];
         // let mut rng = rand::thread_rng();
         // spawn_inds.sort_by_key(|x| rng.gen::<usize>());
@@ -478,10 +582,17 @@ impl StateSetter for AgentBallHitStateTester {
         state_wrapper.ball.position = Position { x: 0., y: 0., z: 91.25 };
         state_wrapper.ball.linear_velocity = Velocity { x: 0., y: -1000., z: 0. };
         state_wrapper.ball.angular_velocity = Velocity { x: 0., y: 0., z: 0. };
+
+        Ok(())
+    }
+
+    fn snapshot(&self) -> Option<Box<dyn StateSetter>> {
+        Some(Box::new(self.clone()))
     }
 }
 
 /// for testing orange goal rewards and such
+#[derive(Clone)]
 pub struct DemoStateTester {
     // spawn_blue_pos: Vec<Vec<f32>>,
     // spawn_blue_yaw: Vec<f64>,
@@ -519,7 +630,7 @@ impl Default for DemoStateTester {
 }
 
 impl StateSetter for DemoStateTester {
-    fn reset(&mut self, state_wrapper: &mut StateWrapper) {
+    fn reset(&mut self, state_wrapper: &mut StateWrapper) -> Result<()> {
         // let spawn_inds = vec![0, 1, 2, 3, 4];
         // let mut rng = rand::thread_rng();
         // spawn_inds.sort_by_key(|x| rng.gen::<usize>());
@@ -568,6 +679,13 @@ impl StateSetter for DemoStateTester {
         state_wrapper.ball.position = Position { x: 4000., y: 0., z: 91.25 };
         state_wrapper.ball.linear_velocity = Velocity { x: 0., y: 0., z: 0. };
         state_wrapper.ball.angular_velocity = Velocity { x: 0., y: 0., z: 0. };
+
+        Ok(())
+    }
+
+    fn snapshot(&self) -> Option<Box<dyn StateSetter>> {
+        Some(Box::new(self.clone()))
     }
 }
 
+
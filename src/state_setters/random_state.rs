@@ -2,6 +2,7 @@ use rand::prelude::*;
 use rand::rng;
 use std::f32::consts::PI;
 
+use crate::error::Result;
 use crate::math::rand_vec3;
 
 use super::{state_setter::StateSetter, wrappers::state_wrapper::StateWrapper};
@@ -15,6 +16,7 @@ const YAW_MAX: f32 = PI;
 const ROLL_MAX: f32 = PI;
 
 /// Random state setter that makes random position/velocity/rotation values for each car and for the ball (within reason, eg. below max speeds)
+#[derive(Clone)]
 pub struct RandomState {
     ball_rand_speed: bool,
     cars_rand_speed: bool,
@@ -111,12 +113,18 @@ impl RandomState {
 }
 
 impl StateSetter for RandomState {
-    fn reset(&mut self, state_wrapper: &mut StateWrapper) {
+    fn reset(&mut self, state_wrapper: &mut StateWrapper) -> Result<()> {
         self._reset_ball_random(state_wrapper, self.ball_rand_speed);
         self._reset_cars_random(state_wrapper, self.cars_on_ground, self.cars_rand_speed);
+
+        Ok(())
     }
 
     fn set_seed(&mut self, seed: u64) {
         self.rng = SmallRng::seed_from_u64(seed);
     }
+
+    fn snapshot(&self) -> Option<Box<dyn StateSetter>> {
+        Some(Box::new(self.clone()))
+    }
 }
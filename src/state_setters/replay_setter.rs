@@ -6,6 +6,7 @@ use ndarray_npy::ViewNpyExt;
 use rand::rngs::SmallRng;
 use rand::Rng;
 use rand::SeedableRng;
+use crate::error::{Error, Result};
 use crate::state_setters::state_setter::StateSetter;
 use crate::state_setters::wrappers::state_wrapper::StateWrapper;
 
@@ -70,15 +71,32 @@ impl ReplaySetter<'_> {
 }
 
 impl StateSetter for ReplaySetter<'_> {
-    fn reset(&mut self, state_wrapper: &mut StateWrapper) {
+    fn reset(&mut self, state_wrapper: &mut StateWrapper) -> Result<()> {
         let index = self.rng.random_range(0..self.states.dim().0);
         let binding = self.states.index_axis(Axis(0), index);
-        let state = binding.as_slice().unwrap();
+        let state = match binding.as_slice() {
+            Some(val) => val,
+            None => return Err(Error::StateSetter("replay state was not contiguous in memory".to_string())),
+        };
+        let required_len = state_wrapper.cars.len() * 13 + 9;
+        if state.len() < required_len {
+            let state_len = state.len();
+            let car_count = state_wrapper.cars.len();
+            return Err(Error::StateSetter(format!(
+                "replay state was of length {state_len} but length {required_len} is required for {car_count} cars"
+            )));
+        }
         Self::set_ball(state_wrapper, state);
         self.set_cars(state_wrapper, state.to_owned());
         if self.random_pads{
             self.set_pads(state_wrapper);
         }
+
+        Ok(())
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
     }
 }
 
@@ -95,16 +113,16 @@ mod tests{
     fn replay_setter_load_threes(){
         rocketsim_rs::init(None, false);
         let gameconfig = crate::envs::game_match::GameConfig{team_size: 3, spawn_opponents: true,
-             gravity: 1., boost_consumption: 1., tick_skip: 8, car_config: CarConfig::octane(),};
+             gravity: 1., boost_consumption: 1., tick_skip: 8, car_config: CarConfig::octane(), car_bodies: crate::envs::car_bodies::CarBodies::Same, step_mode: crate::envs::game_match::StepMode::Final, game_mode: crate::envs::game_match::GameMode::Soccar, mutators: crate::envs::mutators::Mutators::default(), kickoff_phase: None,};
         let mut sim = RocketsimWrapper::new(gameconfig);
-        let (state, _) = sim.get_rlgym_gamestate(false);
+        let (state, _) = sim.get_rlgym_gamestate(false).unwrap();
         let array_to_write = make_test_array();
         let path = "./tests/test_files_npy/test3.npy";
         write_npy(path, &array_to_write).unwrap();
         let mut setter = ReplaySetter::new("./tests/test_files_npy/test3.npy", Some(false), Some(false));
         let mut wrapper = setter.build_wrapper(3, true, Some(&state));
-        setter.reset(&mut wrapper);
-        let (state, _) = sim.set_state(wrapper, false);
+        setter.reset(&mut wrapper).unwrap();
+        let (state, _) = sim.set_state(wrapper, false).unwrap();
         assert_eq!(state.ball.position.x, 2.);
         assert_eq!(state.players[0].car_data.position.x, 3.);
         assert_eq!(state.players[5].boost_amount, 4.);
@@ -115,9 +133,9 @@ mod tests{
     fn replay_setter_load_ones(){
         rocketsim_rs::init(None, false);
         let gameconfig = crate::envs::game_match::GameConfig{team_size: 1, spawn_opponents: true,
-            gravity: 1., boost_consumption: 1., tick_skip: 8, car_config: CarConfig::octane(),};
+            gravity: 1., boost_consumption: 1., tick_skip: 8, car_config: CarConfig::octane(), car_bodies: crate::envs::car_bodies::CarBodies::Same, step_mode: crate::envs::game_match::StepMode::Final, game_mode: crate::envs::game_match::GameMode::Soccar, mutators: crate::envs::mutators::Mutators::default(), kickoff_phase: None,};
         let mut sim = RocketsimWrapper::new(gameconfig);
-        let (state, _) = sim.get_rlgym_gamestate(false);
+        let (state, _) = sim.get_rlgym_gamestate(false).unwrap();
         let pos_ball_0_x = 0;
         let pos_car_0_x = 9;
         let pos_car_1_boost = pos_car_0_x + (13 * 2) - 1;
@@ -131,8 +149,8 @@ mod tests{
         write_npy(path, &array_to_write).unwrap();
         let mut setter = ReplaySetter::new(path, Some(false), Some(false));
         let mut wrapper = setter.build_wrapper(1, true, Some(&state));
-        setter.reset(&mut wrapper);
-        let (state, _) = sim.set_state(wrapper, false);
+        setter.reset(&mut wrapper).unwrap();
+        let (state, _) = sim.set_state(wrapper, false).unwrap();
         assert_eq!(state.ball.position.x, 2.);
         assert_eq!(state.players[0].car_data.position.x, 3.);
         assert_eq!(state.players[1].boost_amount, 4.);
@@ -152,16 +170,16 @@ mod tests{
     fn replay_setter_random_boost(){
         rocketsim_rs::init(None, false);
         let gameconfig = crate::envs::game_match::GameConfig{team_size: 3, spawn_opponents: true,
-             gravity: 1., boost_consumption: 1., tick_skip: 8, car_config: CarConfig::octane(),};
+             gravity: 1., boost_consumption: 1., tick_skip: 8, car_config: CarConfig::octane(), car_bodies: crate::envs::car_bodies::CarBodies::Same, step_mode: crate::envs::game_match::StepMode::Final, game_mode: crate::envs::game_match::GameMode::Soccar, mutators: crate::envs::mutators::Mutators::default(), kickoff_phase: None,};
         let mut sim = RocketsimWrapper::new(gameconfig);
-        let (state, _) = sim.get_rlgym_gamestate(false);
+        let (state, _) = sim.get_rlgym_gamestate(false).unwrap();
         let array_to_write = make_test_array();
         let path = "./tests/test_files_npy/test_boost.npy";
         write_npy(path, &array_to_write).unwrap();
         let mut setter = ReplaySetter::new(path, Some(true), Some(false));
         let mut wrapper = setter.build_wrapper(3, true, Some(&state));
-        setter.reset(&mut wrapper);
-        let (state, _) = sim.set_state(wrapper, false);
+        setter.reset(&mut wrapper).unwrap();
+        let (state, _) = sim.set_state(wrapper, false).unwrap();
         assert_ne!(state.players[5].boost_amount, 4.);
     }
 
@@ -169,21 +187,21 @@ mod tests{
     fn replay_setter_random_pads(){
         rocketsim_rs::init(None, false);
         let gameconfig = crate::envs::game_match::GameConfig{team_size: 3, spawn_opponents: true,
-             gravity: 1., boost_consumption: 1., tick_skip: 8, car_config: CarConfig::octane(),};
+             gravity: 1., boost_consumption: 1., tick_skip: 8, car_config: CarConfig::octane(), car_bodies: crate::envs::car_bodies::CarBodies::Same, step_mode: crate::envs::game_match::StepMode::Final, game_mode: crate::envs::game_match::GameMode::Soccar, mutators: crate::envs::mutators::Mutators::default(), kickoff_phase: None,};
         let mut sim = RocketsimWrapper::new(gameconfig);
-        let (state, _) = sim.get_rlgym_gamestate(false);
+        let (state, _) = sim.get_rlgym_gamestate(false).unwrap();
         let array_to_write = make_test_array();
         let path = "./tests/test_files_npy/test_pads.npy";
         write_npy(path, &array_to_write).unwrap();
         let mut setter = ReplaySetter::new(path, Some(false), Some(true));
         let mut wrapper = setter.build_wrapper(3, true, Some(&state));
-        setter.reset(&mut wrapper);
+        setter.reset(&mut wrapper).unwrap();
         for pad in wrapper.pads.iter(){
             if !pad.is_active{
                 assert!(pad.cooldown == 4. || pad.cooldown == 10.);
             }
         }
-        let (state, _) = sim.set_state(wrapper, false);
+        let (state, _) = sim.set_state(wrapper, false).unwrap();
         //it's technically possible for this to fail if all 34 pads roll true, but that seems unlikely, but just try it again
         assert!(!state.boost_pads.iter().all(|x| x.state.is_active != false));  
     }
@@ -1,11 +1,16 @@
-use crate::gamestates::game_state::GameState;
+use crate::{error::Result, gamestates::game_state::GameState};
 
 use super::wrappers::state_wrapper::StateWrapper;
 
-pub trait StateSetter {
+pub trait StateSetter: Send {
     fn build_wrapper(&mut self, max_team_size: usize, spawn_opponents: bool, game_state: Option<&GameState>) -> StateWrapper {
         StateWrapper::new(Some(max_team_size), if spawn_opponents { Some(max_team_size) } else { Some(0) }, game_state)
     }
-    fn reset(&mut self, state_wrapper: &mut StateWrapper);
+    fn reset(&mut self, state_wrapper: &mut StateWrapper) -> Result<()>;
     fn set_seed(&mut self, _seed: u64) {}
+    /// Copy of this state setter for [Gym::snapshot](crate::Gym::snapshot), the rng should be copied too so that
+    /// resets after a restore are the same. `None` if unsupported.
+    fn snapshot(&self) -> Option<Box<dyn StateSetter>> {
+        None
+    }
 }
@@ -1,5 +1,7 @@
 use rand::{distr::{weighted::WeightedIndex, Distribution}, rng, Rng, rngs::SmallRng, SeedableRng};
 
+use crate::{error::Result, seeding::derive_seed};
+
 use super::{state_setter::StateSetter, wrappers::state_wrapper::StateWrapper};
 
 /// weighted state setter that uses a rand distribution to poll for a choice
@@ -23,15 +25,20 @@ impl WeightedSampleSetter {
 }
 
 impl StateSetter for WeightedSampleSetter {
-    fn reset(&mut self, state_wrapper: &mut StateWrapper) {
+    fn reset(&mut self, state_wrapper: &mut StateWrapper) -> Result<()> {
         let choice = self.distribution.sample(&mut self.rng);
-        self.state_setters[choice].reset(state_wrapper);
+        self.state_setters[choice].reset(state_wrapper)
     }
 
     fn set_seed(&mut self, seed: u64) {
         self.rng = SmallRng::seed_from_u64(seed);
-        for state_setter in &mut self.state_setters {
-            state_setter.set_seed(seed);
+        for (i, state_setter) in self.state_setters.iter_mut().enumerate() {
+            state_setter.set_seed(derive_seed(seed, i as u64));
         }
     }
-}
\ No newline at end of file
+
+    fn snapshot(&self) -> Option<Box<dyn StateSetter>> {
+        let state_setters = self.state_setters.iter().map(|state_setter| state_setter.snapshot()).collect::<Option<Vec<_>>>()?;
+        Some(Box::new(WeightedSampleSetter { state_setters, distribution: self.distribution.clone(), rng: self.rng.clone() }))
+    }
+}
@@ -1,6 +1,6 @@
 use rocketsim_rs::sim::BoostPadState;
 
-use crate::gamestates::game_state::GameState;
+use crate::{envs::game_match::GameMode, gamestates::game_state::GameState};
 
 use super::{car_wrapper::CarWrapper, physics_wrapper::PhysicsWrapper};
 
@@ -12,6 +12,13 @@ pub struct StateWrapper {
     pub ball: PhysicsWrapper,
     pub cars: Vec<CarWrapper>,
     pub pads: [BoostPadState; 34],
+    /// mode of the match that the state is set in, so that setters can place the ball and cars for it
+    pub game_mode: GameMode,
+    /// set by setters that place a kickoff, the gym then runs the [KickoffPhase](crate::envs::kickoff::KickoffPhase)
+    /// and tracks the kickoff until the first touch. Without it `GameState::is_kickoff` stays false, only
+    /// [NoTouchKickoffTimeoutCondition](crate::conditionals::extra_conditions::NoTouchKickoffTimeoutCondition)
+    /// falls back to checking for the ball at the center.
+    pub kickoff: bool,
 }
 
 impl StateWrapper {
@@ -36,6 +43,8 @@ impl StateWrapper {
                     ball: PhysicsWrapper::new(None),
                     cars,
                     pads: [BoostPadState { is_active: true,..Default::default() }; 34],
+                    game_mode: GameMode::Soccar,
+                    kickoff: false,
                 }
             }
         }
@@ -57,6 +66,8 @@ impl StateWrapper {
             ball: PhysicsWrapper::new(Some(&game_state.ball)),
             cars,
             pads,
+            game_mode: game_state.game_mode(),
+            kickoff: false,
         }
     }
 
@@ -0,0 +1,437 @@
+use std::{
+    collections::HashMap,
+    ops::Range,
+    sync::{
+        mpsc::{channel, Receiver, Sender},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+};
+
+use crate::{
+    error::{Error, Result},
+    gym::Gym,
+    make::{make, MakeConfig},
+    seeding::derive_seed,
+};
+
+const WORKER_EXITED: &str = "worker thread exited";
+
+/// Packet that is sent from the VecGym to a worker
+enum ManagerPacket {
+    Reset { seeds: Vec<Option<u64>> },
+    Step { actions: Vec<Vec<Vec<f32>>> },
+    Close,
+}
+
+/// Packet that is sent back from a worker to the VecGym
+enum WorkerPacket {
+    InitReturn { n_agents: Vec<usize> },
+    ResetRet { obs: Vec<Vec<Vec<f32>>> },
+    StepRet { steps: Vec<EnvStep> },
+    Error(Error),
+}
+
+/// Result of a single sub-env step inside of a worker
+struct EnvStep {
+    obs: Vec<Vec<f32>>,
+    reward: Vec<f32>,
+    terminated: bool,
+    truncated: bool,
+    info: HashMap<String, f32>,
+    terminal_obs: Option<Vec<Vec<f32>>>,
+    agent_mask: Vec<bool>,
+}
+
+/// Handle to one of the worker threads, each worker owns the gyms in `env_range`
+struct WorkerHandle {
+    sender: Sender<ManagerPacket>,
+    receiver: Receiver<WorkerPacket>,
+    thread: Option<JoinHandle<()>>,
+    env_range: Range<usize>,
+}
+
+/// Batched return of a step from [VecGym].
+///
+/// `obs` is a flat buffer of shape `[total_agents, obs_size]` and `rewards`/`terminated`/`truncated` are of length `total_agents`.
+/// Agents are always ordered by env index and then by car id inside of each env.
+/// `infos` and `terminal_obs` are per-env, `terminal_obs` holds the flat observation of the finished episode
+/// for sub-envs that were automatically reset during this step.
+#[derive(Clone, Debug, Default)]
+pub struct VecStepResult {
+    pub obs: Vec<f32>,
+    pub rewards: Vec<f32>,
+    pub terminated: Vec<bool>,
+    pub truncated: Vec<bool>,
+    pub infos: Vec<HashMap<String, f32>>,
+    pub terminal_obs: Vec<Option<Vec<f32>>>,
+    /// false for the padded agents of sub-envs that use variable team sizes, see [TeamSizes](crate::envs::team_sizes::TeamSizes)
+    pub agent_mask: Vec<bool>,
+}
+
+/// Vectorized environment that owns `num_envs` instances of [Gym] spread across a pool of worker threads.
+///
+/// Each gym is built on its worker thread from the `MakeConfig` that the factory returns for that env index
+/// so that the gyms are created in parallel.
+/// Sub-envs are automatically reset when their episode ends, [Gym::auto_reset] is turned on for every gym regardless of the `MakeConfig`.
+/// If a gym returns an error then it is returned from the command that caused it, the worker thread stays alive.
+/// If a worker thread exits (eg. a gym panicked) then every later command returns [Error::VecGym].
+///
+/// RocketSim must be initialized (`rocketsim_rs::init`) before creating this.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// rocketsim_rs::init(None, false);
+///
+/// let mut vec_gym = VecGym::new(8, 4, |_env_idx| make::MakeConfig { ... })?;
+/// let obs = vec_gym.reset(None)?;
+/// let actions = vec![vec![0.; 8]; vec_gym.total_agents()];
+/// let step_result = vec_gym.step(actions)?;
+/// ```
+pub struct VecGym {
+    workers: Vec<WorkerHandle>,
+    n_agents_per_env: Vec<usize>,
+    total_agents: usize,
+    obs_size: usize,
+    waiting: bool,
+}
+
+impl VecGym {
+    /// Creates `num_envs` gyms spread evenly (in contiguous chunks) across `num_threads` worker threads.
+    ///
+    /// `config_factory` is called on the worker thread with the env index to get the config for that gym.
+    pub fn new<F>(num_envs: usize, num_threads: usize, config_factory: F) -> Result<Self>
+    where
+        F: Fn(usize) -> MakeConfig + Send + Sync + 'static,
+    {
+        if num_envs == 0 {
+            return Err(Error::VecGym("VecGym requires at least one env"));
+        }
+        let num_threads = num_threads.clamp(1, num_envs);
+        let config_factory = Arc::new(config_factory);
+
+        let envs_per_thread = num_envs / num_threads;
+        let remainder = num_envs % num_threads;
+
+        let mut workers = Vec::with_capacity(num_threads);
+        let mut start = 0;
+        for thread_idx in 0..num_threads {
+            let count = envs_per_thread + if thread_idx < remainder { 1 } else { 0 };
+            let env_range = start..start + count;
+            start += count;
+
+            let (manager_send, worker_recv) = channel();
+            let (worker_send, manager_recv) = channel();
+            let factory = config_factory.clone();
+            let range = env_range.clone();
+            let thread = thread::spawn(move || worker(range, factory, worker_send, worker_recv));
+
+            workers.push(WorkerHandle {
+                sender: manager_send,
+                receiver: manager_recv,
+                thread: Some(thread),
+                env_range,
+            });
+        }
+
+        // if this returns early then the workers are closed when this is dropped
+        let mut vec_gym = VecGym {
+            workers,
+            n_agents_per_env: Vec::with_capacity(num_envs),
+            total_agents: 0,
+            obs_size: 0,
+            waiting: false,
+        };
+
+        let mut first_err = None;
+        for worker in &vec_gym.workers {
+            match worker.receiver.recv() {
+                Ok(WorkerPacket::InitReturn { n_agents }) => vec_gym.n_agents_per_env.extend(n_agents),
+                Ok(WorkerPacket::Error(e)) => {
+                    first_err.get_or_insert(e);
+                }
+                Ok(_) => {
+                    first_err.get_or_insert(Error::VecGym("InitReturn was not returned from a newly created worker"));
+                }
+                Err(_) => {
+                    first_err.get_or_insert(Error::VecGym(WORKER_EXITED));
+                }
+            }
+        }
+        if let Some(e) = first_err {
+            return Err(e);
+        }
+        vec_gym.total_agents = vec_gym.n_agents_per_env.iter().sum();
+
+        Ok(vec_gym)
+    }
+
+    pub fn num_envs(&self) -> usize {
+        self.n_agents_per_env.len()
+    }
+
+    pub fn total_agents(&self) -> usize {
+        self.total_agents
+    }
+
+    pub fn n_agents_per_env(&self) -> &[usize] {
+        &self.n_agents_per_env
+    }
+
+    /// Size of a single agent's observation, this is only known after the first reset (0 before then)
+    pub fn obs_size(&self) -> usize {
+        self.obs_size
+    }
+
+    pub fn is_waiting(&self) -> bool {
+        self.waiting
+    }
+
+    /// Resets every sub-env and returns the flat observation buffer of shape `[total_agents, obs_size]`.
+    ///
+    /// Each sub-env gets its own seed derived from `seed` and its env index (see [derive_seed]).
+    pub fn reset(&mut self, seed: Option<u64>) -> Result<Vec<f32>> {
+        if self.waiting {
+            return Err(Error::VecGym("reset was called while waiting on step_wait"));
+        }
+        let mut first_err = None;
+        for worker in &self.workers {
+            let seeds = worker.env_range.clone().map(|env_idx| seed.map(|seed| derive_seed(seed, env_idx as u64))).collect();
+            if worker.sender.send(ManagerPacket::Reset { seeds }).is_err() {
+                first_err.get_or_insert(Error::VecGym(WORKER_EXITED));
+            }
+        }
+
+        // every worker must be received from so that the next command lines up, an exited worker returns right away
+        let mut env_obs = Vec::with_capacity(self.num_envs());
+        for worker in &self.workers {
+            match worker.receiver.recv() {
+                Ok(WorkerPacket::ResetRet { obs }) => env_obs.extend(obs),
+                Ok(WorkerPacket::Error(e)) => {
+                    first_err.get_or_insert(e);
+                }
+                Ok(_) => {
+                    first_err.get_or_insert(Error::VecGym("ResetRet was not returned from Reset command given"));
+                }
+                Err(_) => {
+                    first_err.get_or_insert(Error::VecGym(WORKER_EXITED));
+                }
+            }
+        }
+        if let Some(e) = first_err {
+            return Err(e);
+        }
+
+        self.obs_size = env_obs
+            .iter()
+            .flatten()
+            .map(|agent_obs| agent_obs.len())
+            .next()
+            .unwrap_or(0);
+
+        let mut flat_obs = Vec::with_capacity(self.total_agents * self.obs_size);
+        for obs in env_obs {
+            self.flatten_into(&mut flat_obs, obs)?;
+        }
+        Ok(flat_obs)
+    }
+
+    /// Sends the actions to the workers without waiting on the result, `actions` must have one entry per agent
+    pub fn step_async(&mut self, actions: Vec<Vec<f32>>) -> Result<()> {
+        if self.waiting {
+            return Err(Error::VecGym("step_async was called twice without calling step_wait"));
+        }
+        if self.obs_size == 0 {
+            return Err(Error::VecGym("reset must be called before stepping the VecGym"));
+        }
+        if actions.len() != self.total_agents {
+            return Err(Error::ActionCount { actions: actions.len(), players: self.total_agents });
+        }
+
+        let mut actions = actions.into_iter();
+        let mut all_sent = true;
+        for worker in &self.workers {
+            let worker_actions = self.n_agents_per_env[worker.env_range.clone()]
+                .iter()
+                .map(|n_agents| actions.by_ref().take(*n_agents).collect())
+                .collect();
+            all_sent &= worker.sender.send(ManagerPacket::Step { actions: worker_actions }).is_ok();
+        }
+        self.waiting = true;
+
+        if !all_sent {
+            // the workers that did get the actions still have to be received from so that the next command lines up
+            let _ = self.step_wait();
+            return Err(Error::VecGym(WORKER_EXITED));
+        }
+        Ok(())
+    }
+
+    /// Waits on every worker to finish stepping and gathers the results into flat buffers
+    pub fn step_wait(&mut self) -> Result<VecStepResult> {
+        if !self.waiting {
+            return Err(Error::VecGym("step_wait was called without calling step_async"));
+        }
+        let mut result = VecStepResult {
+            obs: Vec::with_capacity(self.total_agents * self.obs_size),
+            rewards: Vec::with_capacity(self.total_agents),
+            terminated: Vec::with_capacity(self.total_agents),
+            truncated: Vec::with_capacity(self.total_agents),
+            infos: Vec::with_capacity(self.num_envs()),
+            terminal_obs: Vec::with_capacity(self.num_envs()),
+            agent_mask: Vec::with_capacity(self.total_agents),
+        };
+
+        // every worker must be received from so that the next command lines up
+        let mut first_err = None;
+        for worker in &self.workers {
+            let steps = match worker.receiver.recv() {
+                Ok(WorkerPacket::StepRet { steps }) => steps,
+                Ok(WorkerPacket::Error(e)) => {
+                    first_err.get_or_insert(e);
+                    continue;
+                }
+                Ok(_) => {
+                    first_err.get_or_insert(Error::VecGym("StepRet was not returned from Step command given"));
+                    continue;
+                }
+                Err(_) => {
+                    first_err.get_or_insert(Error::VecGym(WORKER_EXITED));
+                    continue;
+                }
+            };
+
+            for step in steps {
+                let n_agents = step.reward.len();
+                if let Err(e) = self.flatten_into(&mut result.obs, step.obs) {
+                    first_err.get_or_insert(e);
+                }
+                result.rewards.extend(step.reward);
+                result.terminated.extend(vec![step.terminated; n_agents]);
+                result.truncated.extend(vec![step.truncated; n_agents]);
+                result.infos.push(step.info);
+                result.agent_mask.extend(step.agent_mask);
+                result.terminal_obs.push(step.terminal_obs.map(|terminal_obs| {
+                    let mut flat_terminal_obs = Vec::with_capacity(n_agents * self.obs_size);
+                    if let Err(e) = self.flatten_into(&mut flat_terminal_obs, terminal_obs) {
+                        first_err.get_or_insert(e);
+                    }
+                    flat_terminal_obs
+                }));
+            }
+        }
+        self.waiting = false;
+
+        match first_err {
+            Some(e) => Err(e),
+            None => Ok(result),
+        }
+    }
+
+    /// Convenience function for `step_async` followed by `step_wait`
+    pub fn step(&mut self, actions: Vec<Vec<f32>>) -> Result<VecStepResult> {
+        self.step_async(actions)?;
+        self.step_wait()
+    }
+
+    /// Stops all of the worker threads, this is also done when the VecGym is dropped
+    pub fn close(&mut self) {
+        for worker in &self.workers {
+            // the worker may have already exited so the error does not matter here
+            let _ = worker.sender.send(ManagerPacket::Close);
+        }
+        for worker in &mut self.workers {
+            if let Some(thread) = worker.thread.take() {
+                // a worker that panicked has nothing left to clean up
+                let _ = thread.join();
+            }
+        }
+    }
+
+    fn flatten_into(&self, flat_obs: &mut Vec<f32>, obs: Vec<Vec<f32>>) -> Result<()> {
+        for agent_obs in obs {
+            if agent_obs.len() != self.obs_size {
+                return Err(Error::ObsLength { len: agent_obs.len(), expected: self.obs_size });
+            }
+            flat_obs.extend(agent_obs);
+        }
+        Ok(())
+    }
+}
+
+impl Drop for VecGym {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+/// Loop for a single worker thread, builds its gyms and then responds to commands from the VecGym until closed
+fn worker<F>(env_range: Range<usize>, config_factory: Arc<F>, send_chan: Sender<WorkerPacket>, rec_chan: Receiver<ManagerPacket>)
+where
+    F: Fn(usize) -> MakeConfig + Send + Sync + 'static,
+{
+    let gyms: Result<Vec<Gym>> = env_range.map(|env_idx| make(config_factory(env_idx), None)).collect();
+    let mut gyms = match gyms {
+        Ok(mut gyms) => {
+            // the gyms reset themselves so that the terminal obs can be sent along with the obs of the new episode
+            for gym in gyms.iter_mut() {
+                gym.auto_reset = true;
+            }
+            gyms
+        }
+        Err(e) => {
+            // the worker has no gyms to run so it exits after sending the error
+            let _ = send_chan.send(WorkerPacket::Error(e));
+            return;
+        }
+    };
+    // padded so that the amount of agents of every env stays the same with variable team sizes
+    let n_agents = gyms.iter().map(|gym| gym._game_match.max_agents()).collect();
+    if send_chan.send(WorkerPacket::InitReturn { n_agents }).is_err() {
+        return;
+    }
+
+    while let Ok(cmd) = rec_chan.recv() {
+        let packet = match cmd {
+            ManagerPacket::Reset { seeds } => {
+                let obs: Result<Vec<_>> = gyms.iter_mut().zip(seeds).map(|(gym, seed)| Ok(gym.reset(None, seed)?.obs)).collect();
+                match obs {
+                    Ok(obs) => WorkerPacket::ResetRet { obs },
+                    Err(e) => WorkerPacket::Error(e),
+                }
+            }
+            ManagerPacket::Step { actions } => {
+                let steps: Result<Vec<_>> = gyms
+                    .iter_mut()
+                    .zip(actions)
+                    .map(|(gym, actions)| {
+                        let mut step_result = gym.step(actions)?;
+                        let (terminated, truncated) = (step_result.terminated, step_result.truncated);
+                        let terminal_obs = step_result.terminal_obs.take();
+                        let agent_mask = std::mem::take(&mut step_result.agent_mask);
+                        let (obs, reward, _, info) = step_result.into_legacy();
+                        Ok(EnvStep {
+                            obs,
+                            reward,
+                            terminated,
+                            truncated,
+                            info,
+                            terminal_obs,
+                            agent_mask,
+                        })
+                    })
+                    .collect();
+                match steps {
+                    Ok(steps) => WorkerPacket::StepRet { steps },
+                    Err(e) => WorkerPacket::Error(e),
+                }
+            }
+            ManagerPacket::Close => break,
+        };
+
+        if send_chan.send(packet).is_err() {
+            break;
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use rlgym_sim_rs::conditionals::common_conditions::TimeoutCondition;
+use rlgym_sim_rs::envs::game_match::GameConfig;
+use rlgym_sim_rs::make;
+use rlgym_sim_rs::obs_builders::advanced_obs::AdvancedObs;
+use rlgym_sim_rs::reward_functions::common_rewards::misc_rewards::EventReward;
+
+mod common;
+
+#[test]
+fn auto_reset_test() {
+    rocketsim_rs::init(None, false);
+
+    let tick_skip = 8;
+    let config = common::test_builder(GameConfig { tick_skip, ..Default::default() })
+        .with_terminal_condition(Box::new(TimeoutCondition::new(5)))
+        .with_reward_fn(Box::new(EventReward::new(None, None, None, None, None, None, None, None)))
+        .with_auto_reset(true)
+        .build_config()
+        .unwrap();
+    let mut gym = make::make(config, None).unwrap();
+    assert!(gym.auto_reset);
+
+    // throttle so that the terminal state is not the same as the reset state
+    let actions = vec![vec![1., 0., 0., 0., 0., 0., 1., 0.]; 2];
+    for episode in 0..2 {
+        for i in 0..5 {
+            let prev_tick = gym._prev_state.tick_num;
+            let step_result = gym.step(actions.clone()).unwrap();
+            if i == 4 {
+                assert!(step_result.truncated, "episode {episode} should have been truncated");
+                let terminal_obs = step_result.terminal_obs.expect("terminal obs should be kept on auto reset");
+                let terminal_state = step_result.terminal_state.expect("terminal state should be kept on auto reset");
+                assert_eq!(terminal_obs.len(), 2);
+                assert_eq!(terminal_state.tick_num, prev_tick + tick_skip as u64);
+                assert_ne!(terminal_obs, step_result.obs);
+                // the returned state is the initial state of the new episode
+                assert_eq!(gym._prev_state.tick_num, step_result.state.tick_num);
+                assert_ne!(terminal_state.players[0].car_data.position.y, step_result.state.players[0].car_data.position.y);
+            } else {
+                assert!(!step_result.done());
+                assert!(step_result.terminal_obs.is_none());
+                assert!(step_result.terminal_state.is_none());
+            }
+        }
+    }
+
+    // step_into also resets
+    let obs_len = AdvancedObs::obs_len(2);
+    let mut obs = vec![0.; 2 * obs_len];
+    let mut rewards = vec![0.; 2];
+    for i in 0..5 {
+        let info = gym.step_into(actions.clone(), &mut obs, &mut rewards).unwrap();
+        if i == 4 {
+            let terminal_obs = info.terminal_obs.expect("terminal obs should be kept on auto reset");
+            assert_eq!(terminal_obs.len(), 2);
+            assert_eq!(terminal_obs[0].len(), obs_len);
+            assert_ne!(terminal_obs.concat(), obs);
+            assert!(info.terminal_state.is_some());
+        } else {
+            assert!(info.terminal_obs.is_none());
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use rlgym_sim_rs::bots::bot::Bot;
+use rlgym_sim_rs::bots::common_bots::{BallChaser, Goalie, IdleBot, RandomBot};
+use rlgym_sim_rs::conditionals::common_conditions::TimeoutCondition;
+use rlgym_sim_rs::envs::game_match::GameConfig;
+use rlgym_sim_rs::error::Error;
+use rlgym_sim_rs::gamestates::game_state::GameState;
+use rlgym_sim_rs::make;
+use rlgym_sim_rs::reward_functions::common_rewards::misc_rewards::EventReward;
+
+mod common;
+
+#[test]
+fn bots_test() {
+    // the bots work on any state, no sim needed
+    let state = GameState::new_test();
+    let player = &state.players[0];
+    let idle = IdleBot::new().get_controls(player, &state);
+    assert_eq!(idle.throttle, 0.);
+    assert!(!idle.boost);
+    let chase = BallChaser::new(None).get_controls(player, &state);
+    assert_eq!(chase.throttle, 1.);
+    assert!((-1. ..=1.).contains(&chase.steer));
+    let goalie = Goalie::new(None, None).get_controls(player, &state);
+    assert!((-1. ..=1.).contains(&goalie.steer));
+    let random = RandomBot::new(Some(0)).get_controls(player, &state);
+    assert!((-1. ..=1.).contains(&random.throttle));
+
+    rocketsim_rs::init(None, false);
+
+    // 2v2 with the orange team driven by bots
+    let config = common::test_builder(GameConfig { team_size: 2, ..Default::default() })
+        .with_terminal_condition(Box::new(TimeoutCondition::new(1000)))
+        .with_reward_fn(Box::new(EventReward::new(None, None, None, None, None, None, None, None)))
+        .with_bot(2, Box::new(BallChaser::new(None)))
+        .with_bot(3, Box::new(IdleBot::new()))
+        .build_config()
+        .unwrap();
+    let mut gym = make::make(config, None).unwrap();
+    assert_eq!(gym._game_match.learners(), 2);
+
+    let reset_result = gym.reset(None, None).unwrap();
+    assert_eq!(reset_result.state.players.len(), 4);
+    assert_eq!(reset_result.obs.len(), 2);
+    let idle_start = reset_result.state.players[3].car_data.position;
+    let chaser_start = reset_result.state.players[2].car_data.position;
+
+    // actions for all of the cars is an error since only the learning agents are given actions
+    let actions = vec![vec![0.; 8]; 4];
+    assert!(matches!(gym.step(actions), Err(Error::ActionCount { actions: 4, players: 2 })));
+
+    let actions = vec![vec![0.; 8]; 2];
+    let mut step_result = gym.step(actions.clone()).unwrap();
+    for _ in 0..20 {
+        step_result = gym.step(actions.clone()).unwrap();
+    }
+    assert_eq!(step_result.obs.len(), 2);
+    assert_eq!(step_result.rewards.len(), 2);
+    assert_eq!(step_result.stats.len(), 4);
+
+    // the chaser drives off while the idle car stays put
+    let players = &step_result.state.players;
+    assert!(players[2].last_actions.throttle == 1.);
+    assert!((players[2].car_data.position.y - chaser_start.y).abs() > 100.);
+    assert!((players[3].car_data.position.y - idle_start.y).abs() < 1.);
+}
@@ -0,0 +1,74 @@
+use rand::{rngs::SmallRng, SeedableRng};
+use rlgym_sim_rs::conditionals::common_conditions::TimeoutCondition;
+use rlgym_sim_rs::envs::car_bodies::{CarBodies, HitboxType};
+use rlgym_sim_rs::envs::game_match::GameConfig;
+use rlgym_sim_rs::make;
+use rlgym_sim_rs::reward_functions::common_rewards::misc_rewards::EventReward;
+use rocketsim_rs::sim::CarConfig;
+
+mod common;
+
+const RANDOM_BODIES: &[HitboxType] = &[HitboxType::Octane, HitboxType::Dominus, HitboxType::Plank, HitboxType::Merc];
+
+fn make_config(car_bodies: CarBodies, seed: Option<u64>) -> make::MakeConfig {
+    let config = common::test_builder(GameConfig { team_size: 2, car_bodies, ..Default::default() })
+        .with_terminal_condition(Box::new(TimeoutCondition::new(100)))
+        .with_reward_fn(Box::new(EventReward::new(None, Some(1.), None, None, None, None, None, None)))
+        .build_config()
+        .unwrap();
+    make::MakeConfig { seed, ..config }
+}
+
+#[test]
+fn car_bodies_resolve_test() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for hitbox_type in HitboxType::ALL {
+        assert_eq!(HitboxType::from_config(hitbox_type.config()), hitbox_type);
+        assert_eq!(hitbox_type.one_hot()[hitbox_type.index()], 1.);
+    }
+
+    let per_team = CarBodies::PerTeam { blue: HitboxType::Dominus, orange: HitboxType::Breakout };
+    let configs = per_team.resolve(CarConfig::octane(), 2, true, &mut rng);
+    let bodies: Vec<HitboxType> = configs.iter().map(|config| HitboxType::from_config(config)).collect();
+    assert_eq!(bodies, vec![HitboxType::Dominus, HitboxType::Dominus, HitboxType::Breakout, HitboxType::Breakout]);
+
+    // slots without a body use the car config
+    let per_slot = CarBodies::PerSlot(&[HitboxType::Hybrid]);
+    let configs = per_slot.resolve(CarConfig::plank(), 1, true, &mut rng);
+    let bodies: Vec<HitboxType> = configs.iter().map(|config| HitboxType::from_config(config)).collect();
+    assert_eq!(bodies, vec![HitboxType::Hybrid, HitboxType::Plank]);
+
+    let configs = CarBodies::Random(RANDOM_BODIES).resolve(CarConfig::octane(), 3, true, &mut rng);
+    assert_eq!(configs.len(), 6);
+    assert!(configs.iter().all(|config| RANDOM_BODIES.contains(&HitboxType::from_config(config))));
+}
+
+#[test]
+fn car_bodies_test() {
+    rocketsim_rs::init(None, false);
+
+    let per_team = CarBodies::PerTeam { blue: HitboxType::Dominus, orange: HitboxType::Merc };
+    let mut gym = make::make(make_config(per_team, None), None).unwrap();
+    let reset_result = gym.reset(None, None).unwrap();
+    let bodies: Vec<HitboxType> = reset_result.state.players.iter().map(|player| player.hitbox_type).collect();
+    assert_eq!(bodies, vec![HitboxType::Dominus, HitboxType::Dominus, HitboxType::Merc, HitboxType::Merc]);
+
+    // changing only the bodies rebuilds the cars
+    let config = GameConfig { car_bodies: CarBodies::Same, car_config: CarConfig::plank(), ..gym._game_match.game_config };
+    gym.update_config(config, None).unwrap();
+    let reset_result = gym.reset(None, None).unwrap();
+    assert!(reset_result.state.players.iter().all(|player| player.hitbox_type == HitboxType::Plank));
+
+    // random bodies are sampled on every reset and follow the seed
+    let mut gym_1 = make::make(make_config(CarBodies::Random(RANDOM_BODIES), Some(3)), None).unwrap();
+    let mut gym_2 = make::make(make_config(CarBodies::Random(RANDOM_BODIES), Some(3)), None).unwrap();
+    let mut seen = Vec::new();
+    for _ in 0..10 {
+        let bodies_1: Vec<HitboxType> = gym_1.reset(None, None).unwrap().state.players.iter().map(|player| player.hitbox_type).collect();
+        let bodies_2: Vec<HitboxType> = gym_2.reset(None, None).unwrap().state.players.iter().map(|player| player.hitbox_type).collect();
+        assert_eq!(bodies_1, bodies_2);
+        seen.extend(bodies_1);
+    }
+    assert!(seen.iter().all(|body| RANDOM_BODIES.contains(body)));
+    assert!(seen.iter().any(|body| *body != seen[0]));
+}
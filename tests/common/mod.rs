@@ -0,0 +1,16 @@
+//! Fixtures that are shared by the integration tests, include them with `mod common;`
+
+use rlgym_sim_rs::action_parsers::test_parser::TestAction;
+use rlgym_sim_rs::envs::game_match::GameConfig;
+use rlgym_sim_rs::gym_builder::GymBuilder;
+use rlgym_sim_rs::state_setters::default_state::DefaultState;
+
+/// Builder with the components that most tests share: the actions are passed through as they are by [TestAction]
+/// and [DefaultState] is seeded so that the kickoffs are the same on every run.
+/// The other components are the defaults of [GymBuilder] until the test sets them.
+pub fn test_builder(game_config: GameConfig) -> GymBuilder {
+    GymBuilder::new()
+        .with_game_config(game_config)
+        .with_action_parser(Box::new(TestAction::new()))
+        .with_state_setter(Box::new(DefaultState::new(Some(0))))
+}
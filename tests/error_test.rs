@@ -0,0 +1,59 @@
+use rlgym_sim_rs::conditionals::common_conditions::TimeoutCondition;
+use rlgym_sim_rs::envs::game_match::GameConfig;
+use rlgym_sim_rs::make;
+use rlgym_sim_rs::obs_builders::advanced_obs::AdvancedObs;
+use rlgym_sim_rs::reward_functions::common_rewards::misc_rewards::EventReward;
+use rlgym_sim_rs::state_setters::default_state::DefaultState;
+use rlgym_sim_rs::state_setters::wrappers::state_wrapper::StateWrapper;
+use rlgym_sim_rs::{Error, ObsBuilder, StateSetter, VecGym};
+
+mod common;
+
+/// state setter that always fails
+struct FailingSetter {}
+
+impl StateSetter for FailingSetter {
+    fn reset(&mut self, _state_wrapper: &mut StateWrapper) -> rlgym_sim_rs::Result<()> {
+        Err(Error::StateSetter("failing on purpose".to_string()))
+    }
+}
+
+fn make_config(state_setter: Box<dyn StateSetter>, obs_builder: Vec<Box<dyn ObsBuilder>>) -> make::MakeConfig {
+    let config = common::test_builder(GameConfig::default())
+        .with_terminal_condition(Box::new(TimeoutCondition::new(100)))
+        .with_reward_fn(Box::new(EventReward::new(None, None, None, None, None, None, None, None)))
+        .with_state_setter(state_setter)
+        .build_config()
+        .unwrap();
+    // the builders are set afterwards since the builder would fill in a default for none
+    make::MakeConfig { obs_builder, ..config }
+}
+
+#[test]
+fn error_test() {
+    rocketsim_rs::init(None, false);
+
+    // the initial reset happens in make so the state setter error should come from there
+    let res = make::make(make_config(Box::new(FailingSetter {}), vec![Box::new(AdvancedObs::new())]), None);
+    assert!(matches!(res, Err(Error::StateSetter(_))));
+
+    // no observation builders
+    let res = make::make(make_config(Box::new(DefaultState::new(Some(0))), Vec::new()), None);
+    assert!(matches!(res, Err(Error::ObsBuilderCount { builders: 0, players: 2 })));
+
+    // wrong action count is recoverable
+    let mut gym = make::make(make_config(Box::new(DefaultState::new(Some(0))), vec![Box::new(AdvancedObs::new())]), None).unwrap();
+    let res = gym.step(vec![vec![0.; 8]]);
+    assert!(matches!(res, Err(Error::ActionCount { actions: 1, players: 2 })));
+    assert!(gym.step(vec![vec![0.; 8]; 2]).is_ok());
+
+    // errors from the gyms of a VecGym are returned instead of killing the worker
+    let res = VecGym::new(2, 2, |env_idx| {
+        if env_idx == 1 {
+            make_config(Box::new(FailingSetter {}), vec![Box::new(AdvancedObs::new())])
+        } else {
+            make_config(Box::new(DefaultState::new(Some(0))), vec![Box::new(AdvancedObs::new())])
+        }
+    });
+    assert!(matches!(res, Err(Error::StateSetter(_))));
+}
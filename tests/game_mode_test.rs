@@ -0,0 +1,78 @@
+use rlgym_sim_rs::common_values::{BLUE_TEAM, ORANGE_TEAM, VOID_BALL_POSITION};
+use rlgym_sim_rs::conditionals::common_conditions::TimeoutCondition;
+use rlgym_sim_rs::envs::game_match::{GameConfig, GameMode};
+use rlgym_sim_rs::error::Error;
+use rlgym_sim_rs::make;
+use rlgym_sim_rs::obs_builders::advanced_obs::AdvancedObs;
+use rlgym_sim_rs::reward_functions::common_rewards::ball_goal_rewards::VelocityBallToGoalReward;
+use rlgym_sim_rs::state_setters::default_state::DefaultState;
+use rlgym_sim_rs::state_setters::state_setter::StateSetter;
+use rlgym_sim_rs::state_setters::wrappers::state_wrapper::StateWrapper;
+
+mod common;
+
+fn make_config(game_mode: GameMode) -> make::MakeConfig {
+    common::test_builder(GameConfig { team_size: 2, game_mode, ..Default::default() })
+        .with_terminal_condition(Box::new(TimeoutCondition::new(100)))
+        .with_reward_fn(Box::new(VelocityBallToGoalReward::new(None, None)))
+        .build_config()
+        .unwrap()
+}
+
+#[test]
+fn game_mode_values_test() {
+    for game_mode in [GameMode::Soccar, GameMode::Hoops, GameMode::Heatseeker, GameMode::Snowday, GameMode::TheVoid] {
+        assert_eq!(GameMode::from_game_type(game_mode.game_type()), game_mode);
+    }
+    assert_eq!(GameMode::Soccar.num_pads(), 34);
+    assert_eq!(GameMode::Hoops.num_pads(), 20);
+    assert_eq!(GameMode::TheVoid.num_pads(), 0);
+    assert!(GameMode::Snowday.ball_radius() > GameMode::Soccar.ball_radius());
+    assert!(GameMode::Hoops.goal_center(BLUE_TEAM).y < 0.);
+    assert!(GameMode::Hoops.goal_center(ORANGE_TEAM).y > 0.);
+    assert_eq!(AdvancedObs::obs_len_for_mode(GameMode::Hoops, 4), AdvancedObs::obs_len(4) - 14);
+
+    // kickoff spots of hoops are closer to the middle than the soccar ones
+    let mut state_wrapper = StateWrapper::new(Some(2), Some(2), None);
+    state_wrapper.game_mode = GameMode::Hoops;
+    DefaultState::new(Some(0)).reset(&mut state_wrapper).unwrap();
+    assert!(state_wrapper.cars.iter().all(|car| car.position.y.abs() <= 3200.));
+    assert!(state_wrapper.ball.linear_velocity.z > 0.);
+
+    // heatseeker only has 4 kickoff spots per team
+    let mut state_wrapper = StateWrapper::new(Some(5), Some(5), None);
+    state_wrapper.game_mode = GameMode::Heatseeker;
+    assert!(matches!(DefaultState::new(Some(0)).reset(&mut state_wrapper), Err(Error::Config(_))));
+    state_wrapper.game_mode = GameMode::Soccar;
+    DefaultState::new(Some(0)).reset(&mut state_wrapper).unwrap();
+}
+
+#[test]
+fn game_mode_test() {
+    rocketsim_rs::init(None, false);
+
+    let actions = vec![vec![1., 0., 0., 0., 0., 0., 1., 0.]; 4];
+    for game_mode in [GameMode::Hoops, GameMode::Heatseeker, GameMode::Snowday] {
+        let mut gym = make::make(make_config(game_mode), None).unwrap();
+        let reset_result = gym.reset(None, None).unwrap();
+        assert_eq!(reset_result.state.game_mode(), game_mode);
+        assert_eq!(reset_result.obs[0].len(), AdvancedObs::obs_len_for_mode(game_mode, 4));
+        let step_result = gym.step(actions.clone()).unwrap();
+        assert!(step_result.rewards.iter().all(|reward| reward.is_finite()));
+    }
+
+    // the ball is held away from the cars in the void
+    let mut gym = make::make(make_config(GameMode::TheVoid), None).unwrap();
+    for _ in 0..5 {
+        let step_result = gym.step(actions.clone()).unwrap();
+        assert_eq!(step_result.state.ball.position.z, VOID_BALL_POSITION.z);
+        assert_eq!(step_result.obs[0].len(), AdvancedObs::obs_len_for_mode(GameMode::TheVoid, 4));
+    }
+
+    // switching modes rebuilds the arena
+    let mut gym = make::make(make_config(GameMode::Soccar), None).unwrap();
+    let config = GameConfig { game_mode: GameMode::Hoops, ..gym._game_match.game_config };
+    gym.update_config(config, None).unwrap();
+    let reset_result = gym.reset(None, None).unwrap();
+    assert_eq!(reset_result.state.game_mode(), GameMode::Hoops);
+}
@@ -0,0 +1,66 @@
+use rlgym_sim_rs::action_parsers::action_parser::ActionParser;
+use rlgym_sim_rs::bots::common_bots::RandomBot;
+use rlgym_sim_rs::envs::game_match::GameConfig;
+use rlgym_sim_rs::gamestates::game_state::GameState;
+use rlgym_sim_rs::obs_builders::advanced_obs::AdvancedObs;
+use rlgym_sim_rs::{DiscreteAction, Error, GymBuilder, ObsBuilder, Space};
+
+/// gives 3 values per agent instead of 8
+struct ShortAction;
+
+impl ActionParser for ShortAction {
+    fn get_action_space(&mut self) -> Space {
+        Space::box_f32(-1., 1., 3)
+    }
+
+    fn parse_actions(&mut self, actions: Vec<Vec<f32>>, _state: &GameState) -> Vec<Vec<f32>> {
+        actions
+    }
+}
+
+fn obs_builders(count: usize) -> Vec<Box<dyn ObsBuilder>> {
+    (0..count).map(|_| Box::new(AdvancedObs::new()) as Box<dyn ObsBuilder>).collect()
+}
+
+#[test]
+fn gym_builder_config_test() {
+    let config = GymBuilder::new().build_config().unwrap();
+    assert_eq!(config.obs_builder.len(), 1);
+    assert!(config.use_single_obs);
+
+    let res = GymBuilder::new().with_game_config(GameConfig { team_size: 0, ..Default::default() }).build_config();
+    assert!(matches!(res, Err(Error::Config(_))));
+
+    // 2v2 needs 4 builders, or 3 when one of the cars is a bot
+    let game_config = GameConfig { team_size: 2, ..Default::default() };
+    let res = GymBuilder::new().with_game_config(game_config).with_obs_builders(obs_builders(3)).build_config();
+    assert!(matches!(res, Err(Error::ObsBuilderCount { builders: 3, players: 4 })));
+    let res = GymBuilder::new()
+        .with_game_config(game_config)
+        .with_obs_builders(obs_builders(3))
+        .with_bot(3, Box::new(RandomBot::new(None)))
+        .build_config();
+    assert!(res.is_ok());
+
+    let res = GymBuilder::new().with_game_config(game_config).with_bot(4, Box::new(RandomBot::new(None))).build_config();
+    assert!(matches!(res, Err(Error::Config(_))));
+}
+
+#[test]
+fn gym_builder_test() {
+    rocketsim_rs::init(None, false);
+
+    // inference only, no reward or terminal condition
+    let mut gym = GymBuilder::new().with_game_config(GameConfig { team_size: 2, ..Default::default() }).build().unwrap();
+    assert_eq!(gym.observation_space, Space::box_f32(f32::NEG_INFINITY, f32::INFINITY, AdvancedObs::obs_len(4)));
+    let step_result = gym.step(vec![vec![0.; 8]; 4]).unwrap();
+    assert!(gym.observation_space.contains(&step_result.obs[0]));
+    assert!(step_result.rewards.iter().all(|reward| *reward == 0.));
+    assert!(!step_result.done());
+
+    let gym = GymBuilder::new().with_action_parser(Box::new(DiscreteAction::new())).build().unwrap();
+    assert_eq!(gym.action_space, Space::MultiDiscrete(vec![3, 3, 3, 3, 3, 2, 2, 2]));
+
+    let res = GymBuilder::new().with_action_parser(Box::new(ShortAction)).build();
+    assert!(matches!(res, Err(Error::ActionLength { len: 3 })));
+}
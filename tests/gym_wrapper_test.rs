@@ -0,0 +1,145 @@
+use rlgym_sim_rs::conditionals::common_conditions::TimeoutCondition;
+use rlgym_sim_rs::envs::game_match::GameConfig;
+use rlgym_sim_rs::envs::team_sizes::{TeamSizeOption, TeamSizes};
+use rlgym_sim_rs::gym_wrappers::{
+    action_repeat::ActionRepeat,
+    frame_stack::FrameStack,
+    obs_norm::ObsNormalize,
+    reward_scale::{RewardClip, RewardScale},
+    GymWrapper,
+};
+use rlgym_sim_rs::make;
+use rlgym_sim_rs::obs_builders::advanced_obs::AdvancedObs;
+use rlgym_sim_rs::reward_functions::common_rewards::misc_rewards::VelocityReward;
+use rlgym_sim_rs::Gym;
+
+mod common;
+
+fn make_gym() -> Gym {
+    let config = common::test_builder(GameConfig::default())
+        .with_terminal_condition(Box::new(TimeoutCondition::new(1000)))
+        .with_reward_fn(Box::new(VelocityReward::new(None)))
+        .build_config()
+        .unwrap();
+    make::make(config, None).unwrap()
+}
+
+#[test]
+fn gym_wrapper_test() {
+    rocketsim_rs::init(None, false);
+
+    let actions = vec![vec![1., 0.5, 0., 0., 0., 0., 1., 0.]; 2];
+    let obs_len = AdvancedObs::obs_len(2);
+
+    // reward scaling then clipping, compared to an unwrapped gym with the same seed
+    let mut base = make_gym();
+    let mut env = RewardClip::new(RewardScale::new(make_gym(), 2.), -0.5, 0.5);
+    base.reset(None, None).unwrap();
+    env.reset(None).unwrap();
+    for _ in 0..20 {
+        let base_result = base.step(actions.clone()).unwrap();
+        let step_result = env.step(actions.clone()).unwrap();
+        for (reward, base_reward) in step_result.rewards.iter().zip(&base_result.rewards) {
+            assert_eq!(*reward, (base_reward * 2.).clamp(-0.5, 0.5));
+        }
+    }
+
+    // action repeat steps the inner gym several times and sums the rewards
+    let mut base = make_gym();
+    let mut env = ActionRepeat::new(make_gym(), 3);
+    base.reset(None, None).unwrap();
+    let reset_result = env.reset(None).unwrap();
+    let step_result = env.step(actions.clone()).unwrap();
+    assert_eq!(step_result.tick_count, reset_result.tick_count + 3 * 8);
+    let mut base_rewards = vec![0.; 2];
+    let mut base_result = None;
+    for _ in 0..3 {
+        let result = base.step(actions.clone()).unwrap();
+        for (sum, reward) in base_rewards.iter_mut().zip(&result.rewards) {
+            *sum += reward;
+        }
+        base_result = Some(result);
+    }
+    assert_eq!(step_result.obs, base_result.unwrap().obs);
+    for (reward, base_reward) in step_result.rewards.iter().zip(&base_rewards) {
+        assert!((reward - base_reward).abs() < 1e-5);
+    }
+
+    // frame stacking
+    let mut base = make_gym();
+    let mut env = FrameStack::new(make_gym(), 4);
+    let base_reset = base.reset(None, None).unwrap();
+    let reset_result = env.reset(None).unwrap();
+    assert_eq!(reset_result.obs[0].len(), 4 * obs_len);
+    for frame in reset_result.obs[0].chunks(obs_len) {
+        assert_eq!(frame, base_reset.obs[0].as_slice());
+    }
+    let mut prev_stacked = reset_result.obs;
+    for _ in 0..5 {
+        let base_result = base.step(actions.clone()).unwrap();
+        let step_result = env.step(actions.clone()).unwrap();
+        for (agent_obs, (prev_obs, base_obs)) in step_result.obs.iter().zip(prev_stacked.iter().zip(&base_result.obs)) {
+            // oldest frame is dropped and the newest obs is appended
+            assert_eq!(agent_obs[..3 * obs_len], prev_obs[obs_len..]);
+            assert_eq!(agent_obs[3 * obs_len..], base_obs[..]);
+        }
+        prev_stacked = step_result.obs;
+    }
+
+    // observation normalization on top of the other wrappers
+    let mut env = ObsNormalize::new(RewardScale::new(make_gym(), 0.1), Some(5.));
+    env.reset(None).unwrap();
+    for _ in 0..50 {
+        let step_result = env.step(actions.clone()).unwrap();
+        assert!(step_result.obs.iter().flatten().all(|val| val.abs() <= 5.));
+    }
+    assert_eq!(env.mean().len(), obs_len);
+    assert!(env.var().iter().all(|var| *var >= 0.));
+    env.set_update(false);
+    let mean = env.mean().to_vec();
+    env.step(actions.clone()).unwrap();
+    assert_eq!(env.mean(), mean.as_slice());
+    // the innermost gym can still be reached
+    assert_eq!(env.gym()._game_match.agents, 2);
+}
+
+#[test]
+fn obs_normalize_padding_test() {
+    rocketsim_rs::init(None, false);
+
+    // a 1v0 is padded to the 2 agents of a 2v0, the padding has to stay zero and out of the statistics
+    let team_sizes = TeamSizes::new(vec![TeamSizeOption::new(1, false, 1.), TeamSizeOption::new(2, false, 1.)], Some(0))
+        .unwrap()
+        .with_pad_obs_len(AdvancedObs::obs_len(2));
+    let gym = common::test_builder(GameConfig::default())
+        .with_terminal_condition(Box::new(TimeoutCondition::new(3)))
+        .with_reward_fn(Box::new(VelocityReward::new(None)))
+        .with_team_sizes(team_sizes)
+        .with_auto_reset(true)
+        .build()
+        .unwrap();
+    let mut env = ObsNormalize::new(gym, None);
+    let mut episode_mask = env.reset(None).unwrap().agent_mask;
+
+    let actions = vec![vec![1., 0.5, 0., 0., 0., 0., 1., 0.]; 2];
+    let mut saw_padding = false;
+    for _ in 0..30 {
+        let step_result = env.step(actions.clone()).unwrap();
+        for (agent_obs, active) in step_result.obs.iter().zip(&step_result.agent_mask) {
+            if !active {
+                saw_padding = true;
+                assert!(agent_obs.iter().all(|val| *val == 0.));
+            }
+        }
+        // the padding of the terminal obs is that of the episode that ended, not of the next team size
+        if let Some(terminal_obs) = &step_result.terminal_obs {
+            for (agent_obs, active) in terminal_obs.iter().zip(&episode_mask) {
+                if !active {
+                    assert!(agent_obs.iter().all(|val| *val == 0.));
+                }
+            }
+        }
+        episode_mask = step_result.agent_mask;
+    }
+    assert!(saw_padding, "a 1v0 should have been sampled");
+}
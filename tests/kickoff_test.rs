@@ -0,0 +1,89 @@
+use rlgym_sim_rs::conditionals::extra_conditions::NoTouchKickoffTimeoutCondition;
+use rlgym_sim_rs::conditionals::terminal_condition::TerminalCondition;
+use rlgym_sim_rs::envs::game_match::GameConfig;
+use rlgym_sim_rs::envs::kickoff::KickoffPhase;
+use rlgym_sim_rs::gamestates::game_state::GameState;
+use rlgym_sim_rs::GymBuilder;
+
+#[test]
+fn kickoff_timeout_test() {
+    let mut condition = NoTouchKickoffTimeoutCondition::new(3);
+    let kickoff = GameState { is_kickoff: true, ..GameState::new_test() };
+    let countdown = GameState { is_kickoff_pause: true, ..kickoff.clone() };
+    condition.reset(&kickoff);
+
+    // the countdown does not count
+    for _ in 0..5 {
+        assert!(!condition.is_terminal(&countdown));
+    }
+    assert!(!condition.is_terminal(&kickoff));
+    assert!(!condition.is_terminal(&kickoff));
+    assert!(condition.is_terminal(&kickoff));
+
+    // the ball is not at the center in this state, only the kickoff tracking matters
+    condition.reset(&kickoff);
+    assert!(!condition.is_terminal(&GameState::new_test()));
+    assert!(!condition.is_terminal(&kickoff));
+
+    // a kickoff that is not tracked is found from the untouched ball at the center
+    let mut untracked = GameState::new_test();
+    untracked.ball.position.x = 0.;
+    untracked.ball.position.y = 0.;
+    condition.reset(&untracked);
+    assert!(!condition.is_terminal(&untracked));
+    assert!(!condition.is_terminal(&untracked));
+    assert!(condition.is_terminal(&untracked));
+    untracked.players[0].ball_touched = true;
+    assert!(!condition.is_terminal(&untracked));
+}
+
+#[test]
+fn kickoff_test() {
+    rocketsim_rs::init(None, false);
+
+    let game_config = GameConfig { kickoff_phase: Some(KickoffPhase { countdown: 0.5, hold_ball: true }), ..Default::default() };
+    let mut gym = GymBuilder::new().with_game_config(game_config).build().unwrap();
+    let state = gym.reset(None, None).unwrap().state;
+    assert!(state.is_kickoff);
+    assert!(state.is_kickoff_pause);
+    assert_eq!(state.kickoff_time_remaining, 0.5);
+    let car_start = state.players[0].car_data.position;
+
+    // 8 ticks per step, the countdown is 60 ticks
+    let drive = vec![vec![1., 0., 0., 0., 0., 0., 1., 0.]; 2];
+    for _ in 0..7 {
+        let state = gym.step(drive.clone()).unwrap().state;
+        assert!(state.is_kickoff_pause);
+        assert_eq!(state.players[0].car_data.position.x, car_start.x);
+        assert_eq!(state.players[0].car_data.position.y, car_start.y);
+    }
+    let state = gym.step(drive.clone()).unwrap().state;
+    assert!(!state.is_kickoff_pause);
+    assert_eq!(state.kickoff_time_remaining, 0.);
+    assert!(state.is_kickoff);
+    assert_ne!(state.players[0].car_data.position.y, car_start.y);
+
+    // the kickoff ends with the first touch
+    let mut touched = false;
+    for _ in 0..300 {
+        let state = gym.step(drive.clone()).unwrap().state;
+        if state.players.iter().any(|player| player.ball_touched) {
+            assert!(!state.is_kickoff);
+            touched = true;
+            break;
+        }
+        assert!(state.is_kickoff);
+        assert_eq!(state.ball.position.x, 0.);
+        assert_eq!(state.ball.position.y, 0.);
+    }
+    assert!(touched);
+
+    // without a kickoff phase the cars can drive right away but the kickoff is still tracked
+    let mut gym = GymBuilder::new().build().unwrap();
+    let state = gym.reset(None, None).unwrap().state;
+    assert!(state.is_kickoff);
+    assert!(!state.is_kickoff_pause);
+    let car_start = state.players[0].car_data.position;
+    let state = gym.step(drive).unwrap().state;
+    assert_ne!(state.players[0].car_data.position.y, car_start.y);
+}
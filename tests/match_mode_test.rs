@@ -0,0 +1,156 @@
+use std::sync::{Arc, Mutex};
+
+use rlgym_sim_rs::bots::opponent_pool::{Opponent, OpponentPool};
+use rlgym_sim_rs::envs::game_match::GameConfig;
+use rlgym_sim_rs::envs::match_mode::{MatchClock, MatchConfig};
+use rlgym_sim_rs::gamestates::game_state::GameState;
+use rlgym_sim_rs::obs_builders::advanced_obs::AdvancedObs;
+use rlgym_sim_rs::state_setters::default_state::BlueGoalStateTester;
+use rlgym_sim_rs::{GymBuilder, ObsBuilder};
+
+const TICK_SKIP: u64 = 8;
+
+/// one second of regulation and half a second of countdown
+fn short_match(overtime: bool) -> MatchConfig {
+    MatchConfig { game_length: 1., kickoff_countdown: 0.5, overtime }
+}
+
+/// the kickoff (which is tracked by the sim) goes on until `touched`
+fn step(clock: &mut MatchClock, state: &mut GameState, touched: bool) {
+    state.is_kickoff = state.is_kickoff && !touched;
+    clock.update(state, TICK_SKIP);
+}
+
+/// starts the round from a kickoff and touches the ball so that the clock runs
+fn start_play(clock: &mut MatchClock, state: &mut GameState) {
+    state.is_kickoff = true;
+    step(clock, state, false);
+    step(clock, state, true);
+}
+
+/// records the round of every call and whether its obs were built from the state that it was given
+struct CheckingOpponent {
+    calls: Arc<Mutex<Vec<(u32, bool)>>>,
+}
+
+impl Opponent for CheckingOpponent {
+    fn get_actions(&mut self, obs: &[Vec<f32>], state: &GameState) -> Vec<Vec<f32>> {
+        let expected = AdvancedObs::new().build_obs(&state.players[1], state, &GameConfig::default());
+        self.calls.lock().unwrap().push((state.round_num, obs[0] == expected));
+        vec![vec![0.; 8]; obs.len()]
+    }
+}
+
+#[test]
+fn match_clock_test() {
+    let mut state = GameState { is_kickoff: true, ..GameState::new_test() };
+    let mut clock = MatchClock::new(short_match(true));
+    clock.reset(&mut state);
+    assert_eq!(state.round_num, 1);
+    assert_eq!(state.game_seconds_remaining, 1.);
+
+    // the clock waits for the first touch of the kickoff
+    for _ in 0..10 {
+        step(&mut clock, &mut state, false);
+    }
+    assert_eq!(state.game_seconds_remaining, 1.);
+    step(&mut clock, &mut state, true);
+    assert!(state.game_seconds_remaining < 1.);
+
+    // a goal starts a new round from a kickoff
+    state.blue_score += 1;
+    step(&mut clock, &mut state, false);
+    assert!(clock.kickoff_pending());
+    assert!(!clock.is_finished());
+    clock.start_round();
+    clock.write(&mut state);
+    assert_eq!(state.round_num, 2);
+
+    // regulation ends with blue ahead
+    start_play(&mut clock, &mut state);
+    while !clock.is_finished() {
+        step(&mut clock, &mut state, false);
+    }
+    assert_eq!(state.game_seconds_remaining, 0.);
+    assert!(!state.is_overtime);
+}
+
+#[test]
+fn match_overtime_test() {
+    let mut state = GameState::new_test();
+    let mut clock = MatchClock::new(short_match(true));
+    clock.reset(&mut state);
+    start_play(&mut clock, &mut state);
+    while !clock.kickoff_pending() {
+        step(&mut clock, &mut state, false);
+    }
+    // a tie goes to overtime, which starts from a kickoff and has no clock
+    assert!(state.is_overtime);
+    assert!(!clock.is_finished());
+    clock.start_round();
+    start_play(&mut clock, &mut state);
+    for _ in 0..100 {
+        step(&mut clock, &mut state, false);
+    }
+    assert!(!clock.is_finished());
+    state.orange_score += 1;
+    step(&mut clock, &mut state, false);
+    assert!(clock.is_finished());
+
+    // without overtime a tie is a draw
+    let mut state = GameState::new_test();
+    let mut clock = MatchClock::new(short_match(false));
+    clock.reset(&mut state);
+    start_play(&mut clock, &mut state);
+    while !clock.is_finished() {
+        step(&mut clock, &mut state, false);
+    }
+    assert!(!state.is_overtime);
+}
+
+#[test]
+fn match_mode_test() {
+    rocketsim_rs::init(None, false);
+
+    let mut gym = GymBuilder::new().with_match_config(short_match(true)).build().unwrap();
+    let reset_result = gym.reset(None, None).unwrap();
+    assert!(reset_result.state.is_kickoff_pause);
+    assert_eq!(reset_result.state.round_num, 1);
+
+    // the cars are frozen for the countdown of the match
+    assert_eq!(reset_result.state.kickoff_time_remaining, 0.5);
+    let step_result = gym.step(vec![vec![1., 0., 0., 0., 0., 0., 1., 0.]; 2]).unwrap();
+    assert!(step_result.state.is_kickoff_pause);
+    for player in step_result.state.players.iter() {
+        assert_eq!(player.car_data.linear_velocity.x, 0.);
+        assert_eq!(player.car_data.linear_velocity.y, 0.);
+    }
+    assert!(!step_result.done());
+}
+
+#[test]
+fn match_kickoff_opponent_test() {
+    rocketsim_rs::init(None, false);
+
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let mut pool = OpponentPool::new(None, Some(0));
+    pool.add("checking", Box::new(CheckingOpponent { calls: calls.clone() }), 1.);
+    let mut gym = GymBuilder::new()
+        .with_match_config(MatchConfig { game_length: 30., ..short_match(true) })
+        .with_state_setter(Box::new(BlueGoalStateTester::new()))
+        .with_opponent_pool(pool)
+        .build()
+        .unwrap();
+    gym.reset(None, None).unwrap();
+
+    // the step after the goal acts on the kickoff, so the opponent obs have to be rebuilt for it
+    for _ in 0..200 {
+        let step_result = gym.step(vec![vec![0.; 8]]).unwrap();
+        if step_result.state.round_num == 2 || step_result.done() {
+            break;
+        }
+    }
+    let calls = calls.lock().unwrap();
+    assert!(calls.iter().any(|(round_num, _)| *round_num == 2), "the goal should have started a second round");
+    assert!(calls.iter().all(|(_, built_from_state)| *built_from_state), "the opponent was given stale obs");
+}
@@ -0,0 +1,146 @@
+use std::fs;
+
+use rlgym_sim_rs::bots::opponent_pool::{Opponent, OpponentPool};
+use rlgym_sim_rs::conditionals::common_conditions::TimeoutCondition;
+use rlgym_sim_rs::envs::game_match::GameConfig;
+use rlgym_sim_rs::envs::team_sizes::{TeamSizeOption, TeamSizes};
+use rlgym_sim_rs::gamestates::game_state::GameState;
+use rlgym_sim_rs::gym_wrappers::{monitor::{Monitor, MonitorFormat}, GymWrapper};
+use rlgym_sim_rs::make;
+use rlgym_sim_rs::obs_builders::advanced_obs::AdvancedObs;
+use rlgym_sim_rs::reward_functions::common_rewards::misc_rewards::VelocityReward;
+
+mod common;
+
+struct IdleOpponent {}
+
+impl Opponent for IdleOpponent {
+    fn get_actions(&mut self, obs: &[Vec<f32>], _state: &GameState) -> Vec<Vec<f32>> {
+        vec![vec![0.; 8]; obs.len()]
+    }
+}
+
+fn make_config(auto_reset: bool) -> make::MakeConfig {
+    common::test_builder(GameConfig::default())
+        .with_terminal_condition(Box::new(TimeoutCondition::new(5)))
+        .with_reward_fn(Box::new(VelocityReward::new(None)))
+        .with_auto_reset(auto_reset)
+        .build_config()
+        .unwrap()
+}
+
+#[test]
+fn monitor_test() {
+    rocketsim_rs::init(None, false);
+
+    let actions = vec![vec![1., 0., 0., 0., 0., 0., 1., 0.]; 2];
+
+    // jsonl with auto reset
+    let jsonl_path = std::env::temp_dir().join("rlgym_sim_rs_monitor_test.jsonl");
+    let _ = fs::remove_file(&jsonl_path);
+    let gym = make::make(make_config(true), None).unwrap();
+    let mut monitor = Monitor::new(gym, Some(&jsonl_path), MonitorFormat::Jsonl, 2).unwrap();
+    let mut rewards_sum = [0.; 2];
+    for i in 0..15 {
+        let step_result = monitor.step(actions.clone()).unwrap();
+        if i < 5 {
+            rewards_sum[0] += step_result.rewards[0];
+            rewards_sum[1] += step_result.rewards[1];
+        }
+    }
+    assert_eq!(monitor.total_episodes(), 3);
+    // window of 2
+    let records = monitor.episodes().collect::<Vec<_>>();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].episode, 1);
+    for record in records {
+        assert_eq!(record.length, 5);
+        assert_eq!(record.ticks, 40);
+        assert_eq!(record.returns.len(), 2);
+        assert!(record.truncated);
+        assert!(!record.terminated);
+        assert_eq!(record.goals_for, vec![0, 0]);
+    }
+    let means = monitor.rolling_means();
+    assert_eq!(means.episodes, 2);
+    assert_eq!(means.length, 5.);
+    assert_eq!(means.ticks, 40.);
+
+    let contents = fs::read_to_string(&jsonl_path).unwrap();
+    let lines = contents.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 3);
+    let first: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+    assert_eq!(first["episode"], 0);
+    assert_eq!(first["length"], 5);
+    let returns = first["returns"].as_array().unwrap();
+    assert!((returns[0].as_f64().unwrap() as f32 - rewards_sum[0]).abs() < 1e-4);
+    assert!((returns[1].as_f64().unwrap() as f32 - rewards_sum[1]).abs() < 1e-4);
+
+    // csv with manual resets
+    let csv_path = std::env::temp_dir().join("rlgym_sim_rs_monitor_test.csv");
+    let _ = fs::remove_file(&csv_path);
+    let gym = make::make(make_config(false), None).unwrap();
+    let mut monitor = Monitor::new(gym, Some(&csv_path), MonitorFormat::Csv, 100).unwrap();
+    for _ in 0..2 {
+        monitor.reset(None).unwrap();
+        for _ in 0..5 {
+            monitor.step(actions.clone()).unwrap();
+        }
+    }
+    assert_eq!(monitor.total_episodes(), 2);
+    let contents = fs::read_to_string(&csv_path).unwrap();
+    let lines = contents.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("episode,returns,length,ticks"));
+    let row = lines[1].split(',').collect::<Vec<_>>();
+    assert_eq!(row.len(), 9);
+    assert_eq!(row[1].split(';').count(), 2);
+    assert_eq!(row[2], "5");
+    assert_eq!(row[3], "40");
+}
+
+#[test]
+fn monitor_padding_test() {
+    rocketsim_rs::init(None, false);
+
+    // the orange cars are driven by the opponent pool and 1v1s are padded to the 2 learners of a 2v2
+    let mut pool = OpponentPool::new(None, Some(0));
+    pool.add("idle", Box::new(IdleOpponent {}), 1.);
+    let team_sizes = TeamSizes::new(vec![TeamSizeOption::new(1, true, 1.), TeamSizeOption::new(2, true, 1.)], Some(0))
+        .unwrap()
+        .with_pad_obs_len(AdvancedObs::obs_len(4));
+    let gym = common::test_builder(GameConfig::default())
+        .with_terminal_condition(Box::new(TimeoutCondition::new(5)))
+        .with_reward_fn(Box::new(VelocityReward::new(None)))
+        .with_team_sizes(team_sizes)
+        .with_opponent_pool(pool)
+        .with_auto_reset(true)
+        .build()
+        .unwrap();
+    let mut monitor = Monitor::new(gym, None::<&str>, MonitorFormat::Jsonl, 100).unwrap();
+    monitor.reset(None).unwrap();
+
+    let actions = vec![vec![1., 0., 0., 0., 0., 0., 1., 0.]; 2];
+    let mut learners = Vec::new();
+    for _ in 0..40 {
+        let step_result = monitor.step(actions.clone()).unwrap();
+        // the mask of the step that ends an episode is already the one of the next episode
+        if !step_result.done() && learners.len() == monitor.total_episodes() as usize {
+            learners.push(step_result.agent_mask.iter().filter(|active| **active).count());
+        }
+    }
+    assert_eq!(monitor.total_episodes(), 8);
+
+    // every per agent value only has the learning agents
+    let mut returns_sum = 0.;
+    let mut agent_episodes = 0;
+    for (record, learners) in monitor.episodes().zip(learners) {
+        assert_eq!(record.returns.len(), learners);
+        assert_eq!(record.goals_for.len(), learners);
+        assert_eq!(record.goals_against.len(), learners);
+        returns_sum += record.returns.iter().sum::<f32>();
+        agent_episodes += learners;
+    }
+    let means = monitor.rolling_means();
+    assert!((means.episode_return - returns_sum / agent_episodes as f32).abs() < 1e-4);
+}
@@ -0,0 +1,49 @@
+use rlgym_sim_rs::conditionals::common_conditions::TimeoutCondition;
+use rlgym_sim_rs::envs::game_match::GameConfig;
+use rlgym_sim_rs::make;
+use rlgym_sim_rs::reward_functions::common_rewards::misc_rewards::EventReward;
+use rlgym_sim_rs::state_setters::default_state::{BlueGoalStateTester, DefaultState};
+use rlgym_sim_rs::StateSetter;
+
+mod common;
+
+fn make_config(state_setter: Box<dyn StateSetter>) -> make::MakeConfig {
+    common::test_builder(GameConfig::default())
+        // long enough that the goal is scored before the episode ends
+        .with_terminal_condition(Box::new(TimeoutCondition::new(1000)))
+        .with_reward_fn(Box::new(EventReward::new(None, None, None, None, None, None, None, None)))
+        .with_state_setter(state_setter)
+        .build_config()
+        .unwrap()
+}
+
+#[test]
+fn multi_gym_test() {
+    rocketsim_rs::init(None, false);
+
+    // both gyms are on this thread so scores and stats must not be shared between them
+    let mut scoring_gym = make::make(make_config(Box::new(BlueGoalStateTester::new())), None).unwrap();
+    let mut other_gym = make::make(make_config(Box::new(DefaultState::new(Some(0)))), None).unwrap();
+
+    let actions = vec![vec![0.; 8]; 2];
+    let mut scored = false;
+    for _ in 0..200 {
+        let scoring_result = scoring_gym.step(actions.clone()).unwrap();
+        let other_result = other_gym.step(actions.clone()).unwrap();
+
+        assert_eq!(other_result.state.blue_score, 0, "score from the other gym leaked into this gym");
+        assert_eq!(other_result.state.orange_score, 0, "score from the other gym leaked into this gym");
+        assert!(other_result.stats.iter().all(|stats| stats.goals == 0 && stats.own_goals == 0));
+
+        if scoring_result.state.blue_score > 0 {
+            scored = true;
+            break;
+        }
+    }
+    assert!(scored, "blue goal state should have resulted in a goal");
+
+    // a gym made after the goal starts with its own scores
+    let mut new_gym = make::make(make_config(Box::new(DefaultState::new(Some(0)))), None).unwrap();
+    let new_result = new_gym.step(actions).unwrap();
+    assert_eq!(new_result.state.blue_score, 0);
+}
@@ -0,0 +1,62 @@
+use rlgym_sim_rs::conditionals::common_conditions::TimeoutCondition;
+use rlgym_sim_rs::envs::game_match::GameConfig;
+use rlgym_sim_rs::envs::mutators::{ActiveMutators, Mutators};
+use rlgym_sim_rs::make;
+use rlgym_sim_rs::reward_functions::common_rewards::misc_rewards::EventReward;
+use rocketsim_rs::sim::{DemoMode, MutatorConfig};
+
+mod common;
+
+fn make_config(mutators: Mutators) -> make::MakeConfig {
+    common::test_builder(GameConfig { gravity: 0.5, mutators, ..Default::default() })
+        .with_terminal_condition(Box::new(TimeoutCondition::new(100)))
+        .with_reward_fn(Box::new(EventReward::new(None, Some(1.), None, None, None, None, None, None)))
+        .build_config()
+        .unwrap()
+}
+
+#[test]
+fn mutators_apply_test() {
+    let mutators = Mutators {
+        ball_max_speed: Some(1000.),
+        unlimited_flips: Some(true),
+        demo_mode: Some(DemoMode::Disabled),
+        ..Default::default()
+    };
+    let mut config = MutatorConfig::default(rocketsim_rs::sim::GameMode::Soccar);
+    let default_mass = config.car_mass;
+    mutators.apply(&mut config);
+    assert_eq!(config.ball_max_speed, 1000.);
+    assert!(config.unlimited_flips);
+    assert_eq!(config.demo_mode, DemoMode::Disabled);
+    // unset fields keep the defaults
+    assert_eq!(config.car_mass, default_mass);
+
+    let json = serde_json::to_string(&mutators).unwrap();
+    assert_eq!(serde_json::from_str::<Mutators>(&json).unwrap(), mutators);
+    assert_eq!(ActiveMutators::default().ball_max_speed, MutatorConfig::default(rocketsim_rs::sim::GameMode::Soccar).ball_max_speed);
+}
+
+#[test]
+fn mutators_test() {
+    rocketsim_rs::init(None, false);
+
+    let mutators = Mutators {
+        ball_max_speed: Some(1000.),
+        respawn_delay: Some(1.),
+        ..Default::default()
+    };
+    let mut gym = make::make(make_config(mutators), None).unwrap();
+    let reset_result = gym.reset(None, None).unwrap();
+    let default_mutators = ActiveMutators::default();
+    assert_eq!(reset_result.state.mutators.ball_max_speed, 1000.);
+    assert_eq!(reset_result.state.mutators.respawn_delay, 1.);
+    assert_eq!(reset_result.state.mutators.gravity_z, default_mutators.gravity_z * 0.5);
+    assert_eq!(reset_result.state.mutators.car_mass, default_mutators.car_mass);
+
+    // the mutators are changed together with the rest of the config
+    let config = GameConfig { mutators: Mutators::default(), ..gym._game_match.game_config };
+    gym.update_config(config, None).unwrap();
+    let reset_result = gym.reset(None, None).unwrap();
+    assert_eq!(reset_result.state.mutators.ball_max_speed, default_mutators.ball_max_speed);
+}
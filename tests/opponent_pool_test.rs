@@ -0,0 +1,149 @@
+use rlgym_sim_rs::bots::opponent_pool::{MatchOutcome, Opponent, OpponentPool};
+use rlgym_sim_rs::common_values::ORANGE_TEAM;
+use rlgym_sim_rs::conditionals::common_conditions::TimeoutCondition;
+use rlgym_sim_rs::envs::game_match::GameConfig;
+use rlgym_sim_rs::envs::team_sizes::{TeamSizeOption, TeamSizes};
+use rlgym_sim_rs::error::Error;
+use rlgym_sim_rs::gamestates::game_state::GameState;
+use rlgym_sim_rs::gym_builder::GymBuilder;
+use rlgym_sim_rs::make;
+use rlgym_sim_rs::obs_builders::advanced_obs::AdvancedObs;
+use rlgym_sim_rs::reward_functions::common_rewards::misc_rewards::EventReward;
+
+mod common;
+
+/// drives forward with the given throttle
+struct ThrottleOpponent {
+    throttle: f32,
+}
+
+impl Opponent for ThrottleOpponent {
+    fn get_actions(&mut self, obs: &[Vec<f32>], _state: &GameState) -> Vec<Vec<f32>> {
+        obs.iter().map(|_| vec![self.throttle, 0., 0., 0., 0., 0., 0., 0.]).collect()
+    }
+}
+
+fn make_config(pool: OpponentPool) -> make::MakeConfig {
+    common::test_builder(GameConfig::default())
+        .with_terminal_condition(Box::new(TimeoutCondition::new(3)))
+        .with_reward_fn(Box::new(EventReward::new(None, None, None, None, None, None, None, None)))
+        .with_auto_reset(true)
+        .with_opponent_pool(pool)
+        .build_config()
+        .unwrap()
+}
+
+#[test]
+fn opponent_pool_test() {
+    // an empty pool can not pick an opponent
+    let mut empty_pool = OpponentPool::new(None, Some(0));
+    assert!(matches!(empty_pool.sample(), Err(Error::OpponentPool(_))));
+
+    rocketsim_rs::init(None, false);
+
+    let mut pool = OpponentPool::new(Some(ORANGE_TEAM), Some(0));
+    pool.add("forward", Box::new(ThrottleOpponent { throttle: 1. }), 1.);
+    pool.add("reverse", Box::new(ThrottleOpponent { throttle: -1. }), 0.);
+    let mut gym = make::make(make_config(pool), None).unwrap();
+    assert_eq!(gym._game_match.learners(), 1);
+
+    let reset_result = gym.reset(None, None).unwrap();
+    assert_eq!(reset_result.obs.len(), 1);
+    let orange_start = reset_result.state.players[1].car_data.position;
+
+    // only the blue car is given actions, the orange car is driven by the picked opponent
+    let actions = vec![vec![0.; 8]];
+    let mut episodes = 0;
+    for _ in 0..9 {
+        let step_result = gym.step(actions.clone()).unwrap();
+        assert_eq!(step_result.obs.len(), 1);
+        assert_eq!(step_result.rewards.len(), 1);
+        let opponent = step_result.opponent.clone().expect("the opponent should be reported every step");
+        // the other opponent has no weight so it is never picked
+        assert_eq!(opponent.id, "forward");
+        if step_result.done() {
+            episodes += 1;
+            assert_eq!(opponent.outcome, Some(MatchOutcome::Draw));
+            let terminal_state = step_result.terminal_state.unwrap();
+            assert!(terminal_state.players[1].last_actions.throttle == 1.);
+            assert_ne!(terminal_state.players[1].car_data.position.y, orange_start.y);
+        } else {
+            assert_eq!(opponent.outcome, None);
+        }
+    }
+    assert_eq!(episodes, 3);
+
+    // step_into reports the opponent on every step as well
+    let mut obs = vec![0.; AdvancedObs::obs_len(2)];
+    let mut rewards = vec![0.];
+    let info = gym.step_into(actions.clone(), &mut obs, &mut rewards).unwrap();
+    assert!(!info.done());
+    assert_eq!(info.opponent.unwrap().id, "forward");
+
+    let pool = gym._game_match.opponent_pool.as_ref().unwrap();
+    assert_eq!(pool.entries()[0].draws, 3);
+    assert_eq!(pool.entries()[0].games(), 3);
+    assert_eq!(pool.entries()[1].games(), 0);
+}
+
+#[test]
+fn opponent_pool_remove_current_test() {
+    rocketsim_rs::init(None, false);
+
+    let mut pool = OpponentPool::new(Some(ORANGE_TEAM), Some(0));
+    pool.add("forward", Box::new(ThrottleOpponent { throttle: 1. }), 1.);
+    pool.add("reverse", Box::new(ThrottleOpponent { throttle: -1. }), 1.);
+    let mut gym = common::test_builder(GameConfig::default())
+        .with_terminal_condition(Box::new(TimeoutCondition::new(3)))
+        .with_opponent_pool(pool)
+        .with_auto_reset(true)
+        .build()
+        .unwrap();
+    gym.reset(None, None).unwrap();
+
+    // the removed opponent keeps playing until the episode is over
+    let pool = gym._game_match.opponent_pool.as_mut().unwrap();
+    let removed = pool.current_id().unwrap().to_string();
+    assert!(pool.remove(&removed));
+    assert_eq!(pool.entries().len(), 1);
+    let remaining = pool.entries()[0].id.clone();
+
+    let actions = vec![vec![0.; 8]];
+    for _ in 0..3 {
+        let step_result = gym.step(actions.clone()).unwrap();
+        assert_eq!(step_result.opponent.unwrap().id, removed);
+    }
+
+    // the next episode picks from the remaining opponents
+    let step_result = gym.step(actions.clone()).unwrap();
+    assert_eq!(step_result.opponent.unwrap().id, remaining);
+    assert_eq!(gym._game_match.opponent_pool.as_ref().unwrap().current_id(), Some(remaining.as_str()));
+}
+
+#[test]
+fn opponent_pool_no_opponents_test() {
+    let pool = || {
+        let mut pool = OpponentPool::new(None, Some(0));
+        pool.add("forward", Box::new(ThrottleOpponent { throttle: 1. }), 1.);
+        pool
+    };
+
+    // without spawn_opponents every car is blue, so the pool would take over the learners or drive nothing
+    let result = GymBuilder::new()
+        .with_game_config(GameConfig { spawn_opponents: false, ..Default::default() })
+        .with_opponent_pool(pool())
+        .build_config();
+    assert!(matches!(result, Err(Error::Config(_))));
+
+    let team_sizes = TeamSizes::new(vec![TeamSizeOption::new(1, true, 1.), TeamSizeOption::new(1, false, 1.)], Some(0)).unwrap();
+    let result = GymBuilder::new().with_team_sizes(team_sizes).with_opponent_pool(pool()).build_config();
+    assert!(matches!(result, Err(Error::Config(_))));
+
+    // make checks configs that were not built with the builder too
+    let config = make::MakeConfig {
+        game_config: GameConfig { spawn_opponents: false, ..Default::default() },
+        opponent_pool: Some(pool()),
+        ..GymBuilder::new().build_config().unwrap()
+    };
+    assert!(matches!(make::make(config, None), Err(Error::Config(_))));
+}
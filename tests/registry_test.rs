@@ -0,0 +1,149 @@
+use std::{fs, path::PathBuf};
+
+use rlgym_sim_rs::envs::car_bodies::{CarBodies, HitboxType};
+use rlgym_sim_rs::envs::game_match::GameMode;
+use rlgym_sim_rs::envs::match_mode::MatchConfig;
+use rlgym_sim_rs::gamestates::{game_state::GameState, player_data::PlayerData};
+use rlgym_sim_rs::registry::{ComponentSpec, MakeConfigFile, Registry};
+use rlgym_sim_rs::{make_from_config, Error, RewardFn};
+
+const TOML_CONFIG: &str = r#"
+use_single_obs = false
+seed = 5
+
+[game_config]
+team_size = 2
+game_mode = "Hoops"
+car_bodies = { PerTeam = { blue = "Dominus", orange = "Merc" } }
+mutators = { ball_max_speed = 5000.0 }
+
+[match_config]
+game_length = 60.0
+
+[reward_fn]
+name = "CombinedReward"
+params.reward_weights = [1.0, 0.1]
+params.reward_structs = [
+    { name = "EventReward", params = { team_goal = 1.0, concede = -1.0 } },
+    { name = "VelocityPlayerToBallReward" },
+]
+
+[terminal_condition]
+name = "CombinedTerminalConditions"
+params.conditionals = [{ name = "GoalScoredCondition" }, { name = "TimeoutCondition", params = { max_steps = 30 } }]
+
+[obs_builder]
+name = "AdvancedObs"
+
+[action_parser]
+name = "ContinuousAction"
+
+[state_setter]
+name = "WeightedSampleSetter"
+params = { state_setters = [{ name = "DefaultState" }, { name = "RandomState" }], weights = [1.0, 1.0], seed = 1 }
+"#;
+
+const JSON_CONFIG: &str = r#"{
+    "game_config": { "team_size": 1, "spawn_opponents": false },
+    "reward_fn": { "name": "ConstantReward", "params": { "value": 2.0 } },
+    "terminal_condition": { "name": "TimeoutCondition", "params": { "max_steps": 10 } },
+    "obs_builder": { "name": "AdvancedObs" },
+    "action_parser": { "name": "ContinuousAction" },
+    "state_setter": { "name": "DefaultState", "params": { "seed": 0 } }
+}"#;
+
+const RON_CONFIG: &str = r#"(
+    game_config: (team_size: 3, car_config: Breakout),
+    reward_fn: (name: "VelocityReward", params: {"negative": true}),
+    terminal_condition: (name: "NoTouchTimeoutCondition", params: {"max_steps": 100}),
+    obs_builder: (name: "AdvancedObs"),
+    action_parser: (name: "DiscreteAction"),
+    state_setter: (name: "RandomState", params: {"cars_on_ground": true}),
+    auto_reset: true,
+)"#;
+
+struct ConstantReward {
+    value: f32,
+}
+
+impl RewardFn for ConstantReward {
+    fn reset(&mut self, _initial_state: &GameState) {}
+
+    fn get_reward(&mut self, _player: &PlayerData, _state: &GameState) -> f32 {
+        self.value
+    }
+
+    fn get_final_reward(&mut self, _player: &PlayerData, _state: &GameState) -> f32 {
+        self.value
+    }
+}
+
+fn write_config(name: &str, contents: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rlgym_registry_test_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+fn custom_registry() -> Registry {
+    let mut registry = Registry::new();
+    registry.register_reward_fn("ConstantReward", |params, _| Ok(Box::new(ConstantReward { value: params.require("value")? })));
+    registry
+}
+
+#[test]
+fn registry_load_test() {
+    let config_file = MakeConfigFile::load(write_config("config.toml", TOML_CONFIG)).unwrap();
+    assert_eq!(config_file.seed, Some(5));
+    let config = config_file.build(&Registry::new()).unwrap();
+    assert_eq!(config.game_config.game_mode, GameMode::Hoops);
+    assert_eq!(config.game_config.car_bodies, CarBodies::PerTeam { blue: HitboxType::Dominus, orange: HitboxType::Merc });
+    assert_eq!(config.game_config.mutators.ball_max_speed, Some(5000.));
+    assert_eq!(config.game_config.tick_skip, 8);
+    assert_eq!(config.match_config, Some(MatchConfig { game_length: 60., ..Default::default() }));
+    // one builder per agent without single obs
+    assert_eq!(config.obs_builder.len(), 4);
+
+    let config = MakeConfigFile::load(write_config("config.ron", RON_CONFIG)).unwrap().build(&Registry::new()).unwrap();
+    assert_eq!(config.game_config.team_size, 3);
+    assert_eq!(HitboxType::from_config(config.game_config.car_config), HitboxType::Breakout);
+    assert!(config.use_single_obs);
+    assert!(config.auto_reset);
+
+    // custom components are only known to the registry they were added to
+    let json_path = write_config("config.json", JSON_CONFIG);
+    let res = MakeConfigFile::load(&json_path).unwrap().build(&Registry::new());
+    assert!(matches!(res, Err(Error::Config(msg)) if msg.contains("ConstantReward")));
+    let config = MakeConfigFile::load(&json_path).unwrap().build(&custom_registry()).unwrap();
+    assert!(!config.game_config.spawn_opponents);
+
+    // typos in parameters are errors
+    let spec = ComponentSpec {
+        name: "EventReward".to_string(),
+        params: serde_json::from_str(r#"{"gaol": 1.0}"#).unwrap(),
+    };
+    assert!(matches!(Registry::new().build_reward_fn(&spec), Err(Error::Config(msg)) if msg.contains("gaol")));
+
+    let spec = ComponentSpec::new("TimeoutCondition", None);
+    assert!(matches!(Registry::new().build_terminal_condition(&spec), Err(Error::Config(msg)) if msg.contains("max_steps")));
+
+    assert!(matches!(MakeConfigFile::load(write_config("config.yaml", "")), Err(Error::Config(_))));
+}
+
+#[test]
+fn registry_test() {
+    rocketsim_rs::init(None, false);
+
+    let mut gym = make_from_config(write_config("gym.toml", TOML_CONFIG), None, None).unwrap();
+    let reset_result = gym.reset(None, None).unwrap();
+    assert_eq!(reset_result.obs.len(), 4);
+    assert_eq!(reset_result.state.players[0].hitbox_type, HitboxType::Dominus);
+    let step_result = gym.step(vec![vec![1., 0., 0., 0., 0., 0., 0., 0.]; 4]).unwrap();
+    assert_eq!(step_result.rewards.len(), 4);
+
+    let registry = custom_registry();
+    let mut gym = make_from_config(write_config("gym.json", JSON_CONFIG), Some(&registry), None).unwrap();
+    let step_result = gym.step(vec![vec![0.; 8]]).unwrap();
+    assert_eq!(step_result.rewards, vec![2.]);
+}
@@ -0,0 +1,55 @@
+use rlgym_sim_rs::bots::common_bots::RandomBot;
+use rlgym_sim_rs::conditionals::common_conditions::TimeoutCondition;
+use rlgym_sim_rs::envs::game_match::GameConfig;
+use rlgym_sim_rs::make;
+use rlgym_sim_rs::reward_functions::common_rewards::misc_rewards::EventReward;
+use rlgym_sim_rs::seeding::{derive_seed, SeedStream};
+use rlgym_sim_rs::state_setters::random_state::RandomState;
+use rlgym_sim_rs::Gym;
+
+mod common;
+
+fn make_config(seed: Option<u64>) -> make::MakeConfig {
+    let config = common::test_builder(GameConfig::default())
+        .with_terminal_condition(Box::new(TimeoutCondition::new(10)))
+        .with_reward_fn(Box::new(EventReward::new(None, Some(1.), None, None, None, None, None, None)))
+        // unseeded components, these only get a seed from the seed of the gym
+        .with_state_setter(Box::new(RandomState::new(Some(true), Some(true), None, None)))
+        .with_bot(1, Box::new(RandomBot::new(None)))
+        .with_auto_reset(true)
+        .build_config()
+        .unwrap();
+    make::MakeConfig { seed, ..config }
+}
+
+fn trajectory(gym: &mut Gym, steps: usize) -> Vec<u64> {
+    let actions = vec![vec![1., 0.5, 0., 0., 0., 0., 1., 0.]];
+    (0..steps).map(|_| gym.step(actions.clone()).unwrap().state.state_hash()).collect()
+}
+
+#[test]
+fn seeding_test() {
+    rocketsim_rs::init(None, false);
+
+    // the streams are independent of each other
+    assert_ne!(SeedStream::StateSetter.derive(0), SeedStream::Bots.derive(0));
+    assert_ne!(derive_seed(1, 0), derive_seed(0, 1));
+
+    // same seed in the config, over several episodes
+    let mut gym_1 = make::make(make_config(Some(7)), None).unwrap();
+    let mut gym_2 = make::make(make_config(Some(7)), None).unwrap();
+    let hashes = trajectory(&mut gym_1, 35);
+    assert_eq!(hashes, trajectory(&mut gym_2, 35));
+
+    // a different seed gives different states
+    let mut gym_3 = make::make(make_config(Some(8)), None).unwrap();
+    assert_ne!(hashes, trajectory(&mut gym_3, 35));
+
+    // seeding on reset, the tick count is part of the hash so both gyms are fresh
+    let mut gym_4 = make::make(make_config(None), None).unwrap();
+    let mut gym_5 = make::make(make_config(None), None).unwrap();
+    let reset_4 = gym_4.reset(None, Some(3)).unwrap();
+    let reset_5 = gym_5.reset(None, Some(3)).unwrap();
+    assert_eq!(reset_4.state.state_hash(), reset_5.state.state_hash());
+    assert_eq!(trajectory(&mut gym_4, 15), trajectory(&mut gym_5, 15));
+}
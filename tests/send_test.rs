@@ -0,0 +1,44 @@
+use std::thread;
+
+use rlgym_sim_rs::conditionals::common_conditions::TimeoutCondition;
+use rlgym_sim_rs::envs::game_match::GameConfig;
+use rlgym_sim_rs::envs::game_match::GameMatch;
+use rlgym_sim_rs::make;
+use rlgym_sim_rs::reward_functions::common_rewards::misc_rewards::EventReward;
+use rlgym_sim_rs::sim_wrapper::wrapper::RocketsimWrapper;
+use rlgym_sim_rs::Gym;
+
+mod common;
+
+fn assert_send<T: Send>() {}
+
+#[test]
+fn send_test() {
+    assert_send::<Gym>();
+    assert_send::<GameMatch>();
+    assert_send::<RocketsimWrapper>();
+
+    rocketsim_rs::init(None, false);
+
+    let config = common::test_builder(GameConfig::default())
+        .with_terminal_condition(Box::new(TimeoutCondition::new(100)))
+        .with_reward_fn(Box::new(EventReward::new(None, None, None, None, None, None, None, None)))
+        .build_config()
+        .unwrap();
+    // built on this thread, stepped on another and then handed back
+    let mut gym = make::make(config, None).unwrap();
+    let actions = vec![vec![1., 0., 0., 0., 0., 0., 0., 0.]; 2];
+
+    let first_tick = gym.step(actions.clone()).unwrap().tick_count;
+    let thread_actions = actions.clone();
+    let handle = thread::spawn(move || {
+        let tick_count = gym.step(thread_actions).unwrap().tick_count;
+        (gym, tick_count)
+    });
+    let (mut gym, thread_tick) = handle.join().unwrap();
+    assert!(thread_tick > first_tick);
+
+    let step_result = gym.step(actions).unwrap();
+    assert!(step_result.tick_count > thread_tick);
+    assert_eq!(step_result.obs.len(), 2);
+}
@@ -0,0 +1,210 @@
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+use rlgym_sim_rs::action_parsers::test_parser::TestAction;
+use rlgym_sim_rs::bots::opponent_pool::{Opponent, OpponentPool};
+use rlgym_sim_rs::conditionals::common_conditions::{GoalScoredCondition, TimeoutCondition};
+use rlgym_sim_rs::conditionals::extra_conditions::CombinedTerminalConditions;
+use rlgym_sim_rs::envs::game_match::GameConfig;
+use rlgym_sim_rs::envs::team_sizes::{TeamSizeOption, TeamSizes};
+use rlgym_sim_rs::gamestates::{game_state::GameState, player_data::PlayerData};
+use rlgym_sim_rs::make;
+use rlgym_sim_rs::obs_builders::advanced_obs::AdvancedObs;
+use rlgym_sim_rs::reward_functions::common_rewards::misc_rewards::EventReward;
+use rlgym_sim_rs::spaces::Space;
+use rlgym_sim_rs::{ActionParser, Error, Gym, RewardFn, TerminalCondition};
+
+mod common;
+
+/// reward function that does not implement snapshot
+struct NoSnapshotReward {}
+
+impl RewardFn for NoSnapshotReward {
+    fn reset(&mut self, _initial_state: &GameState) {}
+
+    fn get_reward(&mut self, _player: &PlayerData, _state: &GameState) -> f32 {
+        0.
+    }
+
+    fn get_final_reward(&mut self, _player: &PlayerData, _state: &GameState) -> f32 {
+        0.
+    }
+}
+
+/// action parser that jumps at random so that the parsed actions depend on its rng
+struct RandomJumpAction {
+    rng: SmallRng,
+}
+
+impl ActionParser for RandomJumpAction {
+    fn get_action_space(&mut self) -> Space {
+        TestAction::new().get_action_space()
+    }
+
+    fn parse_actions(&mut self, actions: Vec<Vec<f32>>, _state: &GameState) -> Vec<Vec<f32>> {
+        actions
+            .into_iter()
+            .map(|mut action| {
+                action[5] = if self.rng.random_bool(0.3) { 1. } else { 0. };
+                action
+            })
+            .collect()
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
+    }
+
+    fn snapshot(&self) -> Option<Box<dyn ActionParser>> {
+        Some(Box::new(RandomJumpAction { rng: self.rng.clone() }))
+    }
+}
+
+/// opponent whose actions depend on how many steps it has played, `copyable` controls snapshot support
+struct CountingOpponent {
+    steps: u32,
+    copyable: bool,
+}
+
+impl Opponent for CountingOpponent {
+    fn get_actions(&mut self, obs: &[Vec<f32>], _state: &GameState) -> Vec<Vec<f32>> {
+        self.steps += 1;
+        let steer = if self.steps % 6 < 3 { 1. } else { -1. };
+        obs.iter().map(|_| vec![1., steer, 0., 0., 0., 0., 0., 0.]).collect()
+    }
+
+    fn snapshot(&self) -> Option<Box<dyn Opponent>> {
+        if self.copyable {
+            Some(Box::new(CountingOpponent { steps: self.steps, copyable: true }))
+        } else {
+            None
+        }
+    }
+}
+
+fn pool_gym(copyable: bool) -> Gym {
+    let mut pool = OpponentPool::new(None, Some(0));
+    pool.add("a", Box::new(CountingOpponent { steps: 0, copyable }), 1.);
+    pool.add("b", Box::new(CountingOpponent { steps: 0, copyable }), 1.);
+    let team_sizes = TeamSizes::new(vec![TeamSizeOption::new(1, true, 1.), TeamSizeOption::new(2, true, 1.)], Some(0))
+        .unwrap()
+        .with_pad_obs_len(AdvancedObs::obs_len(4));
+    common::test_builder(GameConfig::default())
+        .with_terminal_condition(Box::new(TimeoutCondition::new(7)))
+        .with_team_sizes(team_sizes)
+        .with_opponent_pool(pool)
+        .with_auto_reset(true)
+        .build()
+        .unwrap()
+}
+
+/// obs, rewards, opponent id and player count of one step
+type PoolStepRecord = (Vec<Vec<f32>>, Vec<f32>, Option<String>, usize);
+
+/// steps through several episodes so that the team size and opponent are sampled again
+fn pool_rollout(gym: &mut Gym, start: usize) -> Vec<PoolStepRecord> {
+    let mut results = Vec::new();
+    for i in start..start + 30 {
+        let step_result = gym.step(actions(i)[..2].to_vec()).unwrap();
+        let players = step_result.state.players.len();
+        results.push((step_result.obs, step_result.rewards, step_result.opponent.map(|opponent| opponent.id), players));
+    }
+    results
+}
+
+fn make_config(reward_fn: Box<dyn RewardFn>) -> make::MakeConfig {
+    let term_conds: Vec<Box<dyn TerminalCondition>> = vec![Box::new(TimeoutCondition::new(1000)), Box::new(GoalScoredCondition::new())];
+    common::test_builder(GameConfig { team_size: 2, ..Default::default() })
+        .with_terminal_condition(Box::new(CombinedTerminalConditions::new(term_conds)))
+        .with_reward_fn(reward_fn)
+        .build_config()
+        .unwrap()
+}
+
+fn actions(i: usize) -> Vec<Vec<f32>> {
+    let steer = if i % 20 < 10 { 1. } else { -1. };
+    let jump = if i.is_multiple_of(15) { 1. } else { 0. };
+    vec![vec![1., steer, 0., 0., 0., jump, 1., 0.]; 4]
+}
+
+/// obs, rewards, terminated, tick count and bumps of one step
+type StepRecord = (Vec<Vec<f32>>, Vec<f32>, bool, u64, Vec<u32>);
+
+/// steps and then resets, returning everything that should be identical between runs
+fn rollout(gym: &mut Gym, start: usize) -> Vec<StepRecord> {
+    let mut results = Vec::new();
+    for i in start..start + 40 {
+        let step_result = gym.step(actions(i)).unwrap();
+        let bumps = step_result.stats.iter().map(|stats| stats.bumps_count).collect();
+        results.push((step_result.obs, step_result.rewards, step_result.terminated, step_result.tick_count, bumps));
+    }
+    let reset_result = gym.reset(None, None).unwrap();
+    results.push((reset_result.obs, Vec::new(), false, reset_result.tick_count, Vec::new()));
+    results
+}
+
+#[test]
+fn snapshot_test() {
+    rocketsim_rs::init(None, false);
+
+    let reward_fn = Box::new(EventReward::new(Some(1.), Some(1.), Some(-1.), Some(0.1), None, None, None, None));
+    let mut gym = make::make(make_config(reward_fn), None).unwrap();
+    for i in 0..30 {
+        gym.step(actions(i)).unwrap();
+    }
+
+    let snapshot = gym.snapshot().unwrap();
+    assert_eq!(snapshot.state.tick_num, gym._prev_state.tick_num);
+    assert_eq!(snapshot.sim_state.tick_count, gym._prev_state.tick_num);
+    assert_eq!(snapshot.stats.len(), 4);
+
+    let first = rollout(&mut gym, 30);
+    gym.restore(&snapshot).unwrap();
+    assert_eq!(gym._prev_state.tick_num, snapshot.state.tick_num);
+    let second = rollout(&mut gym, 30);
+    // the snapshot can be restored more than once
+    gym.restore(&snapshot).unwrap();
+    let third = rollout(&mut gym, 30);
+
+    assert_eq!(first, second, "restoring a snapshot did not reproduce the same steps");
+    assert_eq!(first, third, "restoring a snapshot a second time did not reproduce the same steps");
+
+    // the rng of the action parser is restored as well
+    let config = make::MakeConfig {
+        action_parser: Box::new(RandomJumpAction { rng: SmallRng::seed_from_u64(0) }),
+        ..make_config(Box::new(EventReward::new(None, None, None, None, None, None, None, None)))
+    };
+    let mut gym = make::make(config, None).unwrap();
+    gym.reset(None, Some(0)).unwrap();
+    for i in 0..10 {
+        gym.step(actions(i)).unwrap();
+    }
+    let snapshot = gym.snapshot().unwrap();
+    let first = rollout(&mut gym, 10);
+    gym.restore(&snapshot).unwrap();
+    let second = rollout(&mut gym, 10);
+    assert_eq!(first, second, "restoring a snapshot with a random action parser did not reproduce the same steps");
+
+    // components without snapshot support
+    let gym = make::make(make_config(Box::new(NoSnapshotReward {})), None).unwrap();
+    assert!(matches!(gym.snapshot(), Err(Error::Snapshot("reward function"))));
+
+    // the team sizes rng, the opponent pool and the opponent obs are restored as well
+    let mut gym = pool_gym(true);
+    gym.reset(None, None).unwrap();
+    for i in 0..10 {
+        gym.step(actions(i)[..2].to_vec()).unwrap();
+    }
+    let snapshot = gym.snapshot().unwrap();
+    let first = pool_rollout(&mut gym, 10);
+    let first_pool: Vec<_> = gym._game_match.opponent_pool.as_ref().unwrap().entries().iter().map(|entry| entry.games()).collect();
+    gym.restore(&snapshot).unwrap();
+    let second = pool_rollout(&mut gym, 10);
+    let second_pool: Vec<_> = gym._game_match.opponent_pool.as_ref().unwrap().entries().iter().map(|entry| entry.games()).collect();
+
+    assert_eq!(first, second, "restoring a snapshot with team sizes and an opponent pool did not reproduce the same steps");
+    assert_eq!(first_pool, second_pool, "the results of the opponent pool were not restored");
+
+    // opponents without snapshot support
+    let mut gym = pool_gym(false);
+    gym.reset(None, None).unwrap();
+    assert!(matches!(gym.snapshot(), Err(Error::Snapshot("opponent"))));
+}
@@ -0,0 +1,32 @@
+use rlgym_sim_rs::action_parsers::{action_parser::ActionParser, continous_act::ContinuousAction};
+use rlgym_sim_rs::{DiscreteAction, Dtype, Space};
+
+#[test]
+fn spaces_test() {
+    let space = Space::Box { low: -1., high: 1., shape: vec![2, 3], dtype: Dtype::Float32 };
+    assert_eq!(space.flat_len(), 6);
+    assert!(space.contains(&space.zeros()));
+    assert!(!space.contains(&[0., 0., 0., 0., 0., 2.]));
+    assert!(!space.contains(&[0.; 5]));
+
+    let ints = Space::Box { low: 0., high: 10., shape: vec![2], dtype: Dtype::Int64 };
+    assert!(ints.contains(&[3., 10.]));
+    assert!(!ints.contains(&[3.5, 1.]));
+
+    let dict = Space::Dict(vec![
+        ("jump".to_string(), Space::Discrete(2)),
+        ("inputs".to_string(), Space::Tuple(vec![Space::MultiDiscrete(vec![3, 3]), Space::box_f32(0., 1., 2)])),
+    ]);
+    assert_eq!(dict.flat_len(), 5);
+    assert!(dict.contains(&[1., 2., 0., 0.5, 1.]));
+    assert!(!dict.contains(&[2., 2., 0., 0.5, 1.]));
+    assert!(!dict.contains(&[1., 3., 0., 0.5, 1.]));
+    assert!(!dict.contains(&[1., 2., 0., 0.5, 1.5]));
+
+    // the parsers report the action they take, not the action they give to the sim
+    assert_eq!(DiscreteAction::new().get_action_space(), Space::MultiDiscrete(vec![3, 3, 3, 3, 3, 2, 2, 2]));
+    assert_eq!(ContinuousAction::new().get_action_space(), Space::box_f32(-1., 1., 8));
+
+    let json = serde_json::to_string(&dict).unwrap();
+    assert_eq!(serde_json::from_str::<Space>(&json).unwrap(), dict);
+}
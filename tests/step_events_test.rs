@@ -0,0 +1,54 @@
+use rlgym_sim_rs::conditionals::common_conditions::TimeoutCondition;
+use rlgym_sim_rs::envs::game_match::GameConfig;
+use rlgym_sim_rs::gamestates::player_data::StepEvents;
+use rlgym_sim_rs::make;
+use rlgym_sim_rs::reward_functions::common_rewards::misc_rewards::EventReward;
+
+mod common;
+
+#[test]
+fn step_events_test() {
+    rocketsim_rs::init(None, false);
+
+    let tick_skip = 8;
+    let config = common::test_builder(GameConfig { tick_skip, ..Default::default() })
+        .with_terminal_condition(Box::new(TimeoutCondition::new(1000)))
+        .with_reward_fn(Box::new(EventReward::new(None, None, None, None, None, None, None, None)))
+        .build_config()
+        .unwrap();
+    let mut gym = make::make(config, None).unwrap();
+
+    // nothing has been stepped since the reset
+    let reset_state = gym.reset(None, None).unwrap().state;
+    assert!(reset_state.players.iter().all(|player| player.step_events == StepEvents::default()));
+
+    // both cars drive straight at the ball on the kickoff
+    let actions = vec![vec![1., 0., 0., 0., 0., 0., 1., 0.]; 2];
+    let mut total_touches = 0;
+    for i in 0..80 {
+        let prev_tick = gym._prev_state.tick_num;
+        let step_result = gym.step(actions.clone()).unwrap();
+        for player in &step_result.state.players {
+            let events = player.step_events;
+            if i == 0 {
+                assert!(events.touched_ground, "cars start the kickoff on the ground");
+            }
+            if events.touches > 0 {
+                assert!(player.ball_touched);
+                assert!(events.first_touch_tick > prev_tick && events.first_touch_tick <= events.last_touch_tick);
+                assert!(events.last_touch_tick <= step_result.state.tick_num);
+            } else {
+                assert_eq!(events.first_touch_tick, 0);
+            }
+            assert!(events.touches as usize <= tick_skip);
+            assert_eq!(events.demos_dealt, 0);
+            assert_eq!(events.demos_received, 0);
+            total_touches += events.touches;
+        }
+        // every bump has a car on both ends
+        let bumps_dealt: u32 = step_result.state.players.iter().map(|player| player.step_events.bumps_dealt).sum();
+        let bumps_received: u32 = step_result.state.players.iter().map(|player| player.step_events.bumps_received).sum();
+        assert_eq!(bumps_dealt, bumps_received);
+    }
+    assert!(total_touches > 0, "the ball should have been hit on the kickoff");
+}
@@ -0,0 +1,72 @@
+use ndarray::Array2;
+use rlgym_sim_rs::conditionals::common_conditions::TimeoutCondition;
+use rlgym_sim_rs::envs::game_match::GameConfig;
+use rlgym_sim_rs::make;
+use rlgym_sim_rs::obs_builders::advanced_obs::AdvancedObs;
+use rlgym_sim_rs::reward_functions::common_rewards::misc_rewards::EventReward;
+use rlgym_sim_rs::{Error, ObsBuilder};
+
+mod common;
+
+fn make_config(team_size: usize) -> make::MakeConfig {
+    common::test_builder(GameConfig { team_size, ..Default::default() })
+        .with_terminal_condition(Box::new(TimeoutCondition::new(10)))
+        .with_reward_fn(Box::new(EventReward::new(None, Some(1.), None, None, None, None, None, None)))
+        .build_config()
+        .unwrap()
+}
+
+#[test]
+fn step_into_test() {
+    rocketsim_rs::init(None, false);
+
+    let team_size = 2;
+    let players = team_size * 2;
+    let obs_len = AdvancedObs::obs_len(players);
+    // same seed so both gyms should be in the same state
+    let mut gym = make::make(make_config(team_size), None).unwrap();
+    let mut gym_into = make::make(make_config(team_size), None).unwrap();
+
+    let mut obs = vec![0.; players * obs_len];
+    let mut obs_array = Array2::<f32>::zeros((players, obs_len));
+    let mut rewards = vec![0.; players];
+    let actions = vec![vec![1., 0., 0., 0., 0., 0., 0., 1.]; players];
+    for i in 0..10 {
+        let step_result = gym.step(actions.clone()).unwrap();
+        let info = if i % 2 == 0 {
+            let info = gym_into.step_into(actions.clone(), &mut obs, &mut rewards).unwrap();
+            assert_eq!(obs, step_result.obs.concat());
+            info
+        } else {
+            let info = gym_into.step_into_array(actions.clone(), obs_array.view_mut(), &mut rewards).unwrap();
+            for (row, step_obs) in obs_array.rows().into_iter().zip(&step_result.obs) {
+                assert_eq!(row.to_vec(), *step_obs);
+            }
+            info
+        };
+        assert_eq!(rewards, step_result.rewards);
+        assert_eq!(info.terminated, step_result.terminated);
+        assert_eq!(info.truncated, step_result.truncated);
+        assert_eq!(info.tick_count, step_result.tick_count);
+    }
+
+    // the native implementation matches build_obs
+    let state = gym_into._prev_state.clone();
+    let config = gym_into._game_match.get_config();
+    let mut obs_builder = AdvancedObs::new();
+    let mut player_obs = vec![0.; obs_len];
+    for player in &state.players {
+        obs_builder.build_obs_into(player, &state, &config, &mut player_obs).unwrap();
+        assert_eq!(player_obs, obs_builder.build_obs(player, &state, &config));
+    }
+
+    // wrongly sized buffers
+    let mut short_obs = vec![0.; players * obs_len - players];
+    let res = gym_into.step_into(actions.clone(), &mut short_obs, &mut rewards);
+    assert!(matches!(res, Err(Error::BufferLength { buffer: "obs", .. })));
+    let res = gym_into.step_into(actions.clone(), &mut obs, &mut rewards[1..]);
+    assert!(matches!(res, Err(Error::BufferLength { buffer: "rewards", len: 3, expected: 4 })));
+    let mut transposed = Array2::<f32>::zeros((obs_len, players));
+    let res = gym_into.step_into_array(actions.clone(), transposed.view_mut().reversed_axes(), &mut rewards);
+    assert!(matches!(res, Err(Error::BufferLayout("obs"))));
+}
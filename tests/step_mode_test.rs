@@ -0,0 +1,81 @@
+use std::sync::{Arc, Mutex};
+
+use rlgym_sim_rs::conditionals::common_conditions::TimeoutCondition;
+use rlgym_sim_rs::envs::game_match::{GameConfig, StepMode};
+use rlgym_sim_rs::gamestates::{game_state::GameState, player_data::PlayerData};
+use rlgym_sim_rs::make;
+use rlgym_sim_rs::reward_functions::reward_fn::RewardFn;
+use rlgym_sim_rs::Gym;
+
+mod common;
+
+/// records the ticks of the sub-states that it was given
+struct SubStepReward {
+    ticks: Arc<Mutex<Vec<u64>>>,
+}
+
+impl RewardFn for SubStepReward {
+    fn reset(&mut self, _initial_state: &GameState) {}
+
+    fn sub_steps(&mut self, states: &[GameState]) {
+        self.ticks.lock().unwrap().extend(states.iter().map(|state| state.tick_num));
+    }
+
+    fn get_reward(&mut self, _player: &PlayerData, _state: &GameState) -> f32 {
+        0.
+    }
+
+    fn get_final_reward(&mut self, _player: &PlayerData, _state: &GameState) -> f32 {
+        0.
+    }
+}
+
+fn make_gym(step_mode: StepMode, tick_skip: usize, ticks: Arc<Mutex<Vec<u64>>>) -> Gym {
+    let config = common::test_builder(GameConfig { tick_skip, step_mode, ..Default::default() })
+        .with_terminal_condition(Box::new(TimeoutCondition::new(1000)))
+        .with_reward_fn(Box::new(SubStepReward { ticks }))
+        .build_config()
+        .unwrap();
+    make::make(config, None).unwrap()
+}
+
+#[test]
+fn step_mode_test() {
+    rocketsim_rs::init(None, false);
+
+    let tick_skip = 8;
+    let actions = vec![vec![1., 0., 0., 0., 0., 0., 1., 0.]; 2];
+
+    // the default returns the state after every tick of the step
+    let ticks = Arc::new(Mutex::new(Vec::new()));
+    let mut gym = make_gym(StepMode::Final, tick_skip, ticks.clone());
+    for _ in 0..3 {
+        let prev_tick = gym._prev_state.tick_num;
+        let step_result = gym.step(actions.clone()).unwrap();
+        assert_eq!(step_result.state.tick_num, prev_tick + tick_skip as u64);
+        assert!(step_result.sub_states.is_empty());
+        // nothing is stepped after decoding so the returned state is the current state of the sim
+        let sim_state = gym._game_match.sim_wrapper.get_sim_state();
+        assert_eq!(sim_state.ball.pos.y, step_result.state.ball.position.y);
+        assert_eq!(sim_state.cars[0].state.pos.y, step_result.state.players[0].car_data.position.y);
+    }
+    assert!(ticks.lock().unwrap().is_empty());
+
+    // the old behavior decodes after the first tick
+    let mut gym = make_gym(StepMode::FirstTick, tick_skip, Arc::new(Mutex::new(Vec::new())));
+    let prev_tick = gym._game_match.sim_wrapper.get_sim_state().tick_count;
+    let step_result = gym.step(actions.clone()).unwrap();
+    assert_eq!(step_result.state.tick_num, prev_tick + 1);
+    assert_eq!(gym._game_match.sim_wrapper.get_sim_state().tick_count, prev_tick + tick_skip as u64);
+
+    // history keeps the states of the ticks before the returned state and hands them to the components
+    let ticks = Arc::new(Mutex::new(Vec::new()));
+    let mut gym = make_gym(StepMode::History, tick_skip, ticks.clone());
+    let prev_tick = gym._prev_state.tick_num;
+    let step_result = gym.step(actions).unwrap();
+    assert_eq!(step_result.state.tick_num, prev_tick + tick_skip as u64);
+    let sub_ticks = step_result.sub_states.iter().map(|state| state.tick_num).collect::<Vec<_>>();
+    let expected = (1..tick_skip as u64).map(|i| prev_tick + i).collect::<Vec<_>>();
+    assert_eq!(sub_ticks, expected);
+    assert_eq!(*ticks.lock().unwrap(), expected);
+}
@@ -0,0 +1,48 @@
+use rlgym_sim_rs::conditionals::common_conditions::{GoalScoredCondition, TimeoutCondition};
+use rlgym_sim_rs::conditionals::extra_conditions::CombinedTerminalConditions;
+use rlgym_sim_rs::envs::game_match::GameConfig;
+use rlgym_sim_rs::make;
+use rlgym_sim_rs::reward_functions::common_rewards::misc_rewards::EventReward;
+use rlgym_sim_rs::TerminalCondition;
+
+mod common;
+
+#[test]
+fn step_result_test() {
+    rocketsim_rs::init(None, false);
+
+    let tick_skip = 8;
+    let term_conds: Vec<Box<dyn TerminalCondition>> = vec![Box::new(TimeoutCondition::new(5)), Box::new(GoalScoredCondition::new())];
+    let config = common::test_builder(GameConfig { tick_skip, ..Default::default() })
+        .with_terminal_condition(Box::new(CombinedTerminalConditions::new(term_conds)))
+        .with_reward_fn(Box::new(EventReward::new(None, None, None, None, None, None, None, None)))
+        .build_config()
+        .unwrap();
+    let mut gym = make::make(config, None).unwrap();
+
+    let reset_result = gym.reset(None, None).unwrap();
+    assert_eq!(reset_result.obs.len(), 2);
+    assert_eq!(reset_result.stats.len(), reset_result.state.players.len());
+    let mut prev_tick = reset_result.tick_count;
+
+    let actions = vec![vec![1., 0., 0., 0., 0., 0., 0., 0.]; 2];
+    for i in 0..5 {
+        let step_result = gym.step(actions.clone()).unwrap();
+        assert_eq!(step_result.obs.len(), 2);
+        assert_eq!(step_result.rewards.len(), 2);
+        assert_eq!(step_result.stats.len(), step_result.state.players.len());
+        assert_eq!(step_result.tick_count, prev_tick + tick_skip as u64);
+        prev_tick = step_result.tick_count;
+
+        if i == 4 {
+            assert!(step_result.truncated, "timeout condition should have truncated the episode");
+            assert!(step_result.terminal_reasons.contains(&"TimeoutCondition"));
+            let (_obs, _rewards, done, info) = step_result.into_legacy();
+            assert!(done);
+            assert_eq!(info["truncated"], 1.);
+            assert!(info.contains_key("result"));
+        } else {
+            assert!(!step_result.truncated);
+        }
+    }
+}
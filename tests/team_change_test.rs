@@ -69,6 +69,7 @@ fn team_change_test() {
         gravity: 1.,
         boost_consumption: 1.,
         car_config: CarConfig::octane(),
+        ..Default::default()
     };
     // let obs_build: Box<dyn ObsBuilder> = Box::new(AdvancedObs::new());
     let mut obs_build_vec: Vec<Box<dyn ObsBuilder>> = Vec::new();
@@ -84,84 +85,85 @@ fn team_change_test() {
         use_single_obs: true,
         action_parser: act_parse,
         state_setter: state_set, 
+        ..Default::default()
     };
-    let mut gym = make::make(game_config, None);
+    let mut gym = make::make(game_config, None).unwrap();
 
     // -- start testing self-play=false --
 
-    gym.reset(None, None);
+    gym.reset(None, None).unwrap();
     
-    let (obs, _, _, _) = gym.step(actions);
+    let (obs, _, _, _) = gym.step(actions).unwrap().into_legacy();
     let length = obs.len();
     assert!(length == 1, "obs was not of correct length for 1v0, was: {length}");
     assert!(gym._prev_state.players.iter().all(|player| (player.car_id <= 6 && player.car_id >= 0)), "car ids in state did not work correctly in 1v0");
 
-    let new_config = GameConfig { gravity: 1., boost_consumption: 1., team_size: 2, tick_skip: 1, spawn_opponents: false, car_config: CarConfig::octane(), };
+    let new_config = GameConfig { gravity: 1., boost_consumption: 1., team_size: 2, tick_skip: 1, spawn_opponents: false, car_config: CarConfig::octane(), ..Default::default() };
     let mut obs_build_vec: Vec<Box<dyn ObsBuilder>> = Vec::new();
     for _ in 0..2 {
         obs_build_vec.push(Box::new(AdvancedObs::new()));
     }
-    gym.update_config(new_config, Some(obs_build_vec));
+    gym.update_config(new_config, Some(obs_build_vec)).unwrap();
 
-    gym.reset(None, None);
+    gym.reset(None, None).unwrap();
     let actions = vec![vec![2., 1., 0., 1., 0., 1., 0., 1.]; 2];
-    let (obs, _, _, _) = gym.step(actions);
+    let (obs, _, _, _) = gym.step(actions).unwrap().into_legacy();
     let length = obs.len();
     assert!(length == 2, "obs was not of correct length for 2v0, was: {length}");
     assert!(gym._prev_state.players.iter().all(|player| (player.car_id <= 6 && player.car_id >= 0)), "car ids in state did not work correctly in 2v0");
 
-    let new_config = GameConfig { gravity: 1., boost_consumption: 1., team_size: 3, tick_skip: 1, spawn_opponents: false, car_config: CarConfig::octane(), };
+    let new_config = GameConfig { gravity: 1., boost_consumption: 1., team_size: 3, tick_skip: 1, spawn_opponents: false, car_config: CarConfig::octane(), ..Default::default() };
     let mut obs_build_vec: Vec<Box<dyn ObsBuilder>> = Vec::new();
     for _ in 0..3 {
         obs_build_vec.push(Box::new(AdvancedObs::new()));
     }
-    gym.update_config(new_config, Some(obs_build_vec));
-    gym.reset(None, None);
+    gym.update_config(new_config, Some(obs_build_vec)).unwrap();
+    gym.reset(None, None).unwrap();
     let actions = vec![vec![2., 1., 0., 1., 0., 1., 0., 1.]; 3];
-    let (obs, _, _, _) = gym.step(actions);
+    let (obs, _, _, _) = gym.step(actions).unwrap().into_legacy();
     let length = obs.len();
     assert!(length == 3, "obs was not of correct length for 3v0, was: {length}");
     assert!(gym._prev_state.players.iter().all(|player| (player.car_id <= 6 && player.car_id >= 0)), "car ids in state did not work correctly in 3v0");
 
     // -- start of self-play=true --
 
-    let new_config = GameConfig { gravity: 1., boost_consumption: 1., team_size: 1, tick_skip: 1, spawn_opponents: true, car_config: CarConfig::octane(), };
+    let new_config = GameConfig { gravity: 1., boost_consumption: 1., team_size: 1, tick_skip: 1, spawn_opponents: true, car_config: CarConfig::octane(), ..Default::default() };
     let mut obs_build_vec: Vec<Box<dyn ObsBuilder>> = Vec::new();
     for _ in 0..2 {
         obs_build_vec.push(Box::new(AdvancedObs::new()));
     }
-    gym.update_config(new_config, Some(obs_build_vec));
+    gym.update_config(new_config, Some(obs_build_vec)).unwrap();
 
-    gym.reset(None, None);
+    gym.reset(None, None).unwrap();
     let actions = vec![vec![2., 1., 0., 1., 0., 1., 0., 1.]; 2];
-    let (obs, _, _, _) = gym.step(actions);
+    let (obs, _, _, _) = gym.step(actions).unwrap().into_legacy();
     let length = obs.len();
     assert!(length == 2, "obs was not of correct length for 1v1, was: {length}");
     assert!(gym._prev_state.players.iter().all(|player| (player.car_id <= 6 && player.car_id >= 0)), "car ids in state did not work correctly in 1v1");
 
-    let new_config = GameConfig { gravity: 1., boost_consumption: 1., team_size: 2, tick_skip: 1, spawn_opponents: true, car_config: CarConfig::octane(), };
+    let new_config = GameConfig { gravity: 1., boost_consumption: 1., team_size: 2, tick_skip: 1, spawn_opponents: true, car_config: CarConfig::octane(), ..Default::default() };
     let mut obs_build_vec: Vec<Box<dyn ObsBuilder>> = Vec::new();
     for _ in 0..4 {
         obs_build_vec.push(Box::new(AdvancedObs::new()));
     }
-    gym.update_config(new_config, Some(obs_build_vec));
+    gym.update_config(new_config, Some(obs_build_vec)).unwrap();
 
-    gym.reset(None, None);
+    gym.reset(None, None).unwrap();
     let actions = vec![vec![2., 1., 0., 1., 0., 1., 0., 1.]; 4];
-    let (obs, _, _, _) = gym.step(actions);
+    let (obs, _, _, _) = gym.step(actions).unwrap().into_legacy();
     let length = obs.len();
     assert!(length == 4, "obs was not of correct length for 2v2, was: {length}");
     assert!(gym._prev_state.players.iter().all(|player| (player.car_id <= 6 && player.car_id >= 0)), "car ids in state did not work correctly in 2v2");
 
-    let new_config = GameConfig { gravity: 1., boost_consumption: 1., team_size: 3, tick_skip: 1, spawn_opponents: true, car_config: CarConfig::octane(), };
+    let new_config = GameConfig { gravity: 1., boost_consumption: 1., team_size: 3, tick_skip: 1, spawn_opponents: true, car_config: CarConfig::octane(), ..Default::default() };
     let mut obs_build_vec: Vec<Box<dyn ObsBuilder>> = Vec::new();
     for _ in 0..6 {
         obs_build_vec.push(Box::new(AdvancedObs::new()));
     }
-    gym.update_config(new_config, Some(obs_build_vec));
-    gym.reset(None, None);
+    gym.update_config(new_config, Some(obs_build_vec)).unwrap();
+    gym.reset(None, None).unwrap();
     let actions = vec![vec![2., 1., 0., 1., 0., 1., 0., 1.]; 6];
-    let (obs, _, _, _) = gym.step(actions);
+    let (obs, _, _, _) = gym.step(actions).unwrap().into_legacy();
     let length = obs.len();
     assert!(length == 6, "obs was not of correct length for 3v3, was: {length}");
     assert!(gym._prev_state.players.iter().all(|player| (player.car_id <= 6 && player.car_id >= 0)), "car ids in state did not work correctly in 3v3");
@@ -0,0 +1,66 @@
+use rlgym_sim_rs::conditionals::common_conditions::TimeoutCondition;
+use rlgym_sim_rs::envs::game_match::GameConfig;
+use rlgym_sim_rs::envs::team_sizes::{TeamSizeOption, TeamSizes};
+use rlgym_sim_rs::error::Error;
+use rlgym_sim_rs::make;
+use rlgym_sim_rs::obs_builders::advanced_obs::AdvancedObs;
+use rlgym_sim_rs::obs_builders::obs_builder::ObsBuilder;
+use rlgym_sim_rs::reward_functions::common_rewards::misc_rewards::EventReward;
+
+mod common;
+
+#[test]
+fn team_sizes_test() {
+    rocketsim_rs::init(None, false);
+
+    let obs_len = AdvancedObs::obs_len(4);
+    let team_sizes = TeamSizes::new(vec![TeamSizeOption::new(1, true, 1.), TeamSizeOption::new(2, true, 1.)], Some(0))
+        .unwrap()
+        .with_obs_builder_factory(Box::new(|| Box::new(AdvancedObs::new()) as Box<dyn ObsBuilder>))
+        .with_pad_obs_len(obs_len);
+    assert_eq!(team_sizes.max_agents(), 4);
+
+    let config = common::test_builder(GameConfig::default())
+        .with_terminal_condition(Box::new(TimeoutCondition::new(1000)))
+        .with_reward_fn(Box::new(EventReward::new(None, None, None, None, None, None, None, None)))
+        .with_obs_builders(vec![Box::new(AdvancedObs::new()), Box::new(AdvancedObs::new())])
+        .with_team_sizes(team_sizes)
+        .build_config()
+        .unwrap();
+    let mut gym = make::make(config, None).unwrap();
+
+    // every batch has 4 agents whichever size was sampled
+    let actions = vec![vec![1., 0., 0., 0., 0., 0., 0., 0.]; 4];
+    let mut seen_sizes = Vec::new();
+    for _ in 0..20 {
+        let reset_result = gym.reset(None, None).unwrap();
+        let players = reset_result.state.players.len();
+        if !seen_sizes.contains(&players) {
+            seen_sizes.push(players);
+        }
+        assert_eq!(reset_result.obs.len(), 4);
+        assert!(reset_result.obs.iter().all(|player_obs| player_obs.len() == obs_len));
+        assert_eq!(reset_result.agent_mask.iter().filter(|active| **active).count(), players);
+        assert!(reset_result.obs[players..].iter().flatten().all(|val| *val == 0.));
+
+        let step_result = gym.step(actions.clone()).unwrap();
+        assert_eq!(step_result.obs.len(), 4);
+        assert_eq!(step_result.rewards.len(), 4);
+        assert!(step_result.rewards[players..].iter().all(|reward| *reward == 0.));
+        assert_eq!(step_result.agent_mask, reset_result.agent_mask);
+
+        let mut obs = vec![0.; 4 * obs_len];
+        let mut rewards = vec![0.; 4];
+        let info = gym.step_into(actions.clone(), &mut obs, &mut rewards).unwrap();
+        assert_eq!(info.active_agents, players);
+    }
+    seen_sizes.sort_unstable();
+    assert_eq!(seen_sizes, vec![2, 4]);
+}
+
+#[test]
+fn team_sizes_invalid_test() {
+    assert!(matches!(TeamSizes::new(Vec::new(), Some(0)), Err(Error::Config(_))));
+    assert!(matches!(TeamSizes::new(vec![TeamSizeOption::new(1, true, 0.)], Some(0)), Err(Error::Config(_))));
+    assert!(matches!(TeamSizes::new(vec![TeamSizeOption::new(1, true, -1.)], Some(0)), Err(Error::Config(_))));
+}
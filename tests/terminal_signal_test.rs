@@ -0,0 +1,36 @@
+use rlgym_sim_rs::conditionals::common_conditions::{GoalScoredCondition, TimeoutCondition};
+use rlgym_sim_rs::conditionals::extra_conditions::CombinedTerminalConditions;
+use rlgym_sim_rs::gamestates::game_state::GameState;
+use rlgym_sim_rs::{TerminalCondition, TerminalSignal};
+
+#[test]
+fn terminal_signal_test() {
+    let mut state = GameState::new();
+
+    assert_eq!(TerminalSignal::truncated_if(false, "timeout"), TerminalSignal::default());
+    assert_eq!(TerminalSignal::truncated_if(true, "timeout"), TerminalSignal::truncated("timeout"));
+    assert_eq!(TerminalSignal::terminated_if(true, "goal"), TerminalSignal::terminated("goal"));
+
+    // timeouts are truncations
+    let mut timeout = TimeoutCondition::new(2);
+    timeout.reset(&state);
+    assert_eq!(timeout.terminal_signal(&state), TerminalSignal::default());
+    assert_eq!(timeout.terminal_signal(&state), TerminalSignal::truncated("TimeoutCondition"));
+
+    // goals are terminations
+    let mut goal_scored = GoalScoredCondition::new();
+    goal_scored.reset(&state);
+    assert!(!goal_scored.terminal_signal(&state).is_done());
+    state.blue_score += 1;
+    assert_eq!(goal_scored.terminal_signal(&state), TerminalSignal::terminated("GoalScoredCondition"));
+
+    // combined conditions should report both if both happen on the same step
+    let term_conds: Vec<Box<dyn TerminalCondition>> = vec![Box::new(TimeoutCondition::new(1)), Box::new(GoalScoredCondition::new())];
+    let mut combined = CombinedTerminalConditions::new(term_conds);
+    let mut state = GameState::new();
+    combined.reset(&state);
+    state.orange_score += 1;
+    let signal = combined.terminal_signal(&state);
+    assert!(signal.terminated && signal.truncated);
+    assert_eq!(signal.reasons, vec!["TimeoutCondition", "GoalScoredCondition"]);
+}
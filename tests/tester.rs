@@ -109,6 +109,7 @@ fn general_test() {
         gravity: 1.,
         boost_consumption: 1.,
         car_config: CarConfig::octane(),
+        ..Default::default()
     };
     let game_config = make::MakeConfig {
         game_config: config,
@@ -123,8 +124,9 @@ fn general_test() {
         use_single_obs: true,
         action_parser: act_parse,
         state_setter: state_set, 
+        ..Default::default()
     };
-    let mut gym = make::make(game_config, None);
+    let mut gym = make::make(game_config, None).unwrap();
 
     // let obs = gym.reset(None, None);
     // last_state;
@@ -322,7 +324,7 @@ fn general_test() {
 
     // now let's make sure blue goals are working ---------------------------------------------------------------------------------------------------
     gym._game_match._state_setter = Box::new(BlueGoalStateTester::new());
-    gym.reset(None, None);
+    gym.reset(None, None).unwrap();
     // gym.step(actions.clone());
 
     let mut state_vec: Vec<GameState> = Vec::new();
@@ -333,12 +335,12 @@ fn general_test() {
     let mut last_blue_score_tick = 0;
     let mut last_done_tick = 0;
     for _i in 0..(120 * 50) {
-        let (_obs, reward, done, _info) = gym.step(actions.clone());
+        let (_obs, reward, done, _info) = gym.step(actions.clone()).unwrap().into_legacy();
         let mut state = gym._prev_state.clone();
         if done {
             assert!(last_done_tick + ((tick_skip*2) as u64) < state.tick_num, "scored within {tick_skip}*2 ticks which is too close");
             last_done_tick = state.tick_num;
-            gym.reset(None, None);
+            gym.reset(None, None).unwrap();
             state = gym._prev_state.clone();
         }
         if state.blue_score != last_blue_score {
@@ -383,7 +385,7 @@ fn general_test() {
 
     // now let's make sure orange goals are working ---------------------------------------------------------------------------------------------------
     gym._game_match._state_setter = Box::new(OrangeGoalStateTester::new());
-    gym.reset(None, None);
+    gym.reset(None, None).unwrap();
     // gym.step(actions.clone());
 
     let mut state_vec: Vec<GameState> = Vec::new();
@@ -392,7 +394,7 @@ fn general_test() {
     let start_time = Instant::now();
     let mut last_orange_score = 0;
     for _i in 0..(120 * 50) {
-        let (_obs, reward, done, _info) = gym.step(actions.clone());
+        let (_obs, reward, done, _info) = gym.step(actions.clone()).unwrap().into_legacy();
         let state = gym._prev_state.clone();
         if state.orange_score != last_orange_score {
             // if the done flag was not shown then we messed up
@@ -402,7 +404,7 @@ fn general_test() {
             last_orange_score = state.orange_score;
         }
         if done {
-            gym.reset(None, None);
+            gym.reset(None, None).unwrap();
             // state = gym._prev_state.clone();
         }
         // let info_val = *_info.get("result").unwrap();
@@ -430,7 +432,7 @@ fn general_test() {
 
     // now let's make sure ball touches are working
     gym._game_match._state_setter = Box::new(AgentBallHitStateTester::new());
-    gym.reset(None, None);
+    gym.reset(None, None).unwrap();
     // gym.step(actions.clone());
     actions = vec![vec![2., 0., 0., 0., 0., 0., 0., 0.]];
 
@@ -442,7 +444,7 @@ fn general_test() {
     let mut touch_counter = 0;
     // let mut prev_distance;
     for _i in 0..(120 * 50) {
-        let (_obs, reward, done, _info) = gym.step(actions.clone());
+        let (_obs, reward, done, _info) = gym.step(actions.clone()).unwrap().into_legacy();
         let state = gym._prev_state.clone();
         if state.orange_score != last_orange_score {
             // if the done flag was not shown then we messed up
@@ -461,7 +463,7 @@ fn general_test() {
             //     .sqrt();
         }
         if done {
-            gym.reset(None, None);
+            gym.reset(None, None).unwrap();
             // state = gym._prev_state.clone();
         }
         // let info_val = *_info.get("result").unwrap();
@@ -511,6 +513,7 @@ fn general_test() {
         gravity: 1.,
         boost_consumption: 1.,
         car_config: CarConfig::octane(),
+        ..Default::default()
     };
     let game_config = make::MakeConfig {
         game_config: config,
@@ -520,11 +523,12 @@ fn general_test() {
         use_single_obs: true,
         action_parser: act_parse,
         state_setter: state_set, 
+        ..Default::default()
     };
-    let mut gym = make::make(game_config, None);
+    let mut gym = make::make(game_config, None).unwrap();
 
     gym._game_match._state_setter = Box::new(DemoStateTester::new());
-    gym.reset(None, None);
+    gym.reset(None, None).unwrap();
     // gym.step(actions.clone());
 
     let mut state_vec: Vec<GameState> = Vec::new();
@@ -540,10 +544,10 @@ fn general_test() {
     // let mut last_blue_score_tick = 0;
     // let mut last_done_tick = 0;
     for _i in 0..(120 * 50) {
-        let (_obs, reward, done, _info) = gym.step(actions2.clone());
+        let (_obs, reward, done, _info) = gym.step(actions2.clone()).unwrap().into_legacy();
         let mut state = gym._prev_state.clone();
         if done {
-            gym.reset(None, None);
+            gym.reset(None, None).unwrap();
             state = gym._prev_state.clone();
         }
         if state.players[0].is_demoed {
@@ -87,6 +87,7 @@ fn render_test() {
         gravity: 1.,
         boost_consumption: 1.,
         car_config: CarConfig::octane(),
+        ..Default::default()
     };
     let game_config = make::MakeConfig {
         game_config: config,
@@ -96,17 +97,18 @@ fn render_test() {
         use_single_obs: true,
         action_parser: act_parse,
         state_setter: state_set, 
+        ..Default::default()
     };
     let render_config = make::RenderConfig {
         render: true,
         // 3x as fast as realtime (120 tps)
         update_rate: 360.
     };
-    let mut gym = make::make(game_config, Some(render_config));
+    let mut gym = make::make(game_config, Some(render_config)).unwrap();
 
     // now let's make sure blue goals are working ---------------------------------------------------------------------------------------------------
     gym._game_match._state_setter = Box::new(BlueGoalStateTester::new());
-    gym.reset(None, None);
+    gym.reset(None, None).unwrap();
 
     let mut state_vec: Vec<GameState> = Vec::new();
     state_vec.push(gym._prev_state.clone());
@@ -116,13 +118,13 @@ fn render_test() {
     let mut last_blue_score_tick = 0;
     let mut last_done_tick = 0;
     for _i in 0..((120/tick_skip) * 5) {
-        let (_obs, reward, done, _info) = gym.step(actions.clone());
+        let (_obs, reward, done, _info) = gym.step(actions.clone()).unwrap().into_legacy();
         let mut state = gym._prev_state.clone();
 
         if done {
             assert!(last_done_tick + ((tick_skip*2) as u64) < state.tick_num, "scored within {tick_skip}*2 ticks which is too close");
             last_done_tick = state.tick_num;
-            gym.reset(None, None);
+            gym.reset(None, None).unwrap();
             state = gym._prev_state.clone();
         }
 
@@ -160,7 +162,7 @@ fn render_test() {
 
     // now let's make sure orange goals are working ---------------------------------------------------------------------------------------------------
     gym._game_match._state_setter = Box::new(OrangeGoalStateTester::new());
-    gym.reset(None, None);
+    gym.reset(None, None).unwrap();
 
     let mut state_vec: Vec<GameState> = Vec::new();
     state_vec.push(gym._prev_state.clone());
@@ -168,7 +170,7 @@ fn render_test() {
     let start_time = Instant::now();
     let mut last_orange_score = 0;
     for _i in 0..((120/tick_skip) * 5) {
-        let (_obs, reward, done, _info) = gym.step(actions.clone());
+        let (_obs, reward, done, _info) = gym.step(actions.clone()).unwrap().into_legacy();
         let state = gym._prev_state.clone();
 
         if state.orange_score != last_orange_score {
@@ -179,7 +181,7 @@ fn render_test() {
         }
 
         if done {
-            gym.reset(None, None);
+            gym.reset(None, None).unwrap();
         }
 
         if _i % 200 == 0 {
@@ -199,7 +201,7 @@ fn render_test() {
 
     // now let's make sure ball touches are working
     gym._game_match._state_setter = Box::new(AgentBallHitStateTester::new());
-    gym.reset(None, None);
+    gym.reset(None, None).unwrap();
     actions = vec![vec![2., 0., 0., 0., 0., 0., 0., 0.]];
 
     let mut state_vec: Vec<GameState> = Vec::new();
@@ -208,7 +210,7 @@ fn render_test() {
     let mut last_orange_score = 0;
     let mut touch_counter = 0;
     for _i in 0..((120/tick_skip) * 50) {
-        let (_obs, reward, done, _info) = gym.step(actions.clone());
+        let (_obs, reward, done, _info) = gym.step(actions.clone()).unwrap().into_legacy();
         let state = gym._prev_state.clone();
         if state.orange_score != last_orange_score {
             last_orange_score = state.orange_score;
@@ -217,7 +219,7 @@ fn render_test() {
             touch_counter += 1;
         }
         if done {
-            gym.reset(None, None);
+            gym.reset(None, None).unwrap();
         }
 
         if _i % 200 == 0 {
@@ -233,7 +235,7 @@ fn render_test() {
     let fps = (120. * 360.) / seconds_elapsed;
     println!("fps: {fps}");
     println!("touches: {touch_counter}");
-    gym.close_renderer();
+    gym.close_renderer().unwrap();
 
     // now let's make sure demos are working ---------------------------------------------------------------------------------------------------
     let term_cond = Box::new(CombinedTerminalConditions::new(1));
@@ -253,6 +255,7 @@ fn render_test() {
         gravity: 1.,
         boost_consumption: 1.,
         car_config: CarConfig::octane(),
+        ..Default::default()
     };
     let game_config = make::MakeConfig {
         game_config: config,
@@ -262,11 +265,12 @@ fn render_test() {
         use_single_obs: true,
         action_parser: act_parse,
         state_setter: state_set, 
+        ..Default::default()
     };
-    let mut gym = make::make(game_config, Some(render_config));
+    let mut gym = make::make(game_config, Some(render_config)).unwrap();
 
     gym._game_match._state_setter = Box::new(DemoStateTester::new());
-    gym.reset(None, None);
+    gym.reset(None, None).unwrap();
 
     let mut state_vec: Vec<GameState> = Vec::new();
     state_vec.push(gym._prev_state.clone());
@@ -279,10 +283,10 @@ fn render_test() {
     let mut last_bumped_id = 0;
     let mut last_bumpee_id = 0;
     for _i in 0..((120/tick_skip) * 50) {
-        let (_obs, reward, done, _info) = gym.step(actions2.clone());
+        let (_obs, reward, done, _info) = gym.step(actions2.clone()).unwrap().into_legacy();
         let mut state = gym._prev_state.clone();
         if done {
-            gym.reset(None, None);
+            gym.reset(None, None).unwrap();
             state = gym._prev_state.clone();
         }
         if state.players[0].is_demoed {
@@ -314,5 +318,5 @@ fn render_test() {
     println!("fps: {fps}");
     println!("rewards: {rew_val}");
 
-    gym.close_renderer();
+    gym.close_renderer().unwrap();
 }
@@ -0,0 +1,83 @@
+use rlgym_sim_rs::conditionals::common_conditions::{GoalScoredCondition, TimeoutCondition};
+use rlgym_sim_rs::conditionals::extra_conditions::CombinedTerminalConditions;
+use rlgym_sim_rs::envs::game_match::GameConfig;
+use rlgym_sim_rs::error::Error;
+use rlgym_sim_rs::make;
+use rlgym_sim_rs::reward_functions::common_rewards::misc_rewards::EventReward;
+use rlgym_sim_rs::{TerminalCondition, VecGym};
+
+mod common;
+
+fn make_config(team_size: usize, spawn_opponents: bool) -> make::MakeConfig {
+    let term_conds: Vec<Box<dyn TerminalCondition>> = vec![Box::new(TimeoutCondition::new(20)), Box::new(GoalScoredCondition::new())];
+    common::test_builder(GameConfig { spawn_opponents, team_size, ..Default::default() })
+        .with_terminal_condition(Box::new(CombinedTerminalConditions::new(term_conds)))
+        .with_reward_fn(Box::new(EventReward::new(None, None, None, None, None, None, None, None)))
+        .build_config()
+        .unwrap()
+}
+
+#[test]
+fn vec_gym_test() {
+    rocketsim_rs::init(None, false);
+
+    // env 0 and 2 are 1v1, env 1 is 1v0 so that the agent counts differ per env
+    let mut vec_gym = VecGym::new(3, 2, |env_idx| make_config(1, env_idx != 1)).unwrap();
+    assert_eq!(vec_gym.n_agents_per_env(), &[2, 1, 2]);
+    assert_eq!(vec_gym.total_agents(), 5);
+
+    let actions = vec![vec![1., 0., 0., 0., 0., 0., 0., 0.]; vec_gym.total_agents()];
+    assert!(matches!(vec_gym.step(actions.clone()), Err(Error::VecGym(_))));
+
+    let obs = vec_gym.reset(Some(0)).unwrap();
+    assert!(matches!(vec_gym.step(actions[..1].to_vec()), Err(Error::ActionCount { actions: 1, players: 5 })));
+    assert!(matches!(vec_gym.step_wait(), Err(Error::VecGym(_))));
+    let obs_size = vec_gym.obs_size();
+    assert!(obs_size > 0);
+    assert_eq!(obs.len(), 5 * obs_size, "flat obs was not of size total_agents * obs_size");
+
+    let mut saw_done = false;
+    for _ in 0..25 {
+        let result = vec_gym.step(actions.clone()).unwrap();
+        assert_eq!(result.obs.len(), 5 * obs_size);
+        assert_eq!(result.rewards.len(), 5);
+        assert_eq!(result.terminated.len(), 5);
+        assert_eq!(result.truncated.len(), 5);
+        assert_eq!(result.infos.len(), 3);
+        assert_eq!(result.terminal_obs.len(), 3);
+
+        // terminated/truncated are repeated for every agent of an env and terminal obs are only given for finished envs
+        let mut agent_idx = 0;
+        for (env_idx, n_agents) in vec_gym.n_agents_per_env().iter().enumerate() {
+            let env_dones: Vec<bool> = (agent_idx..agent_idx + n_agents)
+                .map(|i| result.terminated[i] || result.truncated[i])
+                .collect();
+            assert!(env_dones.iter().all(|done| *done == env_dones[0]));
+            assert_eq!(env_dones[0], result.terminal_obs[env_idx].is_some());
+            if let Some(terminal_obs) = &result.terminal_obs[env_idx] {
+                assert_eq!(terminal_obs.len(), n_agents * obs_size);
+                saw_done = true;
+            }
+            agent_idx += n_agents;
+        }
+    }
+    assert!(saw_done, "timeout condition should have ended an episode");
+
+    // the seeds of the sub-envs are derived from the seed so they can not overflow
+    vec_gym.reset(Some(u64::MAX)).unwrap();
+
+    vec_gym.close();
+
+    // the obs length of the advanced obs depends on the team size so it differs between a 1v1 and a 2v2 without padding
+    let mut vec_gym = VecGym::new(2, 1, |env_idx| make_config(env_idx + 1, true)).unwrap();
+    assert!(matches!(vec_gym.reset(Some(0)), Err(Error::ObsLength { .. })));
+}
+
+#[test]
+fn vec_gym_worker_exit_test() {
+    assert!(matches!(VecGym::new(0, 1, |_| make_config(1, true)), Err(Error::VecGym(_))));
+
+    // a panic on a worker thread is returned as an error instead of taking down the caller
+    let vec_gym = VecGym::new(2, 2, |_| -> make::MakeConfig { panic!("config factory panicked") });
+    assert!(matches!(vec_gym, Err(Error::VecGym(_))));
+}